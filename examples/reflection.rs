@@ -0,0 +1,64 @@
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use truster::prelude::*;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut world = World::new();
+
+    let mut floor = Plane::new();
+    floor.set_material(Material::mirror());
+    world.add_shape(Rc::new(floor));
+
+    let mut back_wall = Plane::new();
+    back_wall.set_transform(Matrix::translation(0.0, 0.0, 10.0) * &Matrix::rotation_x(PI / 2.0));
+    back_wall.set_material(
+        Material::builder()
+            .color(Color::new(0.9, 0.9, 1.0))
+            .specular(0.0)
+            .build(),
+    );
+    world.add_shape(Rc::new(back_wall));
+
+    let spheres = [
+        (Tuple::point(-1.5, 0.5, 0.0), 0.5, Color::new(1.0, 0.2, 0.2)),
+        (Tuple::point(0.0, 1.0, 1.0), 1.0, Color::new(0.2, 1.0, 0.3)),
+        (Tuple::point(1.5, 0.5, -0.5), 0.5, Color::new(0.2, 0.4, 1.0)),
+    ];
+    for &(center, radius, color) in spheres.iter() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(
+            Matrix::translation(center.x(), center.y(), center.z())
+                * &Matrix::scaling(radius, radius, radius),
+        );
+        sphere.set_material(
+            Material::builder()
+                .color(color)
+                .diffuse(0.7)
+                .specular(0.3)
+                .build(),
+        );
+        world.add_shape(Rc::new(sphere));
+    }
+
+    let mut glass = Sphere::new();
+    glass.set_transform(Matrix::translation(0.0, 0.4, -1.5) * &Matrix::scale_uniform(0.4));
+    glass.set_material(Material::glass());
+    world.add_shape(Rc::new(glass));
+
+    let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
+    world.add_light(Rc::new(light));
+
+    let camera = Camera::new(Config {
+        hsize: 1000,
+        vsize: 500,
+        from: Tuple::point(0.0, 2.0, -6.0),
+        at: Tuple::point(0.0, 0.75, 0.0),
+        ..Config::default()
+    });
+
+    let canvas = camera.render(&world);
+    canvas.to_ppm(&mut std::io::stdout())?;
+
+    Ok(())
+}
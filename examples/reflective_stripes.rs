@@ -0,0 +1,75 @@
+//! Renders the scene of `stripes.rs`, but with a reflective floor. The ball rests on the floor,
+//! because a floating ball only shows its unlit bottom in the reflection.
+//!
+//! The scene is also used by the integration test in `tests/reflection.rs`.
+
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use truster::camera::{Camera, Config};
+use truster::color::Color;
+use truster::light::PointLight;
+use truster::material::Material;
+use truster::matrix::Matrix;
+use truster::shape::{plane::Plane, sphere::Sphere, Shape};
+use truster::texture::{stripe::Stripe, Texture};
+use truster::tuple::Tuple;
+use truster::world::World;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let canvas = camera(1600, 900).render(&world(0.4));
+    canvas.to_ppm(&mut std::io::stdout())?;
+
+    Ok(())
+}
+
+fn stripes() -> Rc<dyn Texture> {
+    Rc::new(Stripe::colors(
+        Color::new(0.1, 0.8, 0.3),
+        Color::new(0.1, 0.3, 0.8),
+    ))
+}
+
+/// Returns the world of the scene, with a floor which reflects `reflective` of the light. The
+/// floor is the first shape of the world.
+pub fn world(reflective: f64) -> World {
+    let mut world = World::new();
+
+    let mut floor = Plane::new();
+    floor.set_material(
+        Material::builder()
+            .texture(stripes())
+            .reflective(reflective)
+            .build(),
+    );
+    world.add_shape(Rc::new(floor));
+
+    let mut wall = Plane::new();
+    wall.set_transform(Matrix::rotation_x(PI / 2.0));
+    wall.set_material(Material::builder().texture(stripes()).build());
+    world.add_shape(Rc::new(wall));
+
+    let mut ball_text = Stripe::colors(Color::new(0.8, 0.3, 0.1), Color::new(0.7, 0.4, 0.1));
+    ball_text.set_transform(Matrix::rotation_y(PI / 4.0) * &Matrix::scale_uniform(0.1));
+    let mut ball = Sphere::new();
+    ball.set_transform(Matrix::translation(0.0, 0.75, 2.0) * &Matrix::scale_uniform(0.75));
+    ball.set_material(Material::builder().texture(Rc::new(ball_text)).build());
+    world.add_shape(Rc::new(ball));
+
+    let light = PointLight::new(Tuple::point(-5.0, 10.0, 5.0), Color::new(1.0, 1.0, 1.0));
+    world.add_light(Rc::new(light));
+
+    world
+}
+
+/// Returns the camera of the scene, which renders images of `hsize` by `vsize` pixels. It is lower
+/// than in `stripes.rs`, so the reflection of the ball is in view.
+pub fn camera(hsize: usize, vsize: usize) -> Camera {
+    Camera::new(Config {
+        hsize,
+        vsize,
+        from: Tuple::point(0.0, 1.5, 9.0),
+        at: Tuple::point(0.0, 0.75, 0.0),
+        ..Config::default()
+    })
+}
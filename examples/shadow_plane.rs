@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::io;
+use std::rc::Rc;
+
+use truster::camera::{Camera, Config};
+use truster::color::Color;
+use truster::light::PointLight;
+use truster::material::Material;
+use truster::matrix::Matrix;
+use truster::shape::{sphere::Sphere, Shape};
+use truster::texture::{checker2d::Checker2d, Texture};
+use truster::tuple::Tuple;
+use truster::world::World;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut world = World::new();
+
+    // A huge, flattened sphere as the floor. Far from the origin, hit points are not exact, so
+    // without an offset which grows with the distance this speckles with shadow acne.
+    let mut checker = Checker2d::colors(Color::new(0.9, 0.9, 0.9), Color::new(0.2, 0.2, 0.2));
+    checker.set_transform(Matrix::scaling(1.0 / 1000.0, 1.0, 1.0 / 1000.0));
+    let mut floor = Sphere::new();
+    floor.set_transform(Matrix::scaling(1000.0, 0.01, 1000.0));
+    floor.set_material(
+        Material::builder()
+            .texture(Rc::new(checker))
+            .specular(0.0)
+            .build(),
+    );
+    world.add_shape(Rc::new(floor));
+
+    for (i, &color) in [Color::RED, Color::GREEN, Color::BLUE].iter().enumerate() {
+        let mut ball = Sphere::new();
+        ball.set_transform(Matrix::translation(2.5 * (i as f64 - 1.0), 1.0, 0.0));
+        ball.set_material(Material::with_color(color));
+        world.add_shape(Rc::new(ball));
+    }
+
+    let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
+    world.add_light(Rc::new(light));
+
+    let camera = Camera::new(Config {
+        hsize: 1000,
+        vsize: 500,
+        from: Tuple::point(0.0, 3.0, -8.0),
+        at: Tuple::point(0.0, 1.0, 0.0),
+        ..Config::default()
+    });
+
+    let image = camera.render(&world);
+    image.to_ppm(&mut io::stdout())?;
+
+    Ok(())
+}
@@ -2,14 +2,13 @@ use std::rc::Rc;
 
 use truster::canvas::Canvas;
 use truster::color::Color;
-use truster::intersection::Hit;
 use truster::light::PointLight;
 use truster::material::Material;
 use truster::matrix::Matrix;
 use truster::ray::Ray;
 use truster::shape::{sphere::Sphere, Shape};
-use truster::texture::solid_color::SolidColor;
 use truster::tuple::Tuple;
+use truster::world::World;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let ray_origin = Tuple::point(0.0, 0.0, -5.0);
@@ -25,11 +24,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     shape.set_transform(
         Matrix::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0) * &Matrix::scaling(0.5, 1.0, 1.0),
     );
-    shape.set_material(Material {
-        texture: Rc::new(SolidColor::new(Color::new(1.0, 0.2, 1.0))),
-        ..Material::default()
-    });
-    let shape: Rc<dyn Shape> = Rc::new(shape);
+    shape.set_material(Material::builder().color(Color::new(1.0, 0.2, 1.0)).build());
+    let mut world = World::new();
+    world.add_shape(Rc::new(shape));
 
     let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
@@ -42,19 +39,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let position = Tuple::point(world_x, world_y, wall_z);
 
             let ray = Ray::new(ray_origin, (position - ray_origin).normalized());
-            let intersections = shape.intersect(&ray);
-
-            if let Some(hit) = intersections.hit() {
-                let point = ray.at(hit.t());
-                let normal = hit.shape().normal_at(point);
-                let eye = -ray.direction();
-
-                let color = hit.shape().material().lighting(
-                    Rc::clone(&shape),
+            if let Some(rec) = world.hit(&ray) {
+                let color = rec.shape().material().lighting(
+                    rec.shape(),
                     &light,
-                    point,
-                    eye,
-                    normal,
+                    rec.point(),
+                    rec.eye(),
+                    rec.normal(),
                     false,
                 );
 
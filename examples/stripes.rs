@@ -17,34 +17,33 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut world = World::new();
 
     let mut floor = Plane::new();
-    floor.set_material(Material {
-        texture: Rc::new(Stripe::colors(
-            Color::new(0.1, 0.8, 0.3),
-            Color::new(0.1, 0.3, 0.8),
-        )),
-        ..Material::default()
-    });
+    floor.set_material(
+        Material::builder()
+            .texture(Rc::new(Stripe::colors(
+                Color::new(0.1, 0.8, 0.3),
+                Color::new(0.1, 0.3, 0.8),
+            )))
+            .build(),
+    );
     world.add_shape(Rc::new(floor));
 
     let mut wall = Plane::new();
     wall.set_transform(Matrix::rotation_x(PI / 2.0));
-    wall.set_material(Material {
-        texture: Rc::new(Stripe::colors(
-            Color::new(0.1, 0.8, 0.3),
-            Color::new(0.1, 0.3, 0.8),
-        )),
-        ..Material::default()
-    });
+    wall.set_material(
+        Material::builder()
+            .texture(Rc::new(Stripe::colors(
+                Color::new(0.1, 0.8, 0.3),
+                Color::new(0.1, 0.3, 0.8),
+            )))
+            .build(),
+    );
     world.add_shape(Rc::new(wall));
 
     let mut ball_text = Stripe::colors(Color::new(0.8, 0.3, 0.1), Color::new(0.7, 0.4, 0.1));
-    ball_text.set_transform(Matrix::rotation_y(PI / 4.0) * &Matrix::scaling(0.1, 0.1, 0.1));
+    ball_text.set_transform(Matrix::rotation_y(PI / 4.0) * &Matrix::scale_uniform(0.1));
     let mut ball = Sphere::new();
-    ball.set_transform(Matrix::translation(0.0, 2.0, 2.0) * &Matrix::scaling(0.75, 0.75, 0.75));
-    ball.set_material(Material {
-        texture: Rc::new(ball_text),
-        ..Material::default()
-    });
+    ball.set_transform(Matrix::translation(0.0, 2.0, 2.0) * &Matrix::scale_uniform(0.75));
+    ball.set_material(Material::builder().texture(Rc::new(ball_text)).build());
     world.add_shape(Rc::new(ball));
 
     let light = PointLight::new(Tuple::point(-5.0, 10.0, 5.0), Color::new(1.0, 1.0, 1.0));
@@ -0,0 +1,106 @@
+//! Holds the [Background] trait, as well as some common backgrounds which implement it.
+//!
+//! A background gives the color of rays which don't hit any shape in a
+//! [World](crate::world::World).
+//!
+//! # Examples
+//!
+//! ```
+//! # use truster::background::GradientBackground;
+//! use std::rc::Rc;
+//! use truster::color::Color;
+//! use truster::world::World;
+//!
+//! let mut world = World::new();
+//! world.set_background(Rc::new(GradientBackground::new(
+//!     Color::WHITE,
+//!     Color::new(0.5, 0.7, 1.0),
+//! )));
+//! ```
+
+use crate::color::Color;
+use crate::ray::Ray;
+
+/// Returns the color seen along a ray which hits nothing. [Background::color_for_ray] should only
+/// look at the direction of `ray`, so the background seems infinitely far away.
+pub trait Background {
+    fn color_for_ray(&self, ray: &Ray) -> Color;
+}
+
+/// A background which has the same color in every direction.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SolidBackground(pub Color);
+
+impl Background for SolidBackground {
+    fn color_for_ray(&self, _: &Ray) -> Color {
+        self.0
+    }
+}
+
+/// A background which blends between two colors from straight down to straight up, like a sky.
+#[derive(Debug, Clone, Copy)]
+pub struct GradientBackground {
+    bottom: Color,
+    top: Color,
+}
+
+impl GradientBackground {
+    /// Returns a new gradient which is `bottom` for rays pointing straight down, and `top` for
+    /// rays pointing straight up. Horizontal rays get the average of both.
+    pub fn new(bottom: Color, top: Color) -> Self {
+        Self { bottom, top }
+    }
+
+    /// Returns the color for rays pointing straight down.
+    pub fn bottom(&self) -> Color {
+        self.bottom
+    }
+
+    /// Returns the color for rays pointing straight up.
+    pub fn top(&self) -> Color {
+        self.top
+    }
+}
+
+impl Background for GradientBackground {
+    fn color_for_ray(&self, ray: &Ray) -> Color {
+        let t = (ray.direction().normalized().y() + 1.0) / 2.0;
+        self.bottom * (1.0 - t) + self.top * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuple::Tuple;
+
+    fn ray(direction: Tuple) -> Ray {
+        Ray::new(Tuple::ORIGIN, direction)
+    }
+
+    #[test]
+    fn solid() {
+        let background = SolidBackground(Color::RED);
+        assert_eq!(
+            background.color_for_ray(&ray(Tuple::vector(0.0, 1.0, 0.0))),
+            Color::RED
+        );
+        assert_eq!(
+            background.color_for_ray(&ray(Tuple::vector(1.0, -1.0, 0.0))),
+            Color::RED
+        );
+    }
+
+    #[test]
+    fn gradient() {
+        let background = GradientBackground::new(Color::BLACK, Color::WHITE);
+        let up = background.color_for_ray(&ray(Tuple::vector(0.0, 1.0, 0.0)));
+        let down = background.color_for_ray(&ray(Tuple::vector(0.0, -1.0, 0.0)));
+        let side = background.color_for_ray(&ray(Tuple::vector(0.0, 0.0, 3.0)));
+
+        assert_ne!(up, down);
+        assert_eq!(up, Color::WHITE);
+        assert_eq!(down, Color::BLACK);
+        assert_eq!(side, Color::gray(0.5));
+    }
+}
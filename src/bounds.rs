@@ -0,0 +1,273 @@
+//! Holds the [Bounds] struct, an axis aligned bounding box.
+//!
+//! # Examples
+//!
+//! Bounds grow to fit the points which are added to them:
+//! ```
+//! # use truster::bounds::Bounds;
+//! use truster::tuple::Tuple;
+//!
+//! let mut bounds = Bounds::empty();
+//! bounds.add_point(Tuple::point(-5.0, 2.0, 0.0));
+//! bounds.add_point(Tuple::point(7.0, 0.0, -3.0));
+//! assert_eq!(bounds.min(), Tuple::point(-5.0, 0.0, -3.0));
+//! assert_eq!(bounds.max(), Tuple::point(7.0, 2.0, 0.0));
+//! ```
+//!
+//! Shapes which go on forever, like planes, have infinite bounds along some axes:
+//! ```
+//! # use truster::bounds::Bounds;
+//! use truster::shape::{Plane, Shape, Sphere};
+//! use truster::tuple::Tuple;
+//!
+//! assert!(Plane::new().bounds().is_infinite());
+//! assert!(!Sphere::new().bounds().is_infinite());
+//! ```
+
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+/// A box with sides parallel to the axes. Any component of its corners can be infinite. See the
+/// module's documentation for more info.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Bounds {
+    min: Tuple,
+    max: Tuple,
+}
+
+impl Bounds {
+    /// Returns new bounds between the points `min` and `max`.
+    pub fn new(min: Tuple, max: Tuple) -> Self {
+        Self { min, max }
+    }
+
+    /// Returns bounds which contain no points at all. Adding a point to them gives bounds
+    /// containing just that point.
+    pub fn empty() -> Self {
+        Self::new(
+            Tuple::point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            Tuple::point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        )
+    }
+
+    /// Returns bounds which contain all of space.
+    pub fn infinite() -> Self {
+        Self::new(
+            Tuple::point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        )
+    }
+
+    /// Returns `self`'s minimum corner.
+    pub fn min(&self) -> Tuple {
+        self.min
+    }
+
+    /// Returns `self`'s maximum corner.
+    pub fn max(&self) -> Tuple {
+        self.max
+    }
+
+    /// Returns whether `self` contains no points.
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|i| self.min[i] > self.max[i])
+    }
+
+    /// Returns whether `self` extends infinitely along any axis.
+    pub fn is_infinite(&self) -> bool {
+        !self.is_empty() && (0..3).any(|i| self.min[i].is_infinite() || self.max[i].is_infinite())
+    }
+
+    /// Returns the center of `self`.
+    pub fn centroid(&self) -> Tuple {
+        self.min.lerp(self.max, 0.5)
+    }
+
+    /// Grows `self` so it contains `point`.
+    pub fn add_point(&mut self, point: Tuple) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    /// Grows `self` so it contains `other`.
+    pub fn merge(&mut self, other: &Self) {
+        if other.is_empty() {
+            return;
+        }
+        self.add_point(other.min);
+        self.add_point(other.max);
+    }
+
+    /// Returns whether `point` is inside `self`, or on its boundary.
+    pub fn contains_point(&self, point: Tuple) -> bool {
+        (0..3).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    /// Returns the smallest bounds which contain `self` transformed by `transform`.
+    ///
+    /// Each axis of the result is computed from the intervals of `self` directly, instead of by
+    /// transforming the corners. This way an infinite axis which the transform does not mix into
+    /// another axis stays confined to its own axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::bounds::Bounds;
+    /// use truster::matrix::Matrix;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let bounds = Bounds::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0));
+    /// let transform = Matrix::translation(1.0, 0.0, 0.0) * &Matrix::scaling(2.0, 1.0, 1.0);
+    /// let transformed = bounds.transform(&transform);
+    /// assert_eq!(transformed.min(), Tuple::point(-1.0, -1.0, -1.0));
+    /// assert_eq!(transformed.max(), Tuple::point(3.0, 1.0, 1.0));
+    /// ```
+    pub fn transform(&self, transform: &Matrix) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+
+        let mut min = Tuple::ORIGIN;
+        let mut max = Tuple::ORIGIN;
+        for i in 0..3 {
+            min[i] = transform[[i, 3]];
+            max[i] = transform[[i, 3]];
+            for j in 0..3 {
+                let factor = transform[[i, j]];
+                if factor == 0.0 {
+                    continue;
+                }
+                let a = factor * self.min[j];
+                let b = factor * self.max[j];
+                min[i] += a.min(b);
+                max[i] += a.max(b);
+            }
+        }
+        Self::new(min, max)
+    }
+
+    /// Returns whether the line through `ray` passes through `self`. Points behind the origin of
+    /// `ray` count as well, so no intersection of a shape inside `self` is missed.
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let mut t_min = f64::NEG_INFINITY;
+        let mut t_max = f64::INFINITY;
+        for i in 0..3 {
+            let origin = ray.origin()[i];
+            let direction = ray.direction()[i];
+            if direction.abs() < EPSILON {
+                if origin < self.min[i] || origin > self.max[i] {
+                    return false;
+                }
+                continue;
+            }
+
+            let t1 = (self.min[i] - origin) / direction;
+            let t2 = (self.max[i] - origin) / direction;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+        t_min <= t_max
+    }
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit() -> Bounds {
+        Bounds::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn empty() {
+        let bounds = Bounds::empty();
+        assert!(bounds.is_empty());
+        assert!(!bounds.is_infinite());
+        assert!(!bounds.contains_point(Tuple::point(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn merge() {
+        let mut bounds = unit();
+        bounds.merge(&Bounds::new(
+            Tuple::point(0.0, 2.0, -3.0),
+            Tuple::point(0.5, 4.0, 0.0),
+        ));
+        assert_eq!(bounds.min(), Tuple::point(-1.0, -1.0, -3.0));
+        assert_eq!(bounds.max(), Tuple::point(1.0, 4.0, 1.0));
+
+        let before = bounds;
+        bounds.merge(&Bounds::empty());
+        assert_eq!(bounds, before);
+    }
+
+    #[test]
+    fn contains_point() {
+        let bounds = unit();
+        assert!(bounds.contains_point(Tuple::point(0.0, 0.0, 0.0)));
+        assert!(bounds.contains_point(Tuple::point(1.0, -1.0, 1.0)));
+        assert!(!bounds.contains_point(Tuple::point(1.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn transform_rotation() {
+        let transform = Matrix::rotation_y(std::f64::consts::FRAC_PI_4);
+        let bounds = unit().transform(&transform);
+        let sqrt2 = 2.0_f64.sqrt();
+        for i in [0, 2] {
+            assert!((bounds.min()[i] + sqrt2).abs() < 1e-6);
+            assert!((bounds.max()[i] - sqrt2).abs() < 1e-6);
+        }
+        assert_eq!(bounds.min().y(), -1.0);
+        assert_eq!(bounds.max().y(), 1.0);
+    }
+
+    #[test]
+    fn transform_infinite_keeps_finite_axes() {
+        let plane = Bounds::new(
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+        let bounds = plane.transform(&Matrix::translation(1.0, 2.0, 3.0));
+        assert_eq!(bounds.min().y(), 2.0);
+        assert_eq!(bounds.max().y(), 2.0);
+        assert!(bounds.is_infinite());
+        assert!(!bounds.min().x().is_nan());
+    }
+
+    #[test]
+    fn intersects() {
+        let bounds = unit();
+        let z = Tuple::vector(0.0, 0.0, 1.0);
+        assert!(bounds.intersects(&Ray::new(Tuple::point(0.5, 0.5, -5.0), z)));
+        assert!(bounds.intersects(&Ray::new(Tuple::point(0.5, 0.5, 5.0), z)));
+        assert!(!bounds.intersects(&Ray::new(Tuple::point(2.0, 0.5, -5.0), z)));
+        let diagonal = Tuple::vector(1.0, 1.0, 1.0).normalized();
+        assert!(bounds.intersects(&Ray::new(Tuple::point(-3.0, -3.0, -3.0), diagonal)));
+        assert!(!bounds.intersects(&Ray::new(Tuple::point(-3.0, -3.0, 0.0), z)));
+    }
+
+    #[test]
+    fn intersects_infinite() {
+        let plane = Bounds::new(
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+        let down = Tuple::vector(0.0, -1.0, 0.0);
+        assert!(plane.intersects(&Ray::new(Tuple::point(100.0, 5.0, -40.0), down)));
+        let parallel = Tuple::vector(1.0, 0.0, 0.0);
+        assert!(!plane.intersects(&Ray::new(Tuple::point(0.0, 1.0, 0.0), parallel)));
+    }
+}
@@ -0,0 +1,279 @@
+//! Holds the [Bvh] struct, a bounding volume hierarchy which speeds up intersecting a ray with
+//! many shapes.
+//!
+//! A [Bvh] is a standalone structure. [World](crate::world::World) does not build one: it still
+//! intersects every ray with every enabled shape. Build a [Bvh] yourself from the shapes you want
+//! to test rays against, and rebuild it whenever those shapes or their transforms change.
+//!
+//! # Examples
+//!
+//! ```
+//! # use truster::bvh::Bvh;
+//! use truster::matrix::Matrix;
+//! use truster::ray::Ray;
+//! use truster::shape::{Plane, Shape, Sphere};
+//! use truster::tuple::Tuple;
+//! use std::rc::Rc;
+//!
+//! let mut shapes: Vec<Rc<dyn Shape>> = vec![Rc::new(Plane::new())];
+//! for i in 0..10 {
+//!     let mut sphere = Sphere::new();
+//!     sphere.set_transform(Matrix::translation(3.0 * i as f64, 1.0, 0.0));
+//!     shapes.push(Rc::new(sphere));
+//! }
+//! let bvh = Bvh::new(shapes);
+//! assert_eq!(bvh.unbounded().len(), 1);
+//!
+//! let ray = Ray::new(Tuple::point(6.0, 5.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+//! let intersections = bvh.intersect(&ray);
+//! let ts: Vec<_> = intersections.iter().map(|i| i.t()).collect();
+//! assert_eq!(ts, vec![3.0, 5.0, 5.0]);
+//! ```
+
+use std::rc::Rc;
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::ray::Ray;
+use crate::shape::Shape;
+
+/// The most shapes a leaf of a [Bvh] holds before it is split.
+const LEAF_SIZE: usize = 4;
+
+/// A tree of bounding boxes around shapes. A ray is only intersected with the shapes whose boxes
+/// it passes through.
+///
+/// Shapes with infinite bounds, like planes, can't be partitioned sensibly. They are kept in a
+/// separate list of [unbounded](Bvh::unbounded) shapes, which is tested against every ray.
+pub struct Bvh {
+    root: Option<Node>,
+    unbounded: Vec<Rc<dyn Shape>>,
+}
+
+enum Node {
+    Leaf {
+        bounds: Bounds,
+        shapes: Vec<(Bounds, Rc<dyn Shape>)>,
+    },
+    Split {
+        bounds: Bounds,
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+}
+
+impl Bvh {
+    /// Returns a new hierarchy holding `shapes`. Shapes with finite bounds are split recursively
+    /// at the median of their centers, along the axis where the centers are most spread out.
+    /// Shapes with empty bounds, like an empty [Group](crate::shape::Group), can't be hit and are
+    /// left out.
+    pub fn new(shapes: Vec<Rc<dyn Shape>>) -> Self {
+        let mut bounded = Vec::new();
+        let mut unbounded = Vec::new();
+        for shape in shapes {
+            let bounds = shape.parent_space_bounds();
+            if bounds.is_infinite() {
+                unbounded.push(shape);
+            } else if !bounds.is_empty() {
+                bounded.push((bounds, shape));
+            }
+        }
+
+        let root = if bounded.is_empty() {
+            None
+        } else {
+            Some(Node::build(bounded))
+        };
+        Self { root, unbounded }
+    }
+
+    /// Returns the shapes with infinite bounds, which are tested against every ray.
+    pub fn unbounded(&self) -> &[Rc<dyn Shape>] {
+        &self.unbounded
+    }
+
+    /// Returns the bounds of all shapes with finite bounds.
+    pub fn bounds(&self) -> Bounds {
+        match &self.root {
+            Some(node) => node.bounds(),
+            None => Bounds::empty(),
+        }
+    }
+
+    /// Returns the shapes which `ray` could intersect, which are the unbounded shapes and the
+    /// shapes whose bounds `ray` passes through.
+    pub fn candidates(&self, ray: &Ray) -> Vec<Rc<dyn Shape>> {
+        let mut result: Vec<_> = self.unbounded.iter().map(Rc::clone).collect();
+        if let Some(root) = &self.root {
+            root.candidates(ray, &mut result);
+        }
+        result
+    }
+
+    /// Returns all intersections of `ray` with the shapes in `self`, sorted by distance.
+    pub fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+        let mut result = Vec::new();
+        for shape in self.candidates(ray) {
+            result.append(&mut shape.intersect_rc(&shape, ray));
+        }
+        result.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+}
+
+impl Node {
+    fn build(mut shapes: Vec<(Bounds, Rc<dyn Shape>)>) -> Self {
+        let mut bounds = Bounds::empty();
+        let mut centroids = Bounds::empty();
+        for (shape_bounds, _) in &shapes {
+            bounds.merge(shape_bounds);
+            centroids.add_point(shape_bounds.centroid());
+        }
+
+        let extent = centroids.max() - centroids.min();
+        let axis = (0..3)
+            .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+            .unwrap();
+        if shapes.len() <= LEAF_SIZE || extent[axis] == 0.0 {
+            return Self::Leaf { bounds, shapes };
+        }
+
+        shapes.sort_unstable_by(|(a, _), (b, _)| a.centroid()[axis].total_cmp(&b.centroid()[axis]));
+        let right = shapes.split_off(shapes.len() / 2);
+        Self::Split {
+            bounds,
+            left: Box::new(Self::build(shapes)),
+            right: Box::new(Self::build(right)),
+        }
+    }
+
+    fn bounds(&self) -> Bounds {
+        match self {
+            Self::Leaf { bounds, .. } | Self::Split { bounds, .. } => *bounds,
+        }
+    }
+
+    fn candidates(&self, ray: &Ray, result: &mut Vec<Rc<dyn Shape>>) {
+        if !self.bounds().intersects(ray) {
+            return;
+        }
+        match self {
+            Self::Leaf { shapes, .. } => {
+                for (bounds, shape) in shapes {
+                    if bounds.intersects(ray) {
+                        result.push(Rc::clone(shape));
+                    }
+                }
+            }
+            Self::Split { left, right, .. } => {
+                left.candidates(ray, result);
+                right.candidates(ray, result);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Matrix;
+    use crate::shape::{Group, Plane, Sphere};
+    use crate::tuple::Tuple;
+
+    fn plane_and_spheres() -> (Rc<dyn Shape>, Vec<Rc<dyn Shape>>) {
+        let plane: Rc<dyn Shape> = Rc::new(Plane::new());
+        let mut shapes = vec![Rc::clone(&plane)];
+        for x in 0..10 {
+            for z in 0..10 {
+                let mut sphere = Sphere::new();
+                sphere.set_transform(Matrix::translation(3.0 * x as f64, 1.0, 3.0 * z as f64));
+                shapes.push(Rc::new(sphere));
+            }
+        }
+        (plane, shapes)
+    }
+
+    fn brute_force(shapes: &[Rc<dyn Shape>], ray: &Ray) -> Vec<f64> {
+        let mut ts: Vec<f64> = shapes
+            .iter()
+            .flat_map(|shape| shape.intersect_rc(shape, ray))
+            .map(|i| i.t())
+            .collect();
+        ts.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        ts
+    }
+
+    #[test]
+    fn plane_is_unbounded() {
+        let (plane, shapes) = plane_and_spheres();
+        let bvh = Bvh::new(shapes);
+        assert_eq!(bvh.unbounded().len(), 1);
+        assert!(Rc::ptr_eq(&bvh.unbounded()[0], &plane));
+        assert_eq!(bvh.bounds().min(), Tuple::point(-1.0, 0.0, -1.0));
+        assert_eq!(bvh.bounds().max(), Tuple::point(28.0, 2.0, 28.0));
+    }
+
+    #[test]
+    fn plane_always_tested_and_spheres_culled() {
+        let (plane, shapes) = plane_and_spheres();
+        let bvh = Bvh::new(shapes.clone());
+        let down = Tuple::vector(0.0, -1.0, 0.0);
+
+        // Straight down onto one sphere.
+        let ray = Ray::new(Tuple::point(9.0, 10.0, 12.0), down);
+        let candidates = bvh.candidates(&ray);
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates.iter().any(|shape| Rc::ptr_eq(shape, &plane)));
+        let ts: Vec<_> = bvh.intersect(&ray).iter().map(|i| i.t()).collect();
+        assert_eq!(ts, vec![8.0, 10.0, 10.0]);
+
+        // Between the spheres, only the plane is left.
+        let ray = Ray::new(Tuple::point(10.5, 10.0, 10.5), down);
+        let candidates = bvh.candidates(&ray);
+        assert_eq!(candidates.len(), 1);
+        assert!(Rc::ptr_eq(&candidates[0], &plane));
+        assert_eq!(bvh.intersect(&ray).len(), 1);
+
+        // Far away from all spheres, the plane is still hit.
+        let ray = Ray::new(Tuple::point(-100.0, 10.0, 500.0), down);
+        assert_eq!(bvh.candidates(&ray).len(), 1);
+        assert_eq!(bvh.intersect(&ray)[0].t(), 10.0);
+
+        // A slanted ray hits the same as testing every shape.
+        let ray = Ray::new(
+            Tuple::point(-5.0, 1.0, 0.5),
+            Tuple::vector(1.0, -0.02, 0.3).normalized(),
+        );
+        assert!(bvh.candidates(&ray).len() < shapes.len());
+        let ts: Vec<_> = bvh.intersect(&ray).iter().map(|i| i.t()).collect();
+        assert_eq!(ts, brute_force(&shapes, &ray));
+    }
+
+    #[test]
+    fn only_unbounded() {
+        let bvh = Bvh::new(vec![Rc::new(Plane::new())]);
+        assert!(bvh.bounds().is_empty());
+        let ray = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        assert_eq!(bvh.intersect(&ray).len(), 1);
+    }
+
+    #[test]
+    fn empty_group_left_out() {
+        let mut shapes: Vec<Rc<dyn Shape>> = Vec::new();
+        for i in 0..6 {
+            let mut sphere = Sphere::new();
+            sphere.set_transform(Matrix::translation(3.0 * i as f64, 0.0, 0.0));
+            shapes.push(Rc::new(sphere));
+        }
+        shapes.push(Rc::new(Group::new()));
+        let bvh = Bvh::new(shapes.clone());
+        assert!(bvh.unbounded().is_empty());
+        assert_eq!(bvh.bounds().min(), Tuple::point(-1.0, -1.0, -1.0));
+        assert_eq!(bvh.bounds().max(), Tuple::point(16.0, 1.0, 1.0));
+
+        let ray = Ray::new(Tuple::point(-5.0, 0.0, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+        assert_eq!(bvh.candidates(&ray).len(), 6);
+        let ts: Vec<_> = bvh.intersect(&ray).iter().map(|i| i.t()).collect();
+        assert_eq!(ts, brute_force(&shapes, &ray));
+    }
+}
@@ -1,12 +1,20 @@
 //! Holds the [Camera] struct.
 
+use std::error::Error;
 use std::f64::consts::PI;
+use std::fmt::{self, Display, Formatter};
+use std::rc::Rc;
 
 use crate::canvas::Canvas;
+use crate::color::Color;
 use crate::matrix::Matrix;
 use crate::ray::Ray;
+use crate::sampling::Rng;
+use crate::shape::Shape;
+use crate::stats::RenderStats;
 use crate::tuple::Tuple;
-use crate::world::World;
+use crate::world::{World, MAX_DEPTH};
+use crate::EPSILON;
 
 /// Used for initializing a [Camera].
 pub struct Config {
@@ -22,6 +30,15 @@ pub struct Config {
     pub at: Tuple,
     /// The up direction for the camera.
     pub up: Tuple,
+    /// The width of a pixel divided by its height. With the default of 1, pixels are square.
+    /// Larger values show more of the scene horizontally in the same number of pixels.
+    pub pixel_aspect: f64,
+    /// How many times reflected and refracted rays may bounce. Lower values render faster, which
+    /// is useful for previews. See [World::color_at_depth].
+    pub max_depth: usize,
+    /// Where in each pixel rays are sent through. More than one sample per pixel smooths jagged
+    /// edges (anti-aliasing).
+    pub sample_pattern: SamplePattern,
 }
 
 impl Default for Config {
@@ -30,9 +47,130 @@ impl Default for Config {
             hsize: 100,
             vsize: 100,
             fov: PI / 3.0,
-            from: Tuple::point(0.0, 0.0, 0.0),
-            at: Tuple::point(0.0, 0.0, -1.0),
-            up: Tuple::vector(0.0, 1.0, 0.0),
+            from: Tuple::ORIGIN,
+            at: Tuple::ORIGIN + Tuple::FORWARD,
+            up: Tuple::UP,
+            pixel_aspect: 1.0,
+            max_depth: MAX_DEPTH,
+            sample_pattern: SamplePattern::default(),
+        }
+    }
+}
+
+impl Config {
+    /// The largest pitch [Config::orbit] allows, just under a quarter turn. At a quarter turn, the
+    /// camera would look straight along the up direction, which leaves its orientation undefined.
+    pub const MAX_PITCH: f64 = PI / 2.0 - 0.01;
+
+    /// Returns the default config, with the camera orbiting `target` at `distance`, looking at
+    /// it. With a `yaw` and `pitch` of 0, the camera is at `target + (0, 0, distance)`. `yaw`
+    /// rotates the camera around the vertical axis through `target`, `pitch` raises it above
+    /// (or, when negative, lowers it below) the target. Both are in radians. `pitch` is clamped to
+    /// [Config::MAX_PITCH] in both directions, so the camera never flips over near the poles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::camera::Config;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let target = Tuple::point(1.0, 2.0, 3.0);
+    /// let config = Config::orbit(target, 5.0, 0.0, 0.0);
+    /// assert_eq!(config.from, Tuple::point(1.0, 2.0, 8.0));
+    /// assert_eq!(config.at, target);
+    /// assert_eq!(config.up, Tuple::UP);
+    /// ```
+    pub fn orbit(target: Tuple, distance: f64, yaw: f64, pitch: f64) -> Self {
+        let pitch = pitch.clamp(-Self::MAX_PITCH, Self::MAX_PITCH);
+        let offset = Tuple::vector(
+            pitch.cos() * yaw.sin(),
+            pitch.sin(),
+            pitch.cos() * yaw.cos(),
+        );
+        Self {
+            from: target + offset * distance,
+            at: target,
+            up: Tuple::UP,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the default config, with the field of view of a lens with focal length `focal_mm`
+    /// on a sensor of `sensor_mm`, both in millimeters: `2 * atan(sensor_mm / (2 * focal_mm))`.
+    /// The field of view spans the longer side of the image, so `sensor_mm` is the size of the
+    /// longer side of the sensor, like 36 for a full frame camera.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::camera::Config;
+    /// let normal = Config::from_focal_length(50.0, 36.0);
+    /// let wide = Config::from_focal_length(24.0, 36.0);
+    /// assert!(wide.fov > normal.fov);
+    /// assert_eq!(Config::from_focal_length(18.0, 36.0).fov, std::f64::consts::FRAC_PI_2);
+    /// ```
+    pub fn from_focal_length(focal_mm: f64, sensor_mm: f64) -> Self {
+        Self {
+            fov: 2.0 * (sensor_mm / (2.0 * focal_mm)).atan(),
+            ..Self::default()
+        }
+    }
+}
+
+/// The points within a pixel that a [Camera] sends rays through. The color of the pixel is the
+/// average of the colors seen through them. All patterns are deterministic, so rendering the
+/// same scene twice gives the same image.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SamplePattern {
+    /// A single ray through the center of each pixel. This is the default.
+    #[default]
+    Center,
+    /// A regular grid of `n` by `n` rays in each pixel.
+    Grid(usize),
+    /// Four rays on a grid rotated by about 27 degrees. No two samples share a row or column,
+    /// which handles nearly horizontal and vertical edges better than a 2 by 2 grid.
+    RotatedGrid,
+    /// `samples` rays at random points in each pixel. The points come from a pseudorandom
+    /// generator started from `seed`, so the same seed always gives the same image.
+    Random { samples: usize, seed: u64 },
+}
+
+impl SamplePattern {
+    /// Returns the number of rays per pixel.
+    pub fn samples(&self) -> usize {
+        match *self {
+            Self::Center => 1,
+            Self::Grid(n) => n * n,
+            Self::RotatedGrid => 4,
+            Self::Random { samples, .. } => samples,
+        }
+    }
+
+    /// Fills `offsets` with the points to sample in the next pixel, as fractions of the pixel
+    /// size from its top left corner.
+    fn offsets(&self, rng: &mut Rng, offsets: &mut Vec<(f64, f64)>) {
+        offsets.clear();
+        match *self {
+            Self::Center => offsets.push((0.5, 0.5)),
+            Self::Grid(n) => {
+                let step = 1.0 / n as f64;
+                for j in 0..n {
+                    for i in 0..n {
+                        offsets.push(((i as f64 + 0.5) * step, (j as f64 + 0.5) * step));
+                    }
+                }
+            }
+            Self::RotatedGrid => offsets.extend_from_slice(&[
+                (0.375, 0.125),
+                (0.875, 0.375),
+                (0.125, 0.625),
+                (0.625, 0.875),
+            ]),
+            Self::Random { samples, .. } => {
+                for _ in 0..samples {
+                    offsets.push((rng.next_f64(), rng.next_f64()));
+                }
+            }
         }
     }
 }
@@ -44,18 +182,120 @@ pub struct Camera {
     vsize: usize,
     half_width: f64,
     half_height: f64,
-    pixel_size: f64,
+    pixel_width: f64,
+    pixel_height: f64,
     transform_inverse: Matrix,
+    max_depth: usize,
+    sample_pattern: SamplePattern,
+}
+
+/// The error returned by [Camera::try_new] for a [Config] which can't give a sensible image, and
+/// by [Camera::render_into] for a canvas of the wrong size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraError {
+    /// The field of view is not strictly between 0 and pi. Wider angles can't be projected onto a
+    /// flat image.
+    InvalidFov(f64),
+    /// The image has no pixels, because its width or height is 0.
+    EmptyImage { hsize: usize, vsize: usize },
+    /// The pixel aspect ratio is not finite and positive, so pixels would have no size.
+    InvalidPixelAspect(f64),
+    /// The sample pattern has no samples, so pixels would have no color.
+    NoSamples(SamplePattern),
+    /// The camera looks at the point it is at, so it has no viewing direction.
+    NoViewDirection(Tuple),
+    /// The canvas to render into is not the size of the camera's image.
+    CanvasSize {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
 }
 
+impl Display for CameraError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFov(fov) => {
+                write!(f, "field of view must be between 0 and pi, got {}", fov)
+            }
+            Self::EmptyImage { hsize, vsize } => {
+                write!(f, "image must not be empty, got {}x{} pixels", hsize, vsize)
+            }
+            Self::InvalidPixelAspect(aspect) => write!(
+                f,
+                "pixel aspect ratio must be finite and positive, got {}",
+                aspect
+            ),
+            Self::NoSamples(pattern) => write!(f, "sample pattern {:?} has no samples", pattern),
+            Self::NoViewDirection(from) => {
+                write!(f, "camera can't look at the point it is at, {}", from)
+            }
+            Self::CanvasSize { expected, actual } => write!(
+                f,
+                "canvas must be {}x{} pixels, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+        }
+    }
+}
+
+impl Error for CameraError {}
+
 impl Camera {
     /// Returns a new [Camera] corresponding to `cfg`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cfg` is invalid. See [Camera::try_new] for when this is the case.
     pub fn new(cfg: Config) -> Self {
+        match Self::try_new(cfg) {
+            Ok(camera) => camera,
+            Err(err) => panic!("invalid camera config: {}", err),
+        }
+    }
+
+    /// Returns a new [Camera] corresponding to `cfg`, or an error if `cfg` is invalid. This is the
+    /// case if the field of view is not strictly between 0 and pi, if the image width or height is
+    /// 0, if the pixel aspect ratio is not finite and positive, if the sample pattern has no
+    /// samples, or if `from` and `at` are the same point. An `up` which is parallel to the viewing
+    /// direction is fine, see [Matrix::view_transform].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::camera::{Camera, CameraError, Config};
+    /// use std::f64::consts::PI;
+    ///
+    /// assert!(Camera::try_new(Config::default()).is_ok());
+    /// assert_eq!(
+    ///     Camera::try_new(Config { fov: PI, ..Config::default() }).err(),
+    ///     Some(CameraError::InvalidFov(PI)),
+    /// );
+    /// ```
+    pub fn try_new(cfg: Config) -> Result<Self, CameraError> {
+        if !(cfg.fov > 0.0 && cfg.fov < PI) {
+            return Err(CameraError::InvalidFov(cfg.fov));
+        }
+        if cfg.hsize == 0 || cfg.vsize == 0 {
+            return Err(CameraError::EmptyImage {
+                hsize: cfg.hsize,
+                vsize: cfg.vsize,
+            });
+        }
+        if !(cfg.pixel_aspect > 0.0 && cfg.pixel_aspect.is_finite()) {
+            return Err(CameraError::InvalidPixelAspect(cfg.pixel_aspect));
+        }
+        if cfg.sample_pattern.samples() == 0 {
+            return Err(CameraError::NoSamples(cfg.sample_pattern));
+        }
+        if (cfg.at - cfg.from).norm() < EPSILON {
+            return Err(CameraError::NoViewDirection(cfg.from));
+        }
+
         let transform = Matrix::view_transform(cfg.from, cfg.at, cfg.up);
         let transform_inverse = transform.inverse();
 
         let half_view = (cfg.fov / 2.0).tan();
-        let aspect = cfg.hsize as f64 / cfg.vsize as f64;
+        let aspect = cfg.hsize as f64 * cfg.pixel_aspect / cfg.vsize as f64;
 
         let (half_width, half_height) = if aspect >= 1.0 {
             (half_view, half_view / aspect)
@@ -63,16 +303,30 @@ impl Camera {
             (half_view * aspect, half_view)
         };
 
-        let pixel_size = half_width * 2.0 / cfg.hsize as f64;
+        let pixel_width = half_width * 2.0 / cfg.hsize as f64;
+        let pixel_height = pixel_width / cfg.pixel_aspect;
 
-        Self {
+        Ok(Self {
             hsize: cfg.hsize,
             vsize: cfg.vsize,
             half_height,
             half_width,
-            pixel_size,
+            pixel_width,
+            pixel_height,
             transform_inverse,
-        }
+            max_depth: cfg.max_depth,
+            sample_pattern: cfg.sample_pattern,
+        })
+    }
+
+    /// Returns the points within each pixel `self` sends rays through.
+    pub fn sample_pattern(&self) -> SamplePattern {
+        self.sample_pattern
+    }
+
+    /// Returns how many times reflected and refracted rays may bounce when rendering.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
     }
 
     /// Returns a ray for the pixel at the given coordinates.
@@ -97,31 +351,234 @@ impl Camera {
     /// # assert_eq!(ray, Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.00000000000000011102230246251565, 0.0, -1.0)));
     /// ```
     pub fn ray_for_pixel(&self, x: usize, y: usize) -> Ray {
-        let offset_x = (x as f64 + 0.5) * self.pixel_size;
-        let offset_y = (y as f64 + 0.5) * self.pixel_size;
+        self.ray_for_pixel_offset(x, y, 0.5, 0.5)
+    }
+
+    /// Returns a ray through the point in the pixel at the given coordinates which is `dx` of
+    /// the pixel's width to the right and `dy` of its height down from its top left corner.
+    /// [Camera::ray_for_pixel] uses 0.5 for both, the center of the pixel.
+    pub fn ray_for_pixel_offset(&self, x: usize, y: usize, dx: f64, dy: f64) -> Ray {
+        let offset_x = (x as f64 + dx) * self.pixel_width;
+        let offset_y = (y as f64 + dy) * self.pixel_height;
 
         let world_x = self.half_width - offset_x;
         let world_y = self.half_height - offset_y;
 
         let pixel = &self.transform_inverse * Tuple::point(world_x, world_y, -1.0);
-        let origin = &self.transform_inverse * Tuple::point(0.0, 0.0, 0.0);
+        let origin = &self.transform_inverse * Tuple::ORIGIN;
         let direction = (pixel - origin).normalized();
 
         Ray::new(origin, direction)
     }
 
-    /// Renders the `world` to a canvas as seen from `self` and returns it.
+    /// Returns an iterator over every pixel of the image, as its column, its row and the ray
+    /// through its center from [Camera::ray_for_pixel]. The pixels come row by row, from the top
+    /// left. This separates making primary rays from tracing them, so the work can be split up in
+    /// any way, like over threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::camera::{Camera, Config};
+    /// use truster::canvas::Canvas;
+    /// use truster::world::World;
+    ///
+    /// let world = World::default_world();
+    /// let camera = Camera::new(Config { hsize: 4, vsize: 3, ..Config::default() });
+    /// let mut canvas = Canvas::new(4, 3);
+    /// for (x, y, ray) in camera.rays() {
+    ///     canvas[[x, y]] = world.color_at(&ray);
+    /// }
+    /// ```
+    pub fn rays(&self) -> impl Iterator<Item = (usize, usize, Ray)> + '_ {
+        (0..self.vsize)
+            .flat_map(move |y| (0..self.hsize).map(move |x| (x, y, self.ray_for_pixel(x, y))))
+    }
+
+    /// Returns the shape of `world` which is visible through the center of the pixel at column `x`
+    /// and row `y`, or `None` if the pixel only shows the background. Useful for selecting shapes
+    /// by clicking on them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::camera::{Camera, Config};
+    /// use std::rc::Rc;
+    /// use truster::shape::{Shape, Sphere};
+    /// use truster::tuple::Tuple;
+    /// use truster::world::World;
+    ///
+    /// let mut world = World::new();
+    /// let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+    /// world.add_shape(Rc::clone(&sphere));
+    ///
+    /// let camera = Camera::new(Config {
+    ///     hsize: 11,
+    ///     vsize: 11,
+    ///     from: Tuple::point(0.0, 0.0, -5.0),
+    ///     at: Tuple::ORIGIN,
+    ///     ..Config::default()
+    /// });
+    /// assert!(Rc::ptr_eq(&camera.pick(&world, 5, 5).unwrap(), &sphere));
+    /// assert!(camera.pick(&world, 0, 0).is_none());
+    /// ```
+    pub fn pick(&self, world: &World, x: usize, y: usize) -> Option<Rc<dyn Shape>> {
+        world.hit(&self.ray_for_pixel(x, y)).map(|rec| rec.shape())
+    }
+
+    /// Renders the `world` to a canvas as seen from `self` and returns it. Rays bounce at most
+    /// [Camera::max_depth] times. Each pixel is the average of the rays through the points of
+    /// [Camera::sample_pattern]. If `world` has light scattering turned on (see
+    /// [World::set_scattering]), the scattered light is added to every ray.
     pub fn render(&self, world: &World) -> Canvas {
         let mut result = Canvas::new(self.hsize, self.vsize);
+        self.render_pixels(world, &mut result);
+        result
+    }
+
+    /// Like [Camera::render], but overwrites the pixels of `canvas` instead of returning a new
+    /// canvas. Reusing one canvas saves allocating a new one every frame, like in a viewer.
+    /// Returns an error, and leaves `canvas` as is, if it is not the size of the image of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::camera::{Camera, Config};
+    /// use truster::canvas::Canvas;
+    /// use truster::world::World;
+    ///
+    /// let world = World::default_world();
+    /// let camera = Camera::new(Config { hsize: 8, vsize: 6, ..Config::default() });
+    /// let mut canvas = Canvas::new(8, 6);
+    /// camera.render_into(&world, &mut canvas).unwrap();
+    /// assert!(camera.render_into(&world, &mut Canvas::new(6, 8)).is_err());
+    /// ```
+    pub fn render_into(&self, world: &World, canvas: &mut Canvas) -> Result<(), CameraError> {
+        if canvas.width() != self.hsize || canvas.height() != self.vsize {
+            return Err(CameraError::CanvasSize {
+                expected: (self.hsize, self.vsize),
+                actual: (canvas.width(), canvas.height()),
+            });
+        }
+        self.render_pixels(world, canvas);
+        Ok(())
+    }
+
+    /// Renders every pixel of `result`, which must be the size of the image of `self`.
+    fn render_pixels(&self, world: &World, result: &mut Canvas) {
+        let mut rng = match self.sample_pattern {
+            SamplePattern::Random { seed, .. } => Rng::new(seed),
+            _ => Rng::new(0),
+        };
+        let mut offsets = Vec::with_capacity(self.sample_pattern.samples());
+        let mut colors = Vec::with_capacity(self.sample_pattern.samples());
+        let mut intersections = Vec::new();
 
         for y in 0..self.vsize {
             for x in 0..self.hsize {
-                let ray = self.ray_for_pixel(x, y);
-                let color = world.color_at(&ray);
-                result[[x, y]] = color;
+                self.sample_pattern.offsets(&mut rng, &mut offsets);
+                world.count_primary_rays(offsets.len() as u64);
+                colors.clear();
+                for &(dx, dy) in offsets.iter() {
+                    let ray = self.ray_for_pixel_offset(x, y, dx, dy);
+                    let mut color =
+                        world.color_at_depth_into(&ray, self.max_depth, &mut intersections);
+                    if world.scattering_steps() > 0 {
+                        color += world.in_scattering(&ray, world.scattering_steps());
+                    }
+                    colors.push(color);
+                }
+                result[[x, y]] = Color::mean(&colors);
             }
         }
+    }
+
+    /// Like [Camera::render], but also returns the number of rays traced and intersection tests
+    /// done for the render. The counts of `world` are reset first. See [World::stats].
+    pub fn render_with_stats(&self, world: &World) -> (Canvas, RenderStats) {
+        world.reset_stats();
+        let canvas = self.render(world);
+        (canvas, world.stats())
+    }
+
+    /// Renders the surface normals of `world`, which helps with finding problems in geometry.
+    /// Pixels whose ray through their center hits a shape get the world space normal `n` at the
+    /// hit as the color `n * 0.5 + 0.5`, so each axis maps to a channel between 0 and 1. This is
+    /// the normal used for shading, which faces the ray. Pixels whose ray misses everything are
+    /// black. Like [Camera::render], the rays are counted in [World::stats].
+    pub fn render_normals(&self, world: &World) -> Canvas {
+        let mut result = Canvas::new(self.hsize, self.vsize);
+        let mut intersections = Vec::new();
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                world.count_primary_rays(1);
+                if let Some(rec) = world.hit_into(&self.ray_for_pixel(x, y), &mut intersections) {
+                    let n = rec.normal();
+                    result[[x, y]] = Color::new(n.x(), n.y(), n.z()).map(|c| c * 0.5 + 0.5);
+                }
+            }
+        }
+        result
+    }
+
+    /// Renders the distance from `self` to `world` as a grayscale depth pass. Pixels whose ray
+    /// through their center hits a shape get the distance `t` to the nearest hit, mapped from
+    /// [`near`, `far`] to [0, 1]: black at `near` or closer, white at `far` or farther. Pixels
+    /// whose ray misses everything are white, as if infinitely far away. Like [Camera::render],
+    /// the rays are counted in [World::stats].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `far` is not larger than `near`.
+    pub fn render_depth(&self, world: &World, near: f64, far: f64) -> Canvas {
+        if far <= near {
+            panic!("far ({}) must be larger than near ({})", far, near);
+        }
+
+        let mut result = Canvas::new(self.hsize, self.vsize);
+        let mut intersections = Vec::new();
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                world.count_primary_rays(1);
+                let depth = match world.hit_into(&self.ray_for_pixel(x, y), &mut intersections) {
+                    Some(rec) => ((rec.t() - near) / (far - near)).clamp(0.0, 1.0),
+                    None => 1.0,
+                };
+                result[[x, y]] = Color::gray(depth);
+            }
+        }
+        result
+    }
+
+    /// Renders where the light at index `light_index` in `world` reaches, which helps with
+    /// placing lights. Pixels whose ray through their center hits a point lit by the light are
+    /// white, and those hitting a point in its shadow are black. With soft shadows, the penumbra
+    /// is gray. Pixels whose ray misses everything are black too. Materials, other lights and
+    /// reflections are ignored. Like [Camera::render], the rays are counted in [World::stats].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `light_index` is out of bounds for the lights of `world`.
+    pub fn render_shadow_mask(&self, world: &World, light_index: usize) -> Canvas {
+        if light_index >= world.lights().len() {
+            panic!(
+                "light index {} out of bounds for {} lights",
+                light_index,
+                world.lights().len()
+            );
+        }
 
+        let mut result = Canvas::new(self.hsize, self.vsize);
+        let mut intersections = Vec::new();
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                world.count_primary_rays(1);
+                if let Some(rec) = world.hit_into(&self.ray_for_pixel(x, y), &mut intersections) {
+                    let lit = 1.0 - world.shadow_fraction(light_index, rec.over_point());
+                    result[[x, y]] = Color::gray(lit);
+                }
+            }
+        }
         result
     }
 }
@@ -132,9 +589,7 @@ mod tests {
     use crate::color::Color;
     use crate::light::PointLight;
     use crate::material::Material;
-    use crate::shape::{sphere::Sphere, Shape};
-    use crate::texture::solid_color::SolidColor;
-    use std::rc::Rc;
+    use crate::shape::{Plane, Sphere};
 
     #[test]
     fn pixel_size() {
@@ -144,7 +599,8 @@ mod tests {
             fov: PI / 2.0,
             ..Config::default()
         });
-        assert_eq!(camera.pixel_size, 0.009999999999999998);
+        assert_eq!(camera.pixel_width, 0.009999999999999998);
+        assert_eq!(camera.pixel_height, 0.009999999999999998);
 
         let camera = Camera::new(Config {
             hsize: 125,
@@ -152,7 +608,34 @@ mod tests {
             fov: PI / 2.0,
             ..Config::default()
         });
-        assert_eq!(camera.pixel_size, 0.009999999999999998);
+        assert_eq!(camera.pixel_width, 0.009999999999999998);
+        assert_eq!(camera.pixel_height, 0.009999999999999998);
+    }
+
+    #[test]
+    fn pixel_aspect_stretches_horizontally() {
+        let config = || Config {
+            hsize: 100,
+            vsize: 100,
+            fov: PI / 2.0,
+            ..Config::default()
+        };
+        let square = Camera::new(config());
+        let wide = Camera::new(Config {
+            pixel_aspect: 2.0,
+            ..config()
+        });
+
+        assert_eq!(wide.pixel_width, 2.0 * wide.pixel_height);
+        assert_eq!(wide.half_width, square.half_width);
+        assert_eq!(wide.half_height, square.half_height / 2.0);
+
+        let corner = |camera: &Camera| {
+            let direction = camera.ray_for_pixel(0, 0).direction();
+            direction.x() / direction.y()
+        };
+        assert!((corner(&square) - 1.0).abs() < 0.000_001);
+        assert!((corner(&wide) - 2.0).abs() < 0.000_001);
     }
 
     #[test]
@@ -173,31 +656,219 @@ mod tests {
         );
     }
 
-    fn test_world() -> World {
-        let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
+    #[test]
+    fn from_focal_length_full_frame() {
+        let config = Config::from_focal_length(50.0, 36.0);
+        assert!((config.fov - 0.6911111611634243).abs() < 1e-12);
+        assert!((config.fov.to_degrees() - 39.6).abs() < 0.01);
+        assert_eq!(config.from, Config::default().from);
+    }
 
-        let mut sphere1 = Sphere::new();
-        sphere1.set_material(Material {
-            texture: Rc::new(SolidColor::new(Color::new(0.8, 1.0, 0.6))),
-            diffuse: 0.7,
-            specular: 0.2,
-            ..Material::default()
+    #[test]
+    fn orbit_yaw_quarter_turn() {
+        let target = Tuple::point(0.0, 1.0, 0.0);
+        let config = Config::orbit(target, 2.0, PI / 2.0, 0.0);
+        assert!((config.from - Tuple::point(2.0, 1.0, 0.0)).norm() < 1e-9);
+        assert_eq!(config.at, target);
+    }
+
+    #[test]
+    fn orbit_clamps_pitch_at_poles() {
+        let target = Tuple::point(0.0, 0.0, 0.0);
+        for &pitch in &[PI / 2.0, -PI, 10.0] {
+            let config = Config::orbit(target, 3.0, 0.3, pitch);
+            assert!((config.from.distance(target) - 3.0).abs() < 1e-9);
+            assert!(config.from.z().abs() > 0.0);
+
+            let camera = Camera::new(config);
+            let direction = camera.ray_for_pixel(50, 50).direction();
+            assert!(direction.as_array().iter().all(|c| c.is_finite()));
+        }
+    }
+
+    #[test]
+    fn sample_pattern_offsets() {
+        let mut rng = Rng::new(0);
+        let mut offsets = Vec::new();
+        SamplePattern::Center.offsets(&mut rng, &mut offsets);
+        assert_eq!(offsets, vec![(0.5, 0.5)]);
+        SamplePattern::Grid(2).offsets(&mut rng, &mut offsets);
+        assert_eq!(
+            offsets,
+            vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]
+        );
+
+        let pattern = SamplePattern::Random {
+            samples: 16,
+            seed: 42,
+        };
+        pattern.offsets(&mut rng, &mut offsets);
+        assert_eq!(offsets.len(), 16);
+        for &(dx, dy) in offsets.iter() {
+            assert!((0.0..1.0).contains(&dx) && (0.0..1.0).contains(&dy));
+        }
+    }
+
+    #[test]
+    fn render_deterministic() {
+        let world = World::default_world();
+        let patterns = [
+            SamplePattern::Center,
+            SamplePattern::Grid(3),
+            SamplePattern::RotatedGrid,
+            SamplePattern::Random {
+                samples: 4,
+                seed: 7,
+            },
+        ];
+        for &sample_pattern in patterns.iter() {
+            let config = || Config {
+                hsize: 9,
+                vsize: 9,
+                fov: PI / 2.0,
+                from: Tuple::point(0.0, 0.0, -5.0),
+                at: Tuple::ORIGIN,
+                sample_pattern,
+                ..Config::default()
+            };
+            let first = Camera::new(config()).render(&world);
+            let second = Camera::new(config()).render(&world);
+            assert_eq!(first.first_diff(&second, 0.0), None);
+
+            let mut first_ppm = Vec::new();
+            let mut second_ppm = Vec::new();
+            first.to_ppm(&mut first_ppm).unwrap();
+            second.to_ppm(&mut second_ppm).unwrap();
+            assert_eq!(first_ppm, second_ppm);
+        }
+    }
+
+    #[test]
+    fn render_grid_smooths_edges() {
+        let world = World::default_world();
+        let config = |sample_pattern| Config {
+            hsize: 9,
+            vsize: 9,
+            fov: PI / 2.0,
+            from: Tuple::point(0.0, 0.0, -5.0),
+            at: Tuple::ORIGIN,
+            sample_pattern,
+            ..Config::default()
+        };
+        let center = Camera::new(config(SamplePattern::Center)).render(&world);
+        let grid = Camera::new(config(SamplePattern::Grid(4))).render(&world);
+        assert!(center.max_channel_diff(&grid) > 0.0);
+        // The middle of the sphere is uniform enough that sampling barely changes it.
+        assert!((center[[4, 4]].g() - grid[[4, 4]].g()).abs() < 0.05);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid camera config: sample pattern Grid(0) has no samples")]
+    fn new_without_samples() {
+        Camera::new(Config {
+            sample_pattern: SamplePattern::Grid(0),
+            ..Config::default()
         });
+    }
 
-        let mut sphere2 = Sphere::new();
-        sphere2.set_transform(Matrix::scaling(0.5, 0.5, 0.5));
+    #[test]
+    fn try_new_rejects_invalid_fov() {
+        for &fov in &[PI, 4.0, 0.0, -1.0, f64::NAN] {
+            let err = Camera::try_new(Config {
+                fov,
+                ..Config::default()
+            })
+            .err()
+            .unwrap();
+            assert!(matches!(err, CameraError::InvalidFov(_)));
+        }
+        let err = Camera::try_new(Config {
+            fov: PI,
+            ..Config::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!("field of view must be between 0 and pi, got {}", PI)
+        );
+    }
 
-        let mut world = World::new();
-        world.add_light(Rc::new(light));
-        world.add_shape(Rc::new(sphere1));
-        world.add_shape(Rc::new(sphere2));
+    #[test]
+    fn try_new_rejects_empty_image() {
+        let err = Camera::try_new(Config {
+            hsize: 10,
+            vsize: 0,
+            ..Config::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(
+            err,
+            CameraError::EmptyImage {
+                hsize: 10,
+                vsize: 0
+            }
+        );
+        assert_eq!(err.to_string(), "image must not be empty, got 10x0 pixels");
+        assert!(Camera::try_new(Config {
+            hsize: 0,
+            ..Config::default()
+        })
+        .is_err());
+    }
 
-        world
+    #[test]
+    fn try_new_rejects_invalid_pixel_aspect() {
+        for &pixel_aspect in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let err = Camera::try_new(Config {
+                pixel_aspect,
+                ..Config::default()
+            })
+            .err()
+            .unwrap();
+            assert!(matches!(err, CameraError::InvalidPixelAspect(_)));
+        }
+        let err = Camera::try_new(Config {
+            pixel_aspect: -1.0,
+            ..Config::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "pixel aspect ratio must be finite and positive, got -1"
+        );
+        assert!(Camera::try_new(Config {
+            pixel_aspect: 0.5,
+            ..Config::default()
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn try_new_rejects_no_samples() {
+        let err = Camera::try_new(Config {
+            sample_pattern: SamplePattern::Grid(0),
+            ..Config::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(err, CameraError::NoSamples(SamplePattern::Grid(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "field of view")]
+    fn new_invalid_fov() {
+        Camera::new(Config {
+            fov: PI,
+            ..Config::default()
+        });
     }
 
     #[test]
     fn render() {
-        let world = test_world();
+        let world = World::default_world();
         let camera = Camera::new(Config {
             hsize: 11,
             vsize: 11,
@@ -216,4 +887,356 @@ mod tests {
             )
         );
     }
+
+    fn facing_mirrors() -> World {
+        let mut world = World::default_world();
+        world.clear_shapes();
+        let mirror = Material {
+            reflective: 1.0,
+            ..Material::default()
+        };
+        let mut floor = Plane::new();
+        floor.set_transform(Matrix::translation(0.0, -1.0, 0.0));
+        floor.set_material(mirror.clone());
+        let mut ceiling = Plane::new();
+        ceiling.set_transform(Matrix::translation(0.0, 1.0, 0.0) * &Matrix::rotation_x(PI));
+        ceiling.set_material(mirror);
+        world.add_shape(Rc::new(floor));
+        world.add_shape(Rc::new(ceiling));
+        world
+    }
+
+    fn mirror_camera(max_depth: usize) -> Camera {
+        Camera::new(Config {
+            hsize: 5,
+            vsize: 5,
+            fov: PI / 2.0,
+            at: Tuple::point(0.1, -1.0, 0.1),
+            up: Tuple::vector(0.0, 0.0, 1.0),
+            max_depth,
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn render_facing_mirrors_any_depth() {
+        let world = facing_mirrors();
+        for &depth in &[0, 1, 5, 50] {
+            let camera = mirror_camera(depth);
+            assert_eq!(camera.max_depth(), depth);
+            camera.render(&world);
+        }
+        let shallow = mirror_camera(0).render(&world);
+        let deep = mirror_camera(5).render(&world);
+        assert!(deep[[2, 2]].r() > shallow[[2, 2]].r());
+    }
+
+    #[test]
+    fn render_depth_zero_is_surface_color() {
+        let world = facing_mirrors();
+        let camera = mirror_camera(0);
+        let image = camera.render(&world);
+        for &(x, y) in &[(0, 0), (2, 2), (4, 1)] {
+            let rec = world.hit(&camera.ray_for_pixel(x, y)).unwrap();
+            assert_eq!(image[[x, y]], world.shade_hit(0, rec));
+        }
+    }
+
+    #[test]
+    fn render_with_stats_single_sphere() {
+        let mut world = World::new();
+        world.add_shape(Rc::new(Sphere::new()));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(-10.0, 10.0, -10.0),
+            Color::WHITE,
+        )));
+        let camera = Camera::new(Config {
+            hsize: 11,
+            vsize: 7,
+            from: Tuple::point(0.0, 0.0, -5.0),
+            at: Tuple::ORIGIN,
+            ..Config::default()
+        });
+
+        let (image, stats) = camera.render_with_stats(&world);
+        assert_eq!(image.max_channel_diff(&camera.render(&world)), 0.0);
+        assert_eq!(stats.primary_rays, 11 * 7);
+        assert_eq!(stats.reflection_rays, 0);
+        assert_eq!(stats.refraction_rays, 0);
+        assert!(stats.shadow_rays > 0 && stats.shadow_rays < stats.primary_rays);
+        assert_eq!(
+            stats.intersection_tests,
+            stats.primary_rays + stats.shadow_rays
+        );
+        assert_eq!(stats.rays(), stats.primary_rays + stats.shadow_rays);
+    }
+
+    #[test]
+    fn render_with_stats_counts_samples_and_bounces() {
+        let world = facing_mirrors();
+        let (_, stats) = mirror_camera(0).render_with_stats(&world);
+        assert_eq!(stats.primary_rays, 25);
+        assert_eq!(stats.reflection_rays, 0);
+
+        let (_, stats) = mirror_camera(3).render_with_stats(&world);
+        assert_eq!(stats.primary_rays, 25);
+        assert!(stats.reflection_rays > 0 && stats.reflection_rays <= 25 * 3);
+
+        let camera = Camera::new(Config {
+            hsize: 5,
+            vsize: 5,
+            sample_pattern: SamplePattern::Grid(2),
+            ..Config::default()
+        });
+        let (_, stats) = camera.render_with_stats(&world);
+        assert_eq!(stats.primary_rays, 25 * 4);
+    }
+
+    #[test]
+    fn render_shadow_mask() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(Matrix::translation(0.0, 1.0, 0.0) * &Matrix::scale_uniform(0.5));
+        let world = World::builder()
+            .shape(Plane::new())
+            .shape(sphere)
+            .light(PointLight::new(Tuple::point(0.0, 10.0, 0.0), Color::WHITE))
+            .build();
+        let camera = Camera::new(Config {
+            hsize: 11,
+            vsize: 11,
+            fov: PI / 2.0,
+            from: Tuple::point(0.0, 5.0, -5.0),
+            at: Tuple::ORIGIN,
+            ..Config::default()
+        });
+
+        let mask = camera.render_shadow_mask(&world, 0);
+        // The center pixel sees the floor right under the sphere, which is between it and the
+        // light.
+        assert_eq!(mask[[5, 5]], Color::BLACK);
+        assert_eq!(mask[[0, 10]], Color::WHITE);
+        assert_eq!(mask[[10, 10]], Color::WHITE);
+        for (_, _, &color) in mask.enumerate_pixels() {
+            assert!(color == Color::BLACK || color == Color::WHITE);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "light index 0 out of bounds for 0 lights")]
+    fn render_shadow_mask_without_light() {
+        let world = World::builder().shape(Plane::new()).build();
+        Camera::new(Config::default()).render_shadow_mask(&world, 0);
+    }
+
+    #[test]
+    fn render_normals() {
+        let world = World::builder().shape(Sphere::new()).build();
+        let camera = Camera::new(Config {
+            hsize: 11,
+            vsize: 11,
+            fov: PI / 2.0,
+            from: Tuple::point(0.0, 0.0, -5.0),
+            at: Tuple::ORIGIN,
+            ..Config::default()
+        });
+
+        let normals = camera.render_normals(&world);
+        let expected = Color::new(0.5, 0.5, 0.0);
+        for i in 0..3 {
+            assert!((normals[[5, 5]][i] - expected[i]).abs() < 1e-9);
+        }
+        assert_eq!(normals[[0, 0]], Color::BLACK);
+
+        // Above the center, the normal tilts up, and to the right it tilts right.
+        assert!(normals[[5, 4]].g() > 0.5);
+        assert!(normals[[6, 5]].r() > 0.5);
+    }
+
+    #[test]
+    fn render_depth() {
+        let mut near_sphere = Sphere::new();
+        near_sphere.set_transform(Matrix::translation(-1.5, 0.0, -1.0));
+        let mut far_sphere = Sphere::new();
+        far_sphere.set_transform(Matrix::translation(1.5, 0.0, 3.0));
+        let world = World::builder()
+            .shape(near_sphere)
+            .shape(far_sphere)
+            .build();
+        let camera = Camera::new(Config {
+            hsize: 21,
+            vsize: 11,
+            fov: PI / 2.0,
+            from: Tuple::point(0.0, 0.0, -5.0),
+            at: Tuple::ORIGIN,
+            ..Config::default()
+        });
+
+        let depth = camera.render_depth(&world, 1.0, 11.0);
+        let near = camera.pick(&world, 6, 5).unwrap();
+        let far = camera.pick(&world, 13, 5).unwrap();
+        assert!(Rc::ptr_eq(&near, &world.shapes()[0]));
+        assert!(Rc::ptr_eq(&far, &world.shapes()[1]));
+        assert!(depth[[6, 5]].r() < depth[[13, 5]].r());
+        assert!(depth[[6, 5]].r() > 0.0 && depth[[13, 5]].r() < 1.0);
+        assert_eq!(depth[[0, 0]], Color::WHITE);
+    }
+
+    #[test]
+    #[should_panic(expected = "far (1) must be larger than near (2)")]
+    fn render_depth_far_before_near() {
+        let world = World::new();
+        Camera::new(Config::default()).render_depth(&world, 2.0, 1.0);
+    }
+
+    #[test]
+    fn render_into_matches_render() {
+        let world = World::default_world();
+        let camera = Camera::new(Config {
+            hsize: 15,
+            vsize: 9,
+            from: Tuple::point(0.0, 0.0, -5.0),
+            at: Tuple::ORIGIN,
+            sample_pattern: SamplePattern::Random {
+                samples: 3,
+                seed: 9,
+            },
+            ..Config::default()
+        });
+
+        let mut canvas = Canvas::new(15, 9);
+        canvas.fill(Color::RED);
+        camera.render_into(&world, &mut canvas).unwrap();
+        assert_eq!(canvas.first_diff(&camera.render(&world), 0.0), None);
+
+        // Rendering again into the same canvas overwrites every pixel the same way.
+        camera.render_into(&world, &mut canvas).unwrap();
+        assert_eq!(canvas.first_diff(&camera.render(&world), 0.0), None);
+    }
+
+    #[test]
+    fn render_into_wrong_size() {
+        let world = World::default_world();
+        let camera = Camera::new(Config {
+            hsize: 4,
+            vsize: 3,
+            ..Config::default()
+        });
+        let mut canvas = Canvas::new(3, 4);
+        canvas.fill(Color::RED);
+        let err = camera.render_into(&world, &mut canvas).unwrap_err();
+        assert_eq!(
+            err,
+            CameraError::CanvasSize {
+                expected: (4, 3),
+                actual: (3, 4)
+            }
+        );
+        assert_eq!(err.to_string(), "canvas must be 4x3 pixels, got 3x4");
+        assert_eq!(canvas[[0, 0]], Color::RED);
+    }
+
+    #[test]
+    fn rays_cover_every_pixel() {
+        let camera = Camera::new(Config {
+            hsize: 7,
+            vsize: 5,
+            from: Tuple::point(1.0, 2.0, -5.0),
+            at: Tuple::ORIGIN,
+            ..Config::default()
+        });
+
+        let mut seen = std::collections::HashSet::new();
+        let mut count = 0;
+        for (x, y, ray) in camera.rays() {
+            assert!(x < 7 && y < 5);
+            assert_eq!(ray, camera.ray_for_pixel(x, y));
+            seen.insert((x, y));
+            count += 1;
+        }
+        assert_eq!(count, 7 * 5);
+        assert_eq!(seen.len(), 7 * 5);
+    }
+
+    #[test]
+    fn try_new_from_equals_at() {
+        let from = Tuple::point(1.0, 2.0, 3.0);
+        let cfg = Config {
+            from,
+            at: from,
+            ..Config::default()
+        };
+        assert_eq!(
+            Camera::try_new(cfg).err(),
+            Some(CameraError::NoViewDirection(from))
+        );
+    }
+
+    #[test]
+    fn up_parallel_to_view_direction() {
+        let world = World::default_world();
+        for &(from, up) in &[
+            (Tuple::point(0.0, 5.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 2.0)),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 0.0)),
+        ] {
+            let camera = Camera::new(Config {
+                hsize: 11,
+                vsize: 11,
+                from,
+                at: Tuple::ORIGIN,
+                up,
+                ..Config::default()
+            });
+            let canvas = camera.render(&world);
+            // The sphere at the origin is in the center of the image.
+            assert!(world.hit(&camera.ray_for_pixel(5, 5)).is_some());
+            for y in 0..11 {
+                for x in 0..11 {
+                    for channel in 0..3 {
+                        assert!(canvas[[x, y]][channel].is_finite());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn debug_renders_count_rays() {
+        let mut world = World::new();
+        world.add_shape(Rc::new(Sphere::new()));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(-10.0, 10.0, -10.0),
+            Color::WHITE,
+        )));
+        let camera = Camera::new(Config {
+            hsize: 11,
+            vsize: 7,
+            from: Tuple::point(0.0, 0.0, -5.0),
+            at: Tuple::ORIGIN,
+            ..Config::default()
+        });
+
+        world.reset_stats();
+        camera.render_normals(&world);
+        let stats = world.stats();
+        assert_eq!(stats.primary_rays, 11 * 7);
+        assert_eq!(stats.shadow_rays, 0);
+        assert_eq!(stats.intersection_tests, stats.primary_rays);
+
+        world.reset_stats();
+        camera.render_depth(&world, 4.0, 6.0);
+        let stats = world.stats();
+        assert_eq!(stats.primary_rays, 11 * 7);
+        assert_eq!(stats.intersection_tests, stats.primary_rays);
+
+        world.reset_stats();
+        camera.render_shadow_mask(&world, 0);
+        let stats = world.stats();
+        assert_eq!(stats.primary_rays, 11 * 7);
+        assert!(stats.shadow_rays > 0 && stats.shadow_rays < stats.primary_rays);
+        assert_eq!(
+            stats.intersection_tests,
+            stats.primary_rays + stats.shadow_rays
+        );
+    }
 }
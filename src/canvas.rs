@@ -37,11 +37,12 @@
 //!     canvas[[2, 1]] = Color::new(0.0, 0.5, 0.0);
 //!     canvas[[4, 2]] = Color::new(-0.5, 0.0, 1.0);
 //!
-//!     let mut file = File::create("foo.ppm")?;
+//!     let path = std::env::temp_dir().join("foo.ppm");
+//!     let mut file = File::create(&path)?;
 //!     canvas.to_ppm(&mut file);
-//!     let mut file = File::open("foo.ppm")?;
+//!     let mut file = File::open(&path)?;
 //!     let mut output = String::new();
-//! 	file.read_to_string(&mut output);
+//!     file.read_to_string(&mut output);
 //!
 //!     assert_eq!(output, "P3
 //! 5 3
@@ -63,7 +64,7 @@
 //! 0 0 255
 //! ");
 //!
-//! 	Ok(())
+//!     Ok(())
 //! }
 //! ```
 
@@ -73,54 +74,584 @@ use std::ops::{Index, IndexMut};
 use crate::color::Color;
 
 /// A 2D image. See the module's documentation for more information.
+///
+/// The pixels are stored in a single buffer, row by row.
 pub struct Canvas {
-    pixels: Vec<Vec<Color>>,
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
 }
 
 impl Canvas {
     /// Creates a new canvas with the given width and height. The new canvas is entirely black.
     pub fn new(width: usize, height: usize) -> Self {
         Self {
-            pixels: vec![vec![Color::default(); width]; height],
+            width,
+            height,
+            pixels: vec![Color::default(); width * height],
         }
     }
 
     /// Returns `self`'s width, that is the number of columns in the image.
     pub fn width(&self) -> usize {
-        if self.pixels.len() == 0 {
-            0
-        } else {
-            self.pixels[0].len()
-        }
+        self.width
     }
 
     /// Returns `self`'s height, that is the number of rows in the image.
     pub fn height(&self) -> usize {
-        self.pixels.len()
+        self.height
+    }
+
+    /// Returns all pixels of `self` in a single slice, row by row from the top left to the bottom
+    /// right. The pixel at column `x` and row `y` is at `y * width + x`. This is useful for
+    /// handing the image to other libraries without going through PPM.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// canvas[[3, 4]] = Color::RED;
+    /// let pixels = canvas.as_slice();
+    /// assert_eq!(pixels.len(), 10 * 20);
+    /// for y in 0..20 {
+    ///     for x in 0..10 {
+    ///         assert_eq!(pixels[y * 10 + x], canvas[[x, y]]);
+    ///     }
+    /// }
+    /// assert_eq!(pixels[4 * 10 + 3], Color::RED);
+    /// ```
+    pub fn as_slice(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Returns all pixels of `self` in a single mutable slice, in the same order as
+    /// [Canvas::as_slice]. The slice can be split into rows with `chunks_mut(width)`, for example
+    /// to render the rows in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(4, 3);
+    /// for (y, row) in canvas.as_mut_slice().chunks_mut(4).enumerate() {
+    ///     for color in row {
+    ///         *color = Color::gray(y as f64);
+    ///     }
+    /// }
+    /// assert_eq!(canvas[[3, 0]], Color::gray(0.0));
+    /// assert_eq!(canvas[[0, 2]], Color::gray(2.0));
+    /// ```
+    pub fn as_mut_slice(&mut self) -> &mut [Color] {
+        &mut self.pixels
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Color]> {
+        // chunks panics on 0, but then there are no pixels to iterate over anyway.
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    fn offset(&self, x: usize, y: usize) -> usize {
+        if x >= self.width || y >= self.height {
+            panic!(
+                "pixel ({}, {}) out of bounds for a {}x{} canvas",
+                x, y, self.width, self.height
+            );
+        }
+        y * self.width + x
+    }
+
+    /// Returns the color of the pixel at column `x` and row `y`, or `None` if that is outside of
+    /// `self`. Unlike indexing, this never panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// canvas[[9, 19]] = Color::RED;
+    /// assert_eq!(canvas.get(9, 19), Some(Color::RED));
+    /// assert_eq!(canvas.get(0, 0), Some(Color::BLACK));
+    /// assert_eq!(canvas.get(10, 0), None);
+    /// assert_eq!(canvas.get(0, 20), None);
+    /// ```
+    pub fn get(&self, x: usize, y: usize) -> Option<Color> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Sets every pixel of `self` to `color`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// canvas.fill(Color::BLUE);
+    /// for y in 0..20 {
+    ///     for x in 0..10 {
+    ///         assert_eq!(canvas[[x, y]], Color::BLUE);
+    ///     }
+    /// }
+    /// ```
+    pub fn fill(&mut self, color: Color) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = color;
+        }
+    }
+
+    /// Sets every pixel of `self` to black, like a new canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// canvas.fill(Color::WHITE);
+    /// canvas.clear();
+    /// assert_eq!(canvas[[4, 7]], Color::BLACK);
+    /// ```
+    pub fn clear(&mut self) {
+        self.fill(Color::BLACK);
+    }
+
+    /// Returns an iterator over all pixels of `self` with their coordinates, as `(x, y, color)`.
+    /// The pixels come row by row, from the top left to the bottom right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use std::collections::HashSet;
+    ///
+    /// let canvas = Canvas::new(10, 20);
+    /// let mut seen = HashSet::new();
+    /// for (x, y, _) in canvas.enumerate_pixels() {
+    ///     assert!(x < 10 && y < 20);
+    ///     assert!(seen.insert((x, y)));
+    /// }
+    /// assert_eq!(seen.len(), 10 * 20);
+    /// ```
+    pub fn enumerate_pixels(&self) -> impl Iterator<Item = (usize, usize, &Color)> {
+        let width = self.width;
+        self.pixels
+            .iter()
+            .enumerate()
+            .map(move |(i, color)| (i % width, i / width, color))
+    }
+
+    /// Returns an iterator over all pixels of `self` with their coordinates, as `(x, y, color)`,
+    /// where the colors can be changed. The order is the same as [Canvas::enumerate_pixels].
+    ///
+    /// # Examples
+    ///
+    /// Applying a gamma correction:
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(2, 2);
+    /// canvas.fill(Color::gray(0.25));
+    /// for (_, _, color) in canvas.enumerate_pixels_mut() {
+    ///     *color = Color::new(color.r().sqrt(), color.g().sqrt(), color.b().sqrt());
+    /// }
+    /// assert_eq!(canvas[[1, 1]], Color::gray(0.5));
+    /// ```
+    pub fn enumerate_pixels_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut Color)> {
+        let width = self.width;
+        self.pixels
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, color)| (i % width, i / width, color))
+    }
+
+    /// Multiplies every color in `self` by `exposure`. Values below 1 darken the image, values
+    /// above 1 brighten it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(2, 2);
+    /// canvas.fill(Color::new(0.8, 0.4, 2.0));
+    /// canvas.apply_exposure(0.5);
+    /// assert_eq!(canvas[[0, 1]], Color::new(0.4, 0.2, 1.0));
+    /// ```
+    pub fn apply_exposure(&mut self, exposure: f64) {
+        for (_, _, color) in self.enumerate_pixels_mut() {
+            *color *= exposure;
+        }
+    }
+
+    /// Scales every color in `self` so the luminance at `percentile` (between 0 and 100) maps to
+    /// white. The brightest pixels above the percentile are left above 1, so a few highlights
+    /// don't darken the rest of the image. The percentile is found with the nearest-rank method
+    /// on the sorted luminances of all pixels. If that luminance is not positive, for example for
+    /// a black image, `self` is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is not between 0 and 100.
+    ///
+    /// # Examples
+    ///
+    /// One very bright pixel doesn't keep the rest of the image dark:
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 10);
+    /// canvas.fill(Color::gray(0.25));
+    /// canvas[[3, 7]] = Color::gray(100.0);
+    /// canvas.auto_exposure(99.0);
+    /// assert_eq!(canvas[[0, 0]], Color::gray(1.0));
+    /// assert_eq!(canvas[[9, 9]], Color::gray(1.0));
+    /// assert_eq!(canvas[[3, 7]], Color::gray(400.0));
+    /// ```
+    ///
+    /// A black image stays black:
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(4, 4);
+    /// canvas.auto_exposure(99.0);
+    /// assert_eq!(canvas[[1, 2]], Color::BLACK);
+    /// ```
+    pub fn auto_exposure(&mut self, percentile: f64) {
+        if !(0.0..=100.0).contains(&percentile) {
+            panic!("percentile {} is not between 0 and 100", percentile);
+        }
+        let mut luminances: Vec<f64> = self.pixels.iter().map(Color::luminance).collect();
+        if luminances.is_empty() {
+            return;
+        }
+        luminances.sort_by(f64::total_cmp);
+
+        let rank = (percentile / 100.0 * luminances.len() as f64).ceil() as usize;
+        let threshold = luminances[rank.max(1) - 1];
+        if threshold > 0.0 && threshold.is_finite() {
+            self.apply_exposure(1.0 / threshold);
+        }
+    }
+
+    /// Applies the Reinhard tone mapping operator `c / (c + 1)` to every channel of every color
+    /// in `self`. This compresses bright colors into [0, 1), so highlights don't clip to white.
+    /// Channels should not be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(2, 2);
+    /// canvas.fill(Color::new(4.0, 4.0, 4.0));
+    /// canvas[[1, 0]] = Color::new(1.0, 0.0, 3.0);
+    /// canvas.tone_map_reinhard();
+    /// assert_eq!(canvas[[0, 0]], Color::new(0.8, 0.8, 0.8));
+    /// assert_eq!(canvas[[1, 0]], Color::new(0.5, 0.0, 0.75));
+    /// ```
+    pub fn tone_map_reinhard(&mut self) {
+        for (_, _, color) in self.enumerate_pixels_mut() {
+            *color = color.map(|c| c / (c + 1.0));
+        }
+    }
+
+    /// Returns a copy of the part of `self` which is `width` pixels wide and `height` pixels high,
+    /// with its top left corner at column `x` and row `y`. The part is clipped at the borders of
+    /// `self`, so the result can be smaller than requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// canvas[[3, 4]] = Color::RED;
+    ///
+    /// let crop = canvas.crop(2, 3, 4, 5);
+    /// assert_eq!(crop.width(), 4);
+    /// assert_eq!(crop.height(), 5);
+    /// assert_eq!(crop[[1, 1]], Color::RED);
+    ///
+    /// let crop = canvas.crop(8, 15, 4, 10);
+    /// assert_eq!(crop.width(), 2);
+    /// assert_eq!(crop.height(), 5);
+    ///
+    /// let crop = canvas.crop(30, 5, 4, 4);
+    /// assert_eq!(crop.width(), 0);
+    /// ```
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        let x = x.min(self.width());
+        let y = y.min(self.height());
+        let width = width.min(self.width() - x);
+        let height = height.min(self.height() - y);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in self.rows().skip(y).take(height) {
+            pixels.extend_from_slice(&row[x..x + width]);
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Copies all pixels of `src` into `self`, with the top left corner of `src` at column `x` and
+    /// row `y`. Pixels which would fall outside of `self` are skipped, so `src` may stick out or
+    /// lie entirely outside.
+    ///
+    /// # Examples
+    ///
+    /// Cropping and pasting back at the same place changes nothing:
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// for (x, y, color) in canvas.enumerate_pixels_mut() {
+    ///     *color = Color::new(x as f64, y as f64, 0.0);
+    /// }
+    /// let original = canvas.crop(0, 0, 10, 20);
+    ///
+    /// let crop = canvas.crop(2, 3, 4, 5);
+    /// canvas.paste(&crop, 2, 3);
+    /// for (x, y, &color) in canvas.enumerate_pixels() {
+    ///     assert_eq!(color, original[[x, y]]);
+    /// }
+    /// ```
+    ///
+    /// Pasting partially outside:
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 20);
+    /// let mut src = Canvas::new(4, 4);
+    /// src.fill(Color::WHITE);
+    /// canvas.paste(&src, 8, 18);
+    /// canvas.paste(&src, 100, 100);
+    /// assert_eq!(canvas[[9, 19]], Color::WHITE);
+    /// assert_eq!(canvas[[7, 19]], Color::BLACK);
+    /// ```
+    pub fn paste(&mut self, src: &Canvas, x: usize, y: usize) {
+        let end = self.width.min(x.saturating_add(src.width));
+        if x >= end {
+            return;
+        }
+        let rows = self.pixels.chunks_mut(self.width).skip(y);
+        for (row, src_row) in rows.zip(src.rows()) {
+            row[x..end].copy_from_slice(&src_row[..end - x]);
+        }
+    }
+
+    /// Returns the largest difference between a channel of a pixel in `self` and the same channel
+    /// of the same pixel in `other`. This is 0 if the canvases are equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` don't have the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let a = Canvas::new(10, 20);
+    /// let mut b = Canvas::new(10, 20);
+    /// assert_eq!(a.max_channel_diff(&b), 0.0);
+    ///
+    /// b[[3, 4]] = Color::new(0.0, 0.25, -0.5);
+    /// assert_eq!(a.max_channel_diff(&b), 0.5);
+    /// ```
+    pub fn max_channel_diff(&self, other: &Self) -> f64 {
+        self.assert_same_size(other);
+        self.pixels
+            .iter()
+            .zip(other.pixels.iter())
+            .map(|(a, b)| channel_diff(*a, *b))
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the coordinates `(x, y)` of the first pixel where a channel of `self` and `other`
+    /// differ by more than `eps`, going row by row from the top left. Returns `None` if all
+    /// pixels are equal within `eps`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` don't have the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let a = Canvas::new(10, 20);
+    /// let mut b = Canvas::new(10, 20);
+    /// assert_eq!(a.first_diff(&b, 0.0), None);
+    ///
+    /// b[[3, 4]] = Color::gray(0.1);
+    /// b[[7, 15]] = Color::gray(0.5);
+    /// assert_eq!(a.first_diff(&b, 0.01), Some((3, 4)));
+    /// assert_eq!(a.first_diff(&b, 0.2), Some((7, 15)));
+    /// assert_eq!(a.first_diff(&b, 0.5), None);
+    /// ```
+    pub fn first_diff(&self, other: &Self, eps: f64) -> Option<(usize, usize)> {
+        self.assert_same_size(other);
+        self.pixels
+            .iter()
+            .zip(other.pixels.iter())
+            .position(|(a, b)| channel_diff(*a, *b) > eps)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    fn assert_same_size(&self, other: &Self) {
+        if self.width != other.width || self.height != other.height {
+            panic!(
+                "cannot compare a {}x{} canvas with a {}x{} canvas",
+                self.width, self.height, other.width, other.height
+            );
+        }
     }
 
     /// Writes `self` to `file` in PPM format. See the module's documentation for an example.
+    ///
+    /// Each row is formatted into an in-memory buffer first and written with a single call, so
+    /// unbuffered writers like [std::fs::File] don't get hit once per pixel. The output is the
+    /// same as writing every pixel separately:
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    /// use std::io::Write;
+    ///
+    /// let mut canvas = Canvas::new(5, 3);
+    /// canvas[[0, 0]] = Color::new(1.5, 0.0, 0.0);
+    /// canvas[[2, 1]] = Color::new(0.0, 0.5, 0.0);
+    /// canvas[[4, 2]] = Color::new(-0.5, 0.0, 1.0);
+    ///
+    /// let mut expected = Vec::new();
+    /// write!(expected, "P3\n5 3\n255\n").unwrap();
+    /// for y in 0..3 {
+    ///     for x in 0..5 {
+    ///         writeln!(expected, "{}", canvas[[x, y]]).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let mut output = Vec::new();
+    /// canvas.to_ppm(&mut output).unwrap();
+    /// assert_eq!(output, expected);
+    /// ```
     pub fn to_ppm(&self, file: &mut dyn Write) -> Result<(), Error> {
-        write!(file, "P3\n{} {}\n255\n", self.width(), self.height())?;
-        for row in self.pixels.iter() {
+        self.to_ppm_maxval(file, 255)
+    }
+
+    /// Writes `self` to `file` in PPM format, with channels going from 0 to `maxval` instead of
+    /// 255. A channel `c` is written as `c * (maxval + 1)`, clamped between 0 and `maxval`, so
+    /// `maxval` 255 gives the same output as [Canvas::to_ppm]. Every channel is still written as a
+    /// decimal number, so the file stays plain text. Use 65535 for 16 bits per channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::canvas::Canvas;
+    /// use truster::color::Color;
+    ///
+    /// let mut canvas = Canvas::new(2, 1);
+    /// canvas[[0, 0]] = Color::gray(0.5);
+    /// canvas[[1, 0]] = Color::new(1.0, 0.0, -1.0);
+    ///
+    /// let mut output = Vec::new();
+    /// canvas.to_ppm_maxval(&mut output, 65535).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(output).unwrap(),
+    ///     "P3\n2 1\n65535\n32768 32768 32768\n65535 0 0\n"
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `maxval` is 0, which PPM does not allow.
+    pub fn to_ppm_maxval(&self, file: &mut dyn Write, maxval: u16) -> Result<(), Error> {
+        if maxval == 0 {
+            panic!("PPM maxval must be at least 1");
+        }
+
+        write!(file, "P3\n{} {}\n{}\n", self.width(), self.height(), maxval)?;
+        // "65535 65535 65535\n" is the longest a pixel can get.
+        let mut buffer = Vec::with_capacity(self.width() * 18);
+        for row in self.rows() {
+            buffer.clear();
             for color in row {
-                writeln!(file, "{}", color)?;
+                writeln!(
+                    buffer,
+                    "{} {} {}",
+                    scale_channel(color.r(), maxval),
+                    scale_channel(color.g(), maxval),
+                    scale_channel(color.b(), maxval)
+                )?;
             }
+            file.write_all(&buffer)?;
         }
         Ok(())
     }
 }
 
+fn scale_channel(value: f64, maxval: u16) -> i64 {
+    ((value * (maxval as f64 + 1.0)) as i64).clamp(0, maxval as i64)
+}
+
+fn channel_diff(a: Color, b: Color) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]).abs()).fold(0.0, f64::max)
+}
+
+/// Indexes a pixel by `[x, y]`. Both coordinates are checked, so a column past the width panics
+/// instead of wrapping around to the next row:
+///
+/// ```should_panic
+/// # use truster::canvas::Canvas;
+/// let canvas = Canvas::new(4, 3);
+/// let _ = canvas[[4, 0]];
+/// ```
 impl Index<[usize; 2]> for Canvas {
     type Output = Color;
 
     fn index(&self, index: [usize; 2]) -> &Self::Output {
-        &self.pixels[index[1]][index[0]]
+        &self.pixels[self.offset(index[0], index[1])]
     }
 }
 
+/// Mutably indexes a pixel by `[x, y]`, with the same bounds checks as indexing:
+///
+/// ```should_panic
+/// # use truster::canvas::Canvas;
+/// use truster::color::Color;
+///
+/// let mut canvas = Canvas::new(4, 3);
+/// canvas[[0, 3]] = Color::gray(1.0);
+/// ```
 impl IndexMut<[usize; 2]> for Canvas {
     fn index_mut(&mut self, index: [usize; 2]) -> &mut Self::Output {
-        &mut self.pixels[index[1]][index[0]]
+        let offset = self.offset(index[0], index[1]);
+        &mut self.pixels[offset]
     }
 }
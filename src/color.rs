@@ -13,6 +13,17 @@
 //! assert_eq!(c.b(), 1.7);
 //! ```
 //!
+//! Common colors are available as constants, and grays can be made with [Color::gray]:
+//! ```
+//! # use truster::color::Color;
+//! assert_eq!(Color::WHITE, Color::new(1.0, 1.0, 1.0));
+//! assert_eq!(Color::BLACK, Color::new(0.0, 0.0, 0.0));
+//! assert_eq!(Color::RED, Color::new(1.0, 0.0, 0.0));
+//! assert_eq!(Color::GREEN, Color::new(0.0, 1.0, 0.0));
+//! assert_eq!(Color::BLUE, Color::new(0.0, 0.0, 1.0));
+//! assert_eq!(Color::gray(0.5), Color::new(0.5, 0.5, 0.5));
+//! ```
+//!
 //! Individual components can be accessed with their respective methods, or with indexing:
 //!
 //! ... or with indexing:
@@ -47,6 +58,13 @@
 //! assert_eq!(c1 - c2, Color::new(-2.0, -4.0, -6.0));
 //! ```
 //!
+//! - Negation
+//! ```
+//! # use truster::color::Color;
+//! let c = Color::new(1.0, -2.0, 3.0);
+//! assert_eq!(-c, Color::new(-1.0, 2.0, -3.0));
+//! ```
+//!
 //! - Scalar multiplication
 //! ```
 //! # use truster::color::Color;
@@ -55,6 +73,15 @@
 //! assert_eq!(c * 0.5, Color::new(0.5, -1.0, 1.5));
 //! ```
 //!
+//! - Scalar division
+//! ```
+//! # use truster::color::Color;
+//! let mut c = Color::new(1.0, -2.0, 3.0);
+//! assert_eq!(c / 2.0, Color::new(0.5, -1.0, 1.5));
+//! c /= 4.0;
+//! assert_eq!(c, Color::new(0.25, -0.5, 0.75));
+//! ```
+//!
 //! - Hadamard multiplication
 //! ```
 //! # use truster::color::Color;
@@ -64,7 +91,9 @@
 //! ```
 
 use std::fmt::Display;
-use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
 
 /// Represents an RGB color. See the module's documentation for more info.
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
@@ -75,11 +104,27 @@ pub struct Color {
 }
 
 impl Color {
+    /// White, (1, 1, 1).
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0);
+    /// Black, (0, 0, 0).
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0);
+    /// Red, (1, 0, 0).
+    pub const RED: Self = Self::new(1.0, 0.0, 0.0);
+    /// Green, (0, 1, 0).
+    pub const GREEN: Self = Self::new(0.0, 1.0, 0.0);
+    /// Blue, (0, 0, 1).
+    pub const BLUE: Self = Self::new(0.0, 0.0, 1.0);
+
     /// Creates a new color with the given rgb components.
-    pub fn new(r: f64, g: f64, b: f64) -> Self {
+    pub const fn new(r: f64, g: f64, b: f64) -> Self {
         Self { r, g, b }
     }
 
+    /// Creates a new gray color where all components are `v`.
+    pub const fn gray(v: f64) -> Self {
+        Self::new(v, v, v)
+    }
+
     /// Returns `self`'s red component.
     pub fn r(&self) -> f64 {
         self.r
@@ -94,6 +139,120 @@ impl Color {
     pub fn b(&self) -> f64 {
         self.b
     }
+
+    /// Returns the relative luminance of `self`, using the Rec. 709 weights. This is the value of
+    /// the gray color that `self` converts to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::color::Color;
+    /// assert_eq!(Color::gray(0.5).luminance(), 0.5);
+    /// assert_eq!(Color::WHITE.luminance(), 1.0);
+    /// assert_eq!(Color::GREEN.luminance(), 0.7152);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Returns a color with `f` applied to each component of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::color::Color;
+    /// let c = Color::new(0.25, -0.5, 1.5);
+    /// assert_eq!(c.map(|c| c * 2.0), Color::new(0.5, -1.0, 3.0));
+    /// assert_eq!(c.map(|c| c.clamp(0.0, 1.0)), Color::new(0.25, 0.0, 1.0));
+    /// ```
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self::new(f(self.r), f(self.g), f(self.b))
+    }
+
+    /// Returns a color with `f` applied to each pair of corresponding components of `self` and
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::color::Color;
+    /// let c1 = Color::new(0.2, 0.9, 0.5);
+    /// let c2 = Color::new(0.7, 0.1, 0.5);
+    /// assert_eq!(c1.zip_map(c2, f64::max), Color::new(0.7, 0.9, 0.5));
+    /// assert_eq!(c1.zip_map(c2, f64::min), Color::new(0.2, 0.1, 0.5));
+    /// ```
+    pub fn zip_map(&self, other: Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        Self::new(f(self.r, other.r), f(self.g, other.g), f(self.b, other.b))
+    }
+
+    /// Blends `self` and `other` with the screen blend mode, `1 - (1 - a) * (1 - b)` per
+    /// component. It is the opposite of multiplying: the result is never darker than either
+    /// color. Screening with white gives white, and screening with black changes nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::color::Color;
+    /// let c = Color::new(0.25, 0.5, 0.75);
+    /// assert_eq!(Color::WHITE.screen(c), Color::WHITE);
+    /// assert_eq!(Color::BLACK.screen(c), c);
+    /// assert_eq!(c.screen(Color::gray(0.5)), Color::new(0.625, 0.75, 0.875));
+    /// ```
+    pub fn screen(&self, other: Self) -> Self {
+        self.zip_map(other, |a, b| 1.0 - (1.0 - a) * (1.0 - b))
+    }
+
+    /// Blends `self` and `other` with the overlay blend mode, with `self` as the base. Where a
+    /// component of `self` is below 0.5, it is multiplied with `other`, otherwise it is
+    /// screened with it, both times doubled. Dark parts get darker and light parts lighter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::color::Color;
+    /// let base = Color::new(0.25, 0.5, 0.75);
+    /// assert_eq!(
+    ///     base.overlay(Color::gray(0.5)),
+    ///     base,
+    /// );
+    /// assert_eq!(
+    ///     base.overlay(Color::gray(0.25)),
+    ///     Color::new(0.125, 0.25, 0.625),
+    /// );
+    /// ```
+    pub fn overlay(&self, other: Self) -> Self {
+        self.zip_map(other, |a, b| {
+            if a < 0.5 {
+                2.0 * a * b
+            } else {
+                1.0 - 2.0 * (1.0 - a) * (1.0 - b)
+            }
+        })
+    }
+
+    /// Returns the average of `colors`. Returns black if `colors` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::color::Color;
+    /// let colors = [
+    ///     Color::new(1.0, 0.0, 0.5),
+    ///     Color::new(0.0, 1.0, 0.5),
+    /// ];
+    /// assert_eq!(Color::mean(&colors), Color::new(0.5, 0.5, 0.5));
+    /// assert_eq!(Color::mean(&[]), Color::new(0.0, 0.0, 0.0));
+    /// ```
+    pub fn mean(colors: &[Color]) -> Self {
+        if colors.is_empty() {
+            return Self::default();
+        }
+        let mut sum = Self::default();
+        for &color in colors {
+            sum += color;
+        }
+        sum / colors.len() as f64
+    }
 }
 
 impl Display for Color {
@@ -137,6 +296,14 @@ impl SubAssign for Color {
     }
 }
 
+impl Neg for Color {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::Output::new(-self.r, -self.g, -self.b)
+    }
+}
+
 impl Mul for Color {
     type Output = Self;
 
@@ -169,6 +336,22 @@ impl MulAssign<f64> for Color {
     }
 }
 
+impl Div<f64> for Color {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self::Output::new(self.r / rhs, self.g / rhs, self.b / rhs)
+    }
+}
+
+impl DivAssign<f64> for Color {
+    fn div_assign(&mut self, rhs: f64) {
+        self.r /= rhs;
+        self.g /= rhs;
+        self.b /= rhs;
+    }
+}
+
 impl Index<usize> for Color {
     type Output = f64;
 
@@ -0,0 +1,168 @@
+//! Renders random scenes to catch panics and non-finite colors. The scenes are seeded, so a
+//! failure can be reproduced by rendering the scene of the failing seed.
+
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use crate::camera::{Camera, Config, SamplePattern};
+use crate::color::Color;
+use crate::light::PointLight;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::sampling::Rng;
+use crate::shape::{Disk, Plane, Quad, Shape, Sphere, Torus, Triangle};
+use crate::tuple::Tuple;
+use crate::world::World;
+
+const SEEDS: u64 = 16;
+const SHAPES: usize = 6;
+
+fn range(rng: &mut Rng, min: f64, max: f64) -> f64 {
+    min + rng.next_f64() * (max - min)
+}
+
+fn random_point(rng: &mut Rng, extent: f64) -> Tuple {
+    Tuple::point(
+        range(rng, -extent, extent),
+        range(rng, -extent, extent),
+        range(rng, -extent, extent),
+    )
+}
+
+/// Returns a random scale for one axis. Sometimes the scale is tiny, so the transform is nearly
+/// singular.
+fn random_scale(rng: &mut Rng) -> f64 {
+    if rng.next_f64() < 0.1 {
+        range(rng, 1e-6, 1e-3)
+    } else {
+        range(rng, 0.1, 3.0)
+    }
+}
+
+fn random_transform(rng: &mut Rng) -> Matrix {
+    let position = random_point(rng, 4.0);
+    Matrix::translation(position.x(), position.y(), position.z())
+        * &Matrix::rotation_x(range(rng, 0.0, 2.0 * PI))
+        * &Matrix::rotation_y(range(rng, 0.0, 2.0 * PI))
+        * &Matrix::rotation_z(range(rng, 0.0, 2.0 * PI))
+        * &Matrix::scaling(random_scale(rng), random_scale(rng), random_scale(rng))
+}
+
+fn random_material(rng: &mut Rng) -> Material {
+    Material::builder()
+        .color(Color::new(rng.next_f64(), rng.next_f64(), rng.next_f64()))
+        .ambient(range(rng, 0.0, 0.3))
+        .diffuse(rng.next_f64())
+        .specular(rng.next_f64())
+        .shininess(range(rng, 0.0, 300.0))
+        .reflective(if rng.next_f64() < 0.3 {
+            rng.next_f64()
+        } else {
+            0.0
+        })
+        .transparency(if rng.next_f64() < 0.3 {
+            rng.next_f64()
+        } else {
+            0.0
+        })
+        .refractive_index(range(rng, 1.0, 2.5))
+        .build()
+}
+
+fn random_shape(rng: &mut Rng) -> Box<dyn Shape> {
+    let mut shape: Box<dyn Shape> = match (rng.next_f64() * 7.0) as usize {
+        0 => Box::new(Plane::new()),
+        1 => Box::new(Disk::new()),
+        2 => Box::new(Quad::new()),
+        3 => Box::new(Torus::new()),
+        4 => Box::new(Triangle::new(
+            random_point(rng, 1.0),
+            random_point(rng, 1.0),
+            random_point(rng, 1.0),
+        )),
+        _ => Box::new(Sphere::new()),
+    };
+    shape.set_transform(random_transform(rng));
+    shape.set_material(random_material(rng));
+    shape
+}
+
+fn random_world(rng: &mut Rng) -> World {
+    let mut world = World::new();
+    for _ in 0..SHAPES {
+        world.add_shape(Rc::from(random_shape(rng)));
+    }
+    for _ in 0..1 + (rng.next_f64() * 2.0) as usize {
+        let color = Color::new(rng.next_f64(), rng.next_f64(), rng.next_f64());
+        world.add_light(Rc::new(PointLight::new(random_point(rng, 10.0), color)));
+    }
+    world
+}
+
+fn random_camera(rng: &mut Rng, seed: u64) -> Camera {
+    let mut from = random_point(rng, 10.0);
+    if from.norm() < 1.0 {
+        from = Tuple::point(0.0, 0.0, -5.0);
+    }
+    Camera::new(Config {
+        hsize: 12,
+        vsize: 8,
+        fov: range(rng, 0.3, 2.5),
+        from,
+        at: Tuple::ORIGIN,
+        sample_pattern: SamplePattern::Random { samples: 2, seed },
+        ..Config::default()
+    })
+}
+
+#[test]
+fn random_scenes_render_finite_colors() {
+    for seed in 0..SEEDS {
+        let mut rng = Rng::new(seed);
+        let world = random_world(&mut rng);
+        let camera = random_camera(&mut rng, seed);
+
+        let canvas = camera.render(&world);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let color = canvas[[x, y]];
+                for channel in 0..3 {
+                    assert!(
+                        color[channel].is_finite(),
+                        "seed {}: pixel ({}, {}) is {}",
+                        seed,
+                        x,
+                        y,
+                        color
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn degenerate_rays_give_finite_colors() {
+    for seed in 0..SEEDS {
+        let mut rng = Rng::new(seed);
+        let world = random_world(&mut rng);
+
+        // Rays don't need a normalized direction, but one without a direction can't hit anything.
+        for &length in &[0.0, 1e-6, 1e-3, 1e3, 1e6] {
+            let direction = random_point(&mut rng, 1.0) - Tuple::ORIGIN;
+            let direction = direction.normalized() * length;
+            let ray = Ray::new(random_point(&mut rng, 5.0), direction);
+            let color = world.color_at(&ray);
+            for channel in 0..3 {
+                assert!(
+                    color[channel].is_finite(),
+                    "seed {}: ray {:?} gives {}",
+                    seed,
+                    ray,
+                    color
+                );
+            }
+        }
+    }
+}
@@ -3,13 +3,25 @@
 
 use std::cmp::Ordering::{self, Equal, Greater, Less};
 use std::fmt::{Debug, Error, Formatter};
+use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
 use crate::ray::Ray;
 use crate::shape::Shape;
 use crate::tuple::Tuple;
-
+use crate::EPSILON;
+
+/// The id of the next intersection. Ids only need to be unique, not ordered with respect to
+/// anything else, so the counter is updated with relaxed ordering. That is still atomic, so
+/// threads creating intersections at the same time never get the same id, but it doesn't force
+/// the synchronization `SeqCst` would.
+///
+/// The counter wraps around when it overflows. With 64 bit `usize`, that takes centuries at a
+/// billion intersections per second. With 32 bit `usize`, it can happen after about 4 billion
+/// intersections, after which ids are reused. This is harmless in practice, because only
+/// intersections that exist at the same time are compared, and those were created within a few
+/// rays of each other.
 static ID: AtomicUsize = AtomicUsize::new(0);
 
 /// Stores some information about intersections between rays and shapes.
@@ -19,6 +31,7 @@ pub struct Intersection {
     id: usize,
     t: f64,
     shape: Rc<dyn Shape>,
+    uv: Option<(f64, f64)>,
 }
 
 impl Intersection {
@@ -29,7 +42,18 @@ impl Intersection {
         Self {
             t,
             shape,
-            id: ID.fetch_add(1, AtomicOrdering::SeqCst),
+            uv: None,
+            id: ID.fetch_add(1, AtomicOrdering::Relaxed),
+        }
+    }
+
+    /// Returns a new [Intersection] like [Intersection::new], which also remembers where on the
+    /// surface of `shape` it is, in (`u`, `v`) coordinates. Triangles use this to store the
+    /// barycentric coordinates of the intersection.
+    pub fn with_uv(t: f64, shape: Rc<dyn Shape>, u: f64, v: f64) -> Self {
+        Self {
+            uv: Some((u, v)),
+            ..Self::new(t, shape)
         }
     }
 
@@ -42,18 +66,33 @@ impl Intersection {
     pub fn shape(&self) -> Rc<dyn Shape> {
         Rc::clone(&self.shape)
     }
+
+    /// Returns `self`'s u coordinate, if it has one. See [Intersection::with_uv].
+    pub fn u(&self) -> Option<f64> {
+        self.uv.map(|(u, _)| u)
+    }
+
+    /// Returns `self`'s v coordinate, if it has one. See [Intersection::with_uv].
+    pub fn v(&self) -> Option<f64> {
+        self.uv.map(|(_, v)| v)
+    }
 }
 
 impl Debug for Intersection {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        writeln!(
-            f,
-            "{{ id: {}, t: {}, shape: <something> }}",
-            self.id, self.t
-        )
+        f.debug_struct("Intersection")
+            .field("id", &self.id)
+            .field("t", &self.t)
+            .field("uv", &self.uv)
+            .field("shape", &self.shape)
+            .finish()
     }
 }
 
+/// Intersections are equal if they are the same intersection, or clones of it. Two intersections
+/// at the same distance of the same shape, from different rays or computed twice, are not equal.
+/// This is intentional, it lets a hit be found again in a list of intersections. To compare the
+/// distances of intersections, use [Distances::ts].
 impl PartialEq for Intersection {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -80,25 +119,102 @@ impl PartialOrd for Intersection {
 
 /// Hit holds a hit function which should return an instance of `T` if there is a hit. None
 /// otherwise.
-/// Known implementations: [Vec<Intersection>].
+/// Known implementations: [Vec<Intersection>], [`[Intersection]`](slice) and [Intersections].
 pub trait Hit<T = Intersection> {
     fn hit(&self) -> Option<&T>;
 }
 
-/// Implement [Hit] to get the first intersection which is a hit. The list should be sorted. If
-/// there are no hits, `None` is returned.
+/// Implement [Hit] to get the intersection which is a hit, that is the one with the smallest
+/// positive distance. The slice does not have to be sorted. If there are no hits, `None` is
+/// returned.
+impl Hit for [Intersection] {
+    fn hit(&self) -> Option<&Intersection> {
+        self.iter()
+            .filter(|i| i.t > 0.0)
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+    }
+}
+
+/// Implement [Hit] for vectors. See the implementation for slices.
 impl Hit for Vec<Intersection> {
     fn hit(&self) -> Option<&Intersection> {
-        for i in self.iter() {
-            if i.t > 0.0 {
-                return Some(i);
-            }
-        }
-        None
+        self.as_slice().hit()
+    }
+}
+
+/// Distances holds a function to get the distances of a list of intersections, in order. This is
+/// useful for comparing the results of intersecting, which [Intersection]'s [PartialEq] doesn't
+/// do.
+/// Known implementations: [Vec<Intersection>] and [`[Intersection]`](slice).
+///
+/// # Examples
+///
+/// ```
+/// # use truster::intersection::Distances;
+/// use truster::ray::Ray;
+/// use truster::shape::{Shape, Sphere};
+/// use truster::tuple::Tuple;
+///
+/// let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+/// let sphere = Sphere::new();
+/// assert_eq!(sphere.intersect(&ray).ts(), sphere.intersect(&ray).ts());
+/// assert_ne!(sphere.intersect(&ray), sphere.intersect(&ray));
+/// ```
+pub trait Distances {
+    fn ts(&self) -> Vec<f64>;
+}
+
+impl Distances for [Intersection] {
+    fn ts(&self) -> Vec<f64> {
+        self.iter().map(Intersection::t).collect()
     }
 }
 
-const EPS: f64 = 0.000_001;
+impl Distances for Vec<Intersection> {
+    fn ts(&self) -> Vec<f64> {
+        self.as_slice().ts()
+    }
+}
+
+/// A list of intersections which does not have to be sorted. Its [Hit] implementation finds the
+/// intersection with the smallest positive distance in a single pass over the list, like the one
+/// for slices.
+#[derive(Clone, Debug, Default)]
+pub struct Intersections(Vec<Intersection>);
+
+impl Intersections {
+    /// Returns a new list holding `intersections`, in any order.
+    pub fn new(intersections: Vec<Intersection>) -> Self {
+        Self(intersections)
+    }
+
+    /// Returns the intersections `self` is holding.
+    pub fn into_inner(self) -> Vec<Intersection> {
+        self.0
+    }
+}
+
+impl From<Vec<Intersection>> for Intersections {
+    fn from(intersections: Vec<Intersection>) -> Self {
+        Self::new(intersections)
+    }
+}
+
+impl Deref for Intersections {
+    type Target = [Intersection];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Implement [Hit] to get the intersection with the smallest positive distance. If there are no
+/// hits, `None` is returned.
+impl Hit for Intersections {
+    fn hit(&self) -> Option<&Intersection> {
+        self.0.hit()
+    }
+}
 
 /// HitRecord stores some information relating to ray-shape intersections. Cloning is near constant
 /// time and memory.
@@ -109,20 +225,30 @@ pub struct HitRecord {
     point: Tuple,
     over_point: Tuple,
     under_point: Tuple,
+    bias: f64,
     eye: Tuple,
     normal: Tuple,
+    reflect: Tuple,
+    #[allow(dead_code)]
     inside: bool,
+    n1: f64,
+    n2: f64,
+    uv: Option<(f64, f64)>,
+    time: f64,
 }
 
 impl HitRecord {
     /// Returns a new [HitRecord] corresponding to the given intersection and ray.
+    /// The refractive indices on both sides of the hit are assumed to be 1. Use
+    /// [HitRecord::with_intersections] to compute them. The eye vector is normalized, so `ray`
+    /// doesn't need to have a normalized direction.
     pub fn new(intersection: &Intersection, ray: &Ray) -> Self {
         let t = intersection.t;
         let shape = Rc::clone(&intersection.shape);
         let point = ray.at(t);
-        let eye = -ray.direction();
+        let eye = -ray.direction().normalized();
 
-        let mut normal = shape.normal_at(point);
+        let mut normal = shape.normal_at_time(point, ray.time());
         let inside = if normal.dot(eye) < 0.0 {
             normal = -normal;
             true
@@ -130,8 +256,12 @@ impl HitRecord {
             false
         };
 
-        let over_point = point + normal * EPS;
-        let under_point = point - normal * EPS;
+        // Rounding errors in the hit point grow with its distance from the origin, so the offset
+        // does too. Otherwise shapes far away shadow themselves.
+        let bias = EPSILON * (1.0 + (point - Tuple::ORIGIN).norm());
+        let over_point = point + normal * bias;
+        let under_point = point - normal * bias;
+        let reflect = ray.direction().reflect(normal);
 
         Self {
             t,
@@ -139,12 +269,49 @@ impl HitRecord {
             point,
             over_point,
             under_point,
+            bias,
             eye,
             normal,
+            reflect,
             inside,
+            n1: 1.0,
+            n2: 1.0,
+            uv: intersection.uv,
+            time: ray.time(),
         }
     }
 
+    /// Returns a new [HitRecord] corresponding to `hit` and `ray`, and computes the refractive
+    /// indices on both sides of the hit. `intersections` should be all intersections of `ray`
+    /// sorted by distance, like [World::intersect](crate::world::World::intersect) returns, and
+    /// contain `hit`.
+    ///
+    /// The intersections are walked in order while keeping track of the shapes the ray is inside
+    /// of. The refractive index of a point is that of the most recently entered shape, or 1 if
+    /// the point is not inside any shape.
+    pub fn with_intersections(
+        hit: &Intersection,
+        ray: &Ray,
+        intersections: &[Intersection],
+    ) -> Self {
+        let mut rec = Self::new(hit, ray);
+        let (n1, n2) = refractive_indices(hit, intersections);
+        rec.n1 = n1;
+        rec.n2 = n2;
+        rec
+    }
+
+    /// Returns the distance along the ray to the hit.
+    pub fn t(&self) -> f64 {
+        self.t
+    }
+
+    /// Returns the [time](Ray::time) of the ray which made the hit. Rays spawned at the hit, like
+    /// reflected and shadow rays, are sent at the same time.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
     /// Returns the shape `self` is holding.
     pub fn shape(&self) -> Rc<dyn Shape> {
         Rc::clone(&self.shape)
@@ -160,26 +327,118 @@ impl HitRecord {
         self.normal
     }
 
+    /// Returns true if the ray hit the shape from the inside, so it is leaving the shape, false
+    /// if it is entering it. When this is true, [HitRecord::normal] is flipped to face the ray.
+    pub fn inside(&self) -> bool {
+        self.inside
+    }
+
     /// Returns the eye `self` is holding.
     pub fn eye(&self) -> Tuple {
         self.eye
     }
 
-    /// Returns `self`'s point slightly outwards from from the shape.
+    /// Returns the direction of the ray reflected about the normal.
+    pub fn reflect(&self) -> Tuple {
+        self.reflect
+    }
+
+    /// Returns `self`'s point slightly outwards from from the shape. The offset is EPSILON times
+    /// one more than the distance of the point from the origin, to stay ahead of rounding errors.
     pub fn over_point(&self) -> Tuple {
         self.over_point
     }
 
-    /// Returns `self`'s point slightly inwards from from the shape.
+    /// Returns `self`'s point slightly inwards from from the shape, by the same offset as
+    /// [HitRecord::over_point].
     pub fn under_point(&self) -> Tuple {
         self.under_point
     }
+
+    /// Returns the distance between `self`'s point and [HitRecord::over_point]. Pass it to
+    /// [Ray::offset_origin] to start rays spawned at the hit off the surface.
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Returns the fraction of light which is reflected at the hit, according to Schlick's
+    /// approximation of the Fresnel equations. The rest of the light is refracted. This is 1 when
+    /// there is total internal reflection.
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eye.dot(self.normal);
+
+        if self.n1 > self.n2 {
+            let ratio = self.n1 / self.n2;
+            let sin2_t = ratio * ratio * (1.0 - cos * cos);
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+            cos = (1.0 - sin2_t).sqrt();
+        }
+
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+
+    /// Returns the refractive index of the material the ray is leaving.
+    pub fn n1(&self) -> f64 {
+        self.n1
+    }
+
+    /// Returns the refractive index of the material the ray is entering.
+    pub fn n2(&self) -> f64 {
+        self.n2
+    }
+
+    /// Returns the u coordinate of the intersection `self` was made from, if it has one.
+    pub fn u(&self) -> Option<f64> {
+        self.uv.map(|(u, _)| u)
+    }
+
+    /// Returns the v coordinate of the intersection `self` was made from, if it has one.
+    pub fn v(&self) -> Option<f64> {
+        self.uv.map(|(_, v)| v)
+    }
+}
+
+fn refractive_indices(hit: &Intersection, intersections: &[Intersection]) -> (f64, f64) {
+    fn last_index(containers: &[Rc<dyn Shape>]) -> f64 {
+        containers
+            .last()
+            .map(|shape| shape.material().refractive_index)
+            .unwrap_or(1.0)
+    }
+
+    let mut containers: Vec<Rc<dyn Shape>> = Vec::new();
+    let mut n1 = 1.0;
+    for intersection in intersections {
+        if intersection == hit {
+            n1 = last_index(&containers);
+        }
+
+        match containers
+            .iter()
+            .position(|shape| Rc::ptr_eq(shape, &intersection.shape))
+        {
+            Some(index) => {
+                containers.remove(index);
+            }
+            None => containers.push(Rc::clone(&intersection.shape)),
+        }
+
+        if intersection == hit {
+            return (n1, last_index(&containers));
+        }
+    }
+    (n1, 1.0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::material::Material;
     use crate::matrix::Matrix;
+    use crate::shape::plane::Plane;
     use crate::shape::sphere::Sphere;
 
     #[test]
@@ -228,6 +487,50 @@ mod tests {
         assert_eq!(i, &i4);
     }
 
+    #[test]
+    fn hit_unsorted() {
+        let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+        let i1 = Intersection::new(5.0, Rc::clone(&sphere));
+        let i2 = Intersection::new(7.0, Rc::clone(&sphere));
+        let i3 = Intersection::new(-3.0, Rc::clone(&sphere));
+        let i4 = Intersection::new(2.0, Rc::clone(&sphere));
+        let is = vec![i1, i2, i3, Intersection::clone(&i4)];
+        let i = is.hit().unwrap();
+        assert_eq!(i, &i4);
+    }
+
+    #[test]
+    fn hit_slice() {
+        let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+        let i1 = Intersection::new(-1.0, Rc::clone(&sphere));
+        let i2 = Intersection::new(2.0, Rc::clone(&sphere));
+        let i3 = Intersection::new(3.0, Rc::clone(&sphere));
+        let is = [i1, Intersection::clone(&i2), i3];
+        let i = is[..].hit().unwrap();
+        assert_eq!(i, &i2);
+        assert_eq!(is[..1].hit(), None);
+    }
+
+    #[test]
+    fn intersections_list() {
+        let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+        let i1 = Intersection::new(5.0, Rc::clone(&sphere));
+        let i2 = Intersection::new(2.0, Rc::clone(&sphere));
+        let is = Intersections::from(vec![i1, Intersection::clone(&i2)]);
+        assert_eq!(is.len(), 2);
+        assert_eq!(is.hit(), Some(&i2));
+        assert_eq!(is.into_inner()[1], i2);
+    }
+
+    #[test]
+    fn debug_shows_shape_and_t() {
+        let intersection = Intersection::new(4.5, Rc::new(Sphere::new()));
+        let debug = format!("{:?}", intersection);
+        assert!(debug.starts_with("Intersection {"), "{}", debug);
+        assert!(debug.contains("t: 4.5"), "{}", debug);
+        assert!(debug.contains("shape: Sphere { transform: "), "{}", debug);
+    }
+
     #[test]
     fn hit_record_outside() {
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
@@ -256,6 +559,33 @@ mod tests {
         assert!(rec.inside);
     }
 
+    #[test]
+    fn hit_record_eye_normalized() {
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 4.0));
+        let intersection = Intersection::new(1.0, Rc::new(Sphere::new()));
+        let rec = HitRecord::new(&intersection, &ray);
+
+        assert_eq!(rec.point, Tuple::point(0.0, 0.0, -1.0));
+        assert_eq!(rec.eye, Tuple::vector(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn hit_record_inside_accessor() {
+        let shape: Rc<dyn Shape> = Rc::new(Sphere::new());
+
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = HitRecord::new(&Intersection::new(4.0, Rc::clone(&shape)), &ray);
+        assert_eq!(rec.point(), Tuple::point(0.0, 0.0, -1.0));
+        assert_eq!(rec.normal(), Tuple::vector(0.0, 0.0, -1.0));
+        assert!(!rec.inside());
+
+        let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = HitRecord::new(&Intersection::new(1.0, shape), &ray);
+        assert_eq!(rec.point(), Tuple::point(0.0, 0.0, 1.0));
+        assert_eq!(rec.normal(), Tuple::vector(0.0, 0.0, -1.0));
+        assert!(rec.inside());
+    }
+
     #[test]
     fn hit_record_over_under_point() {
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
@@ -263,9 +593,154 @@ mod tests {
         shape.set_transform(Matrix::translation(0.0, 0.0, 1.0));
         let intersection = Intersection::new(5.0, Rc::new(shape));
         let rec = HitRecord::new(&intersection, &ray);
-        assert!(rec.over_point.z() < -EPS / 2.0);
+        assert!(rec.over_point.z() < -EPSILON / 2.0);
         assert!(rec.point.z() > rec.over_point.z());
-        assert!(rec.under_point.z() > -EPS / 2.0);
+        assert!(rec.under_point.z() > -EPSILON / 2.0);
         assert!(rec.point.z() < rec.under_point.z());
     }
+
+    #[test]
+    fn hit_record_reflect() {
+        let shape: Rc<dyn Shape> = Rc::new(Plane::new());
+        let v = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::new(Tuple::point(0.0, 1.0, -1.0), Tuple::vector(0.0, -v, v));
+        let intersection = Intersection::new(2.0_f64.sqrt(), shape);
+        let rec = HitRecord::new(&intersection, &ray);
+        assert_eq!(rec.reflect(), Tuple::vector(0.0, v, v));
+    }
+
+    #[test]
+    fn hit_record_over_under_point_offset() {
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let intersection = Intersection::new(4.0, Rc::new(Sphere::new()));
+        let rec = HitRecord::new(&intersection, &ray);
+        // The hit is 1 away from the origin, so the offset is twice EPSILON.
+        assert_eq!(rec.over_point(), rec.point() + rec.normal() * 2.0 * EPSILON);
+        assert_eq!(
+            rec.under_point(),
+            rec.point() - rec.normal() * 2.0 * EPSILON
+        );
+        assert_eq!(rec.bias(), 2.0 * EPSILON);
+        let spawned = Ray::new(rec.point(), rec.reflect()).offset_origin(rec.normal(), rec.bias());
+        assert_eq!(spawned.origin(), rec.over_point());
+    }
+
+    #[test]
+    fn hit_record_refractive_indices() {
+        fn glass_sphere(transform: Matrix, refractive_index: f64) -> Rc<dyn Shape> {
+            let mut sphere = Sphere::new();
+            sphere.set_transform(transform);
+            sphere.set_material(Material {
+                transparency: 1.0,
+                refractive_index,
+                ..Material::default()
+            });
+            Rc::new(sphere)
+        }
+
+        let a = glass_sphere(Matrix::scaling(2.0, 2.0, 2.0), 1.5);
+        let b = glass_sphere(Matrix::translation(0.0, 0.0, -0.25), 2.0);
+        let c = glass_sphere(Matrix::translation(0.0, 0.0, 0.25), 2.5);
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -4.0), Tuple::vector(0.0, 0.0, 1.0));
+        let intersections = vec![
+            Intersection::new(2.0, Rc::clone(&a)),
+            Intersection::new(2.75, Rc::clone(&b)),
+            Intersection::new(3.25, Rc::clone(&c)),
+            Intersection::new(4.75, Rc::clone(&b)),
+            Intersection::new(5.25, Rc::clone(&c)),
+            Intersection::new(6.0, Rc::clone(&a)),
+        ];
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+
+        for (intersection, &(n1, n2)) in intersections.iter().zip(expected.iter()) {
+            let rec = HitRecord::with_intersections(intersection, &ray, &intersections);
+            assert_eq!(rec.n1(), n1);
+            assert_eq!(rec.n2(), n2);
+        }
+    }
+
+    fn glass_sphere() -> Rc<dyn Shape> {
+        let mut sphere = Sphere::new();
+        sphere.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Material::default()
+        });
+        Rc::new(sphere)
+    }
+
+    #[test]
+    fn schlick_total_internal_reflection() {
+        let shape = glass_sphere();
+        let v = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::new(Tuple::point(0.0, 0.0, v), Tuple::vector(0.0, 1.0, 0.0));
+        let intersections = vec![
+            Intersection::new(-v, Rc::clone(&shape)),
+            Intersection::new(v, shape),
+        ];
+        let rec = HitRecord::with_intersections(&intersections[1], &ray, &intersections);
+        assert_eq!(rec.schlick(), 1.0);
+    }
+
+    #[test]
+    fn schlick_perpendicular() {
+        let shape = glass_sphere();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+        let intersections = vec![
+            Intersection::new(-1.0, Rc::clone(&shape)),
+            Intersection::new(1.0, shape),
+        ];
+        let rec = HitRecord::with_intersections(&intersections[1], &ray, &intersections);
+        assert!((rec.schlick() - 0.04).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn schlick_small_angle() {
+        let shape = glass_sphere();
+        let ray = Ray::new(Tuple::point(0.0, 0.99, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+        let intersections = vec![Intersection::new(1.8589, shape)];
+        let rec = HitRecord::with_intersections(&intersections[0], &ray, &intersections);
+        assert!((rec.schlick() - 0.48873).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn hit_record_new_refractive_indices() {
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let intersection = Intersection::new(4.0, Rc::new(Sphere::new()));
+        let rec = HitRecord::new(&intersection, &ray);
+        assert_eq!(rec.n1(), 1.0);
+        assert_eq!(rec.n2(), 1.0);
+    }
+
+    #[test]
+    fn ids_unique_across_threads() {
+        use std::collections::HashSet;
+        use std::thread;
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                thread::spawn(|| {
+                    let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+                    (0..10_000)
+                        .map(|i| Intersection::new(i as f64, Rc::clone(&sphere)).id)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut ids = HashSet::new();
+        for thread in threads {
+            for id in thread.join().unwrap() {
+                assert!(ids.insert(id), "id {} was handed out twice", id);
+            }
+        }
+        assert_eq!(ids.len(), 8 * 10_000);
+    }
 }
@@ -2,15 +2,29 @@
 //! [The Ray Tracer Challenge](https://pragprog.com/titles/jbtracer/the-ray-tracer-challenge/),
 //! by Jamis Buck.
 
+/// The tolerance used for floating point comparisons throughout the crate. Hits are offset by this
+/// distance along the normal to avoid self-intersection ("shadow acne"), and rays which are
+/// closer than this to parallel with a flat shape miss it.
+pub const EPSILON: f64 = 0.000_001;
+
+pub mod background;
+pub mod bounds;
+pub mod bvh;
 pub mod camera;
 pub mod canvas;
 pub mod color;
+#[cfg(test)]
+mod fuzz;
 pub mod intersection;
 pub mod light;
 pub mod material;
 pub mod matrix;
+pub mod prelude;
 pub mod ray;
+pub mod sampling;
 pub mod shape;
+pub mod stats;
 pub mod texture;
+pub mod transform;
 pub mod tuple;
 pub mod world;
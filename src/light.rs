@@ -3,16 +3,89 @@
 use crate::color::Color;
 use crate::tuple::Tuple;
 
-/// Light at a point in 3D space with a color.
+/// Light at a point in 3D space with a color and an intensity.
+///
+/// The color is the hue of the light, and the intensity how bright it is. Surfaces are lit by
+/// their product, the [radiance](PointLight::radiance). The default intensity is 1, so the color
+/// alone decides how the light looks. Intensities above 1 give bright lights, without having to
+/// use colors with components above 1.
+///
+/// By default, the light is equally strong at any distance. [PointLight::with_attenuation] makes
+/// it fall off with distance.
 pub struct PointLight {
     position: Tuple,
     color: Color,
+    intensity: f64,
+    constant: f64,
+    linear: f64,
+    quadratic: f64,
 }
 
 impl PointLight {
     /// Creates and returns a new point light at the given position with the given color.
     pub fn new(position: Tuple, color: Color) -> Self {
-        Self { position, color }
+        Self {
+            position,
+            color,
+            intensity: 1.0,
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 0.0,
+        }
+    }
+
+    /// Returns `self` with the given intensity. The default intensity is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::light::PointLight;
+    /// use truster::color::Color;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let warm = Color::new(1.0, 0.7, 0.4);
+    /// let light = PointLight::new(Tuple::point(0.0, 0.0, 0.0), warm).with_intensity(5.0);
+    /// assert_eq!(light.color(), warm);
+    /// assert_eq!(light.intensity(), 5.0);
+    /// assert_eq!(light.radiance(), Color::new(5.0, 3.5, 2.0));
+    /// ```
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Returns `self` with the given attenuation terms. At distance `d`, the light is multiplied
+    /// by `1 / (constant + linear * d + quadratic * d²)`. The default terms are (1, 0, 0), which
+    /// means no attenuation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::light::PointLight;
+    /// use truster::color::Color;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let light = PointLight::new(Tuple::point(0.0, 0.0, 0.0), Color::WHITE)
+    ///     .with_attenuation(0.0, 0.0, 1.0);
+    /// assert_eq!(light.attenuation_at(1.0), 1.0);
+    /// assert_eq!(light.attenuation_at(2.0), 0.25);
+    /// assert_eq!(light.attenuation_at(4.0), 0.0625);
+    /// ```
+    pub fn with_attenuation(mut self, constant: f64, linear: f64, quadratic: f64) -> Self {
+        self.constant = constant;
+        self.linear = linear;
+        self.quadratic = quadratic;
+        self
+    }
+
+    /// Returns `self`'s attenuation terms as (constant, linear, quadratic).
+    pub fn attenuation(&self) -> (f64, f64, f64) {
+        (self.constant, self.linear, self.quadratic)
+    }
+
+    /// Returns the factor `self`'s light is multiplied with at `distance` from it.
+    pub fn attenuation_at(&self, distance: f64) -> f64 {
+        1.0 / (self.constant + self.linear * distance + self.quadratic * distance * distance)
     }
 
     /// Returns `self`'s position.
@@ -24,4 +97,14 @@ impl PointLight {
     pub fn color(&self) -> Color {
         self.color
     }
+
+    /// Returns `self`'s intensity.
+    pub fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /// Returns the light `self` gives off, its color multiplied by its intensity.
+    pub fn radiance(&self) -> Color {
+        self.color * self.intensity
+    }
 }
@@ -1,14 +1,31 @@
 //! Holds the [Material] struct.
 
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 use std::rc::Rc;
 
 use crate::color::Color;
+use crate::intersection::HitRecord;
 use crate::light::PointLight;
 use crate::shape::Shape;
 use crate::texture::{solid_color::SolidColor, Texture};
 use crate::tuple::Tuple;
 
+pub mod presets;
+
 /// Material with lighting properties. Give it to a shape to change its appearance.
+///
+/// Materials can be built with struct update syntax, or more conveniently with
+/// [Material::builder].
+///
+/// `normal_texture` fakes bumps on a surface without extra geometry. Its color at a point is read
+/// as an (x, y, z) offset in world space, which is added to the normal of the shape.
+///
+/// Both faces of a surface shade the same when a [World](crate::world::World) renders it: the
+/// normal of a hit is flipped to face the eye before lighting, see [HitRecord::normal].
+///
+/// `emission` is light given off by the surface itself, like for a lamp. It is added to the
+/// shaded color regardless of lights and shadows, but doesn't light other shapes.
 #[derive(Clone)]
 pub struct Material {
     pub texture: Rc<dyn Texture>,
@@ -16,9 +33,132 @@ pub struct Material {
     pub diffuse: f64,
     pub specular: f64,
     pub shininess: f64,
+    pub reflective: f64,
+    pub transparency: f64,
+    pub refractive_index: f64,
+    pub casts_shadow: bool,
+    pub normal_texture: Option<Rc<dyn Texture>>,
+    pub emission: Color,
 }
 
 impl Material {
+    /// Returns a [MaterialBuilder] which starts out with the properties of [Material::default].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::material::Material;
+    /// use truster::color::Color;
+    ///
+    /// let material = Material::builder()
+    ///     .color(Color::new(1.0, 0.2, 1.0))
+    ///     .diffuse(0.7)
+    ///     .specular(0.3)
+    ///     .build();
+    /// assert_eq!(material.diffuse, 0.7);
+    /// assert_eq!(material.specular, 0.3);
+    /// assert_eq!(material.ambient, 0.1);
+    /// ```
+    pub fn builder() -> MaterialBuilder {
+        MaterialBuilder::default()
+    }
+
+    /// Returns the default material, but with a [SolidColor] texture of `color`.
+    pub fn with_color(color: Color) -> Self {
+        Self {
+            texture: Rc::new(SolidColor::new(color)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a mirror: fully reflective, with a diffuse coefficient of 0.1 and no ambient light,
+    /// so the surface itself barely shows.
+    pub fn mirror() -> Self {
+        Self {
+            ambient: 0.0,
+            diffuse: 0.1,
+            reflective: 1.0,
+            ..Self::default()
+        }
+    }
+
+    /// Returns glass: fully transparent with a refractive index of 1.5, with a diffuse
+    /// coefficient of 0.1 and no ambient light. It is also 0.9 reflective, so reflection and
+    /// refraction are mixed according to the angle the glass is seen at.
+    pub fn glass() -> Self {
+        Self {
+            ambient: 0.0,
+            diffuse: 0.1,
+            reflective: 0.9,
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the preset material called `name`, like `"glass"`, `"matte"`, `"metal"` or
+    /// `"mirror"`, or `None` if there is no such preset. See the [presets] module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::material::Material;
+    /// let mirror = Material::preset("mirror").unwrap();
+    /// assert_eq!(mirror.reflective, 1.0);
+    /// assert!(Material::preset("plaid").is_none());
+    /// ```
+    pub fn preset(name: &str) -> Option<Self> {
+        presets::get(name)
+    }
+
+    /// Checks that the ambient, diffuse, specular, reflective and transparency coefficients are
+    /// between 0 and 1, and that the shininess is not negative. Returns the first problem found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::material::{Material, MaterialError};
+    /// assert_eq!(Material::default().validate(), Ok(()));
+    ///
+    /// let material = Material {
+    ///     diffuse: 1.5,
+    ///     ..Material::default()
+    /// };
+    /// assert_eq!(
+    ///     material.validate(),
+    ///     Err(MaterialError::OutOfRange { property: "diffuse", value: 1.5 }),
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), MaterialError> {
+        for &(property, value) in &[
+            ("ambient", self.ambient),
+            ("diffuse", self.diffuse),
+            ("specular", self.specular),
+            ("reflective", self.reflective),
+            ("transparency", self.transparency),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(MaterialError::OutOfRange { property, value });
+            }
+        }
+        if self.shininess < 0.0 {
+            return Err(MaterialError::NegativeShininess(self.shininess));
+        }
+        Ok(())
+    }
+
+    /// Scales the diffuse, specular and reflective coefficients down by the same factor, so they
+    /// sum to at most 1. This way a surface never reflects more light than it receives. The
+    /// ratios between the coefficients are kept. Nothing changes if the sum is already at most 1.
+    pub fn normalize_energy(&mut self) {
+        let sum = self.diffuse + self.specular + self.reflective;
+        if sum > 1.0 {
+            self.diffuse /= sum;
+            self.specular /= sum;
+            self.reflective /= sum;
+        }
+    }
+
     /// Shades the object. Returns the color they would emit at `position`. `light` is the light
     /// that is lighting the scene. `eye` is the direction of the 'eye' that is looking at the
     /// scene. `normal` is the normal vector of the shape that the material is on at `position`.
@@ -32,12 +172,65 @@ impl Material {
         normal: Tuple,
         in_shadow: bool,
     ) -> Color {
-        let color = self.texture.color_at_shape(position, Rc::clone(&shape)) * light.color();
-        let lightv = (light.position() - position).normalized();
+        let visibility = if in_shadow { 0.0 } else { 1.0 };
+        self.lighting_with_visibility(shape, light, position, eye, normal, visibility)
+    }
+
+    /// Like [Material::lighting], but with the fraction of `light` that reaches `position`
+    /// instead of a shadow flag. `visibility` should be between 0 (fully in shadow) and 1 (not in
+    /// shadow). The diffuse and specular parts are scaled by it, and by the attenuation of
+    /// `light` over the distance to `position`. The ambient part is not.
+    ///
+    /// The emission of `self` is always added, even in shadow.
+    pub fn lighting_with_visibility(
+        &self,
+        shape: Rc<dyn Shape>,
+        light: &PointLight,
+        position: Tuple,
+        eye: Tuple,
+        normal: Tuple,
+        visibility: f64,
+    ) -> Color {
+        let color = self.texture.color_at_shape(position, shape);
+        self.shade(color, light, position, eye, normal, visibility) + self.emission
+    }
+
+    /// Like [Material::lighting_with_visibility], at the hit `rec`. The color of the texture is
+    /// looked up with [Texture::color_at_hit], so hits with (u, v) coordinates, like those on
+    /// triangles, use them to map the texture.
+    ///
+    /// Unlike [Material::lighting_with_visibility], the emission of `self` is not added. A
+    /// [World](crate::world::World) shades a hit once per light, and adds the emission only once.
+    pub fn lighting_at_hit(&self, light: &PointLight, rec: &HitRecord, visibility: f64) -> Color {
+        let color = self.texture.color_at_hit(rec);
+        self.shade(
+            color,
+            light,
+            rec.point(),
+            rec.eye(),
+            rec.normal(),
+            visibility,
+        )
+    }
+
+    /// Shades a point whose texture has `color`. See [Material::lighting_with_visibility].
+    fn shade(
+        &self,
+        color: Color,
+        light: &PointLight,
+        position: Tuple,
+        eye: Tuple,
+        normal: Tuple,
+        visibility: f64,
+    ) -> Color {
+        let color = color * light.radiance();
+        let lightv = light.position() - position;
+        let visibility = visibility * light.attenuation_at(lightv.norm());
+        let lightv = lightv.normalized();
         let ambient = color * self.ambient;
         let light_dot_normal = lightv.dot(normal);
 
-        if in_shadow || light_dot_normal < 0.0 {
+        if visibility <= 0.0 || light_dot_normal < 0.0 {
             return ambient;
         }
 
@@ -46,13 +239,13 @@ impl Material {
         let reflect_dot_eye = reflectv.dot(eye);
 
         if reflect_dot_eye <= 0.0 {
-            return ambient + diffuse;
+            return ambient + diffuse * visibility;
         }
 
         let factor = reflect_dot_eye.powf(self.shininess);
-        let specular = light.color() * self.specular * factor;
+        let specular = light.radiance() * self.specular * factor;
 
-        return ambient + diffuse + specular;
+        ambient + diffuse * visibility + specular * visibility
     }
 }
 
@@ -64,14 +257,160 @@ impl Default for Material {
             diffuse: 0.9,
             specular: 0.9,
             shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            casts_shadow: true,
+            normal_texture: None,
+            emission: Color::BLACK,
         }
     }
 }
 
+/// Builds a [Material] one property at a time. Get one with [Material::builder]. Properties which
+/// are not set keep their value from [Material::default].
+#[derive(Clone, Default)]
+pub struct MaterialBuilder {
+    material: Material,
+}
+
+impl MaterialBuilder {
+    /// Sets the texture to a single solid color.
+    pub fn color(self, color: Color) -> Self {
+        self.texture(Rc::new(SolidColor::new(color)))
+    }
+
+    /// Sets the texture.
+    pub fn texture(mut self, texture: Rc<dyn Texture>) -> Self {
+        self.material.texture = texture;
+        self
+    }
+
+    /// Sets the ambient coefficient.
+    pub fn ambient(mut self, ambient: f64) -> Self {
+        self.material.ambient = ambient;
+        self
+    }
+
+    /// Sets the diffuse coefficient.
+    pub fn diffuse(mut self, diffuse: f64) -> Self {
+        self.material.diffuse = diffuse;
+        self
+    }
+
+    /// Sets the specular coefficient.
+    pub fn specular(mut self, specular: f64) -> Self {
+        self.material.specular = specular;
+        self
+    }
+
+    /// Sets the shininess.
+    pub fn shininess(mut self, shininess: f64) -> Self {
+        self.material.shininess = shininess;
+        self
+    }
+
+    /// Sets the reflectivity. 0 means not reflective at all, 1 means a perfect mirror.
+    pub fn reflective(mut self, reflective: f64) -> Self {
+        self.material.reflective = reflective;
+        self
+    }
+
+    /// Sets the transparency. 0 means opaque, 1 means fully transparent.
+    pub fn transparency(mut self, transparency: f64) -> Self {
+        self.material.transparency = transparency;
+        self
+    }
+
+    /// Sets the refractive index, which determines how much light bends when it enters or leaves
+    /// a shape with the material. Vacuum is 1, water is 1.333, glass is 1.5 and diamond is 2.42.
+    pub fn refractive_index(mut self, refractive_index: f64) -> Self {
+        self.material.refractive_index = refractive_index;
+        self
+    }
+
+    /// Sets whether shapes with the material cast shadows.
+    pub fn casts_shadow(mut self, casts_shadow: bool) -> Self {
+        self.material.casts_shadow = casts_shadow;
+        self
+    }
+
+    /// Sets the color of the light given off by shapes with the material.
+    pub fn emission(mut self, emission: Color) -> Self {
+        self.material.emission = emission;
+        self
+    }
+
+    /// Sets the texture which perturbs the normals of shapes with the material.
+    pub fn normal_texture(mut self, normal_texture: Rc<dyn Texture>) -> Self {
+        self.material.normal_texture = Some(normal_texture);
+        self
+    }
+
+    /// Returns the finished [Material].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the material is not valid according to [Material::validate].
+    pub fn build(self) -> Material {
+        if let Err(err) = self.material.validate() {
+            panic!("{}", err);
+        }
+        self.material
+    }
+}
+
+/// The error returned by [Material::validate].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaterialError {
+    /// A coefficient which should be between 0 and 1 is not.
+    OutOfRange { property: &'static str, value: f64 },
+    /// The shininess is negative.
+    NegativeShininess(f64),
+}
+
+impl Display for MaterialError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::OutOfRange { property, value } => {
+                write!(f, "{} should be between 0 and 1, got {}", property, value)
+            }
+            Self::NegativeShininess(value) => {
+                write!(f, "shininess should not be negative, got {}", value)
+            }
+        }
+    }
+}
+
+impl Error for MaterialError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::matrix::Matrix;
     use crate::shape::sphere::Sphere;
+    use crate::texture::stripe::Stripe;
+
+    #[test]
+    fn mirror() {
+        let mirror = Material::mirror();
+        assert_eq!(mirror.reflective, 1.0);
+        assert_eq!(mirror.diffuse, 0.1);
+        assert_eq!(mirror.ambient, 0.0);
+        assert_eq!(mirror.transparency, 0.0);
+        assert_eq!(mirror.validate(), Ok(()));
+    }
+
+    #[test]
+    fn glass() {
+        let glass = Material::glass();
+        assert_eq!(glass.transparency, 1.0);
+        assert_eq!(glass.refractive_index, 1.5);
+        assert_eq!(glass.reflective, 0.9);
+        assert_eq!(glass.diffuse, 0.1);
+        assert_eq!(glass.ambient, 0.0);
+        assert_eq!(glass.validate(), Ok(()));
+    }
 
     #[test]
     fn lighting_eye_between_light_and_surface() {
@@ -95,7 +434,7 @@ mod tests {
         let material = Material::default();
         let position = Tuple::point(0.0, 0.0, 0.0);
 
-        let eye = Tuple::vector(0.0, (2.0 as f64).sqrt() / 2.0, -(2.0 as f64).sqrt() / 2.0);
+        let eye = Tuple::vector(0.0, 2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
@@ -128,7 +467,7 @@ mod tests {
         let material = Material::default();
         let position = Tuple::point(0.0, 0.0, 0.0);
 
-        let eye = Tuple::vector(0.0, -(2.0 as f64).sqrt() / 2.0, -(2.0 as f64).sqrt() / 2.0);
+        let eye = Tuple::vector(0.0, -2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = PointLight::new(Tuple::point(0.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
@@ -168,4 +507,253 @@ mod tests {
         let result = material.lighting(shape, &light, position, eye, normal, true);
         assert_eq!(result, Color::new(0.1, 0.1, 0.1));
     }
+
+    #[test]
+    fn lighting_with_partial_visibility() {
+        let shape: Rc<dyn Shape> = Rc::new(Sphere::new());
+
+        let material = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+
+        let result = material.lighting_with_visibility(shape, &light, position, eye, normal, 0.5);
+        assert_eq!(result, Color::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn lighting_quadratic_attenuation() {
+        let shape: Rc<dyn Shape> = Rc::new(Sphere::new());
+
+        let material = Material {
+            ambient: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let near = PointLight::new(Tuple::point(0.0, 0.0, -1.0), Color::new(1.0, 1.0, 1.0))
+            .with_attenuation(0.0, 0.0, 1.0);
+        let far = PointLight::new(Tuple::point(0.0, 0.0, -2.0), Color::new(1.0, 1.0, 1.0))
+            .with_attenuation(0.0, 0.0, 1.0);
+
+        let near = material.lighting(Rc::clone(&shape), &near, position, eye, normal, false);
+        let far = material.lighting(shape, &far, position, eye, normal, false);
+        assert_eq!(near, Color::new(0.9, 0.9, 0.9));
+        assert_eq!(far, near / 4.0);
+    }
+
+    #[test]
+    fn lighting_intensity_doubles_diffuse() {
+        let shape: Rc<dyn Shape> = Rc::new(Sphere::new());
+
+        let material = Material {
+            ambient: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let color = Color::new(1.0, 0.5, 0.25);
+        let normal_light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), color);
+        let bright_light =
+            PointLight::new(Tuple::point(0.0, 0.0, -10.0), color).with_intensity(2.0);
+
+        let normal_result = material.lighting(
+            Rc::clone(&shape),
+            &normal_light,
+            position,
+            eye,
+            normal,
+            false,
+        );
+        let bright_result = material.lighting(shape, &bright_light, position, eye, normal, false);
+        assert_eq!(normal_result, Color::new(0.9, 0.45, 0.225));
+        assert_eq!(bright_result, normal_result * 2.0);
+    }
+
+    #[test]
+    fn with_color() {
+        let color = Color::new(0.3, 0.6, 0.9);
+        let material = Material::with_color(color);
+        assert_eq!(
+            material.texture.color_at(Tuple::point(4.0, -2.0, 1.0)),
+            color
+        );
+        assert_eq!(material.ambient, Material::default().ambient);
+    }
+
+    #[test]
+    fn lighting_with_stripe_texture() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let mut sphere = Sphere::new();
+        sphere.set_transform(Matrix::scaling(2.0, 2.0, 2.0));
+        let shape: Rc<dyn Shape> = Rc::new(sphere);
+
+        let material = Material {
+            texture: Rc::new(Stripe::colors(white, black)),
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+
+        // In object space these points are at x = 0.5 and x = -0.5, which are in different stripes.
+        let c1 = material.lighting(
+            Rc::clone(&shape),
+            &light,
+            Tuple::point(1.0, 0.0, -1.0),
+            eye,
+            normal,
+            false,
+        );
+        let c2 = material.lighting(
+            Rc::clone(&shape),
+            &light,
+            Tuple::point(-1.0, 0.0, -1.0),
+            eye,
+            normal,
+            false,
+        );
+        assert_eq!(c1, white);
+        assert_eq!(c2, black);
+    }
+
+    #[test]
+    fn builder_default() {
+        let built = Material::builder().build();
+        let default = Material::default();
+        let point = Tuple::point(0.0, 0.0, 0.0);
+        assert_eq!(
+            built.texture.color_at(point),
+            default.texture.color_at(point)
+        );
+        assert_eq!(built.ambient, default.ambient);
+        assert_eq!(built.diffuse, default.diffuse);
+        assert_eq!(built.specular, default.specular);
+        assert_eq!(built.shininess, default.shininess);
+        assert_eq!(built.reflective, default.reflective);
+        assert_eq!(built.transparency, 0.0);
+        assert_eq!(built.refractive_index, 1.0);
+        assert_eq!(built.casts_shadow, default.casts_shadow);
+        assert!(built.normal_texture.is_none());
+        assert_eq!(built.emission, Color::BLACK);
+    }
+
+    #[test]
+    fn builder_sets_properties() {
+        let material = Material::builder()
+            .color(Color::new(0.5, 0.25, 1.0))
+            .ambient(0.2)
+            .diffuse(0.6)
+            .specular(0.4)
+            .shininess(50.0)
+            .reflective(0.5)
+            .transparency(0.9)
+            .refractive_index(1.5)
+            .casts_shadow(false)
+            .emission(Color::new(0.5, 0.5, 0.0))
+            .build();
+        assert_eq!(
+            material.texture.color_at(Tuple::point(1.0, 2.0, 3.0)),
+            Color::new(0.5, 0.25, 1.0)
+        );
+        assert_eq!(material.ambient, 0.2);
+        assert_eq!(material.diffuse, 0.6);
+        assert_eq!(material.specular, 0.4);
+        assert_eq!(material.shininess, 50.0);
+        assert_eq!(material.reflective, 0.5);
+        assert_eq!(material.transparency, 0.9);
+        assert_eq!(material.refractive_index, 1.5);
+        assert!(!material.casts_shadow);
+        assert_eq!(material.emission, Color::new(0.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn validate_out_of_range() {
+        let material = Material {
+            reflective: -0.1,
+            ..Material::default()
+        };
+        assert_eq!(
+            material.validate(),
+            Err(MaterialError::OutOfRange {
+                property: "reflective",
+                value: -0.1
+            })
+        );
+
+        let material = Material {
+            shininess: -1.0,
+            ..Material::default()
+        };
+        assert_eq!(
+            material.validate(),
+            Err(MaterialError::NegativeShininess(-1.0))
+        );
+    }
+
+    #[test]
+    fn normalize_energy() {
+        let mut material = Material {
+            diffuse: 0.8,
+            specular: 0.4,
+            reflective: 0.8,
+            ..Material::default()
+        };
+        material.normalize_energy();
+        assert_eq!(material.diffuse, 0.4);
+        assert_eq!(material.specular, 0.2);
+        assert_eq!(material.reflective, 0.4);
+        assert_eq!(material.ambient, Material::default().ambient);
+    }
+
+    #[test]
+    fn normalize_energy_below_one() {
+        let mut material = Material {
+            diffuse: 0.5,
+            specular: 0.2,
+            reflective: 0.1,
+            ..Material::default()
+        };
+        material.normalize_energy();
+        assert_eq!(material.diffuse, 0.5);
+        assert_eq!(material.specular, 0.2);
+        assert_eq!(material.reflective, 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "reflective should be between 0 and 1, got 1.5")]
+    fn builder_reflective_out_of_range() {
+        Material::builder().reflective(1.5).build();
+    }
+
+    #[test]
+    fn lighting_emission_in_shadow() {
+        let shape: Rc<dyn Shape> = Rc::new(Sphere::new());
+
+        let emission = Color::new(0.8, 0.6, 0.2);
+        let material = Material::builder().emission(emission).build();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+
+        let result = material.lighting(shape, &light, position, eye, normal, true);
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1) + emission);
+        for i in 0..3 {
+            assert!(result[i] >= emission[i]);
+        }
+    }
 }
@@ -0,0 +1,78 @@
+//! A small table of named materials for quick prototyping. Look them up with
+//! [Material::preset](super::Material::preset) or [get].
+//!
+//! # Examples
+//!
+//! ```
+//! # use truster::material::presets;
+//! for &name in presets::NAMES {
+//!     assert!(presets::get(name).is_some());
+//! }
+//! assert!(presets::get("unobtainium").is_none());
+//! ```
+
+use super::Material;
+
+/// The names of all presets, in alphabetical order.
+pub const NAMES: &[&str] = &["glass", "matte", "metal", "mirror"];
+
+/// Returns the material called `name`, or `None` if there is no such preset. See [NAMES] for the
+/// available presets. All presets have the default white texture.
+///
+/// - `"glass"` is [Material::glass].
+/// - `"matte"` is a dull surface, without specular highlights.
+/// - `"metal"` is a shiny, partly reflective surface, with a sharp highlight.
+/// - `"mirror"` is [Material::mirror].
+pub fn get(name: &str) -> Option<Material> {
+    let material = match name {
+        "glass" => Material::glass(),
+        "matte" => Material {
+            diffuse: 0.9,
+            specular: 0.0,
+            ..Material::default()
+        },
+        "metal" => Material {
+            ambient: 0.05,
+            diffuse: 0.3,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 0.6,
+            ..Material::default()
+        },
+        "mirror" => Material::mirror(),
+        _ => return None,
+    };
+    Some(material)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mirror_is_reflective() {
+        let mirror = get("mirror").unwrap();
+        assert!(mirror.reflective >= 0.9);
+    }
+
+    #[test]
+    fn matte_has_no_specular() {
+        let matte = get("matte").unwrap();
+        assert_eq!(matte.specular, 0.0);
+        assert_eq!(matte.reflective, 0.0);
+    }
+
+    #[test]
+    fn unknown_name() {
+        assert!(get("unobtainium").is_none());
+        assert!(get("Mirror").is_none());
+        assert!(get("").is_none());
+    }
+
+    #[test]
+    fn all_valid() {
+        for &name in NAMES {
+            assert!(get(name).unwrap().validate().is_ok(), "{}", name);
+        }
+    }
+}
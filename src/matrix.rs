@@ -81,6 +81,21 @@
 //! assert_eq!(&m * p, Tuple::point(18.0, 24.0, 33.0));
 //! ```
 //!
+//! Both the matrix and the tuple can be owned or borrowed:
+//! ```
+//! # use truster::matrix::Matrix;
+//! use truster::tuple::Tuple;
+//!
+//! let m = Matrix::translation(1.0, 2.0, 3.0);
+//! let p = Tuple::point(1.0, 1.0, 1.0);
+//! let expected = Tuple::point(2.0, 3.0, 4.0);
+//! assert_eq!(&m * p, expected);
+//! assert_eq!(&m * &p, expected);
+//! assert_eq!(m.clone() * &p, expected);
+//! assert_eq!(m * p, expected);
+//! assert_eq!(Matrix::scaling(2.0, 2.0, 2.0) * p, Tuple::point(2.0, 2.0, 2.0));
+//! ```
+//!
 //! Take the transpose of a matrix with [Matrix::transpose]:
 //! ```
 //! # use truster::matrix::Matrix;
@@ -92,10 +107,10 @@
 //! ]);
 //!
 //! assert_eq!(m.transpose(), Matrix::new(&[
-//! 	1.0, 12.0, 21.0, -1.0,
-//! 	2.0, 42.0, 24.0, -2.0,
-//! 	3.0, 69.0, 96.0, -3.0,
-//! 	4.0, 37.0, 73.0, -4.0,
+//!     1.0, 12.0, 21.0, -1.0,
+//!     2.0, 42.0, 24.0, -2.0,
+//!     3.0, 69.0, 96.0, -3.0,
+//!     4.0, 37.0, 73.0, -4.0,
 //! ]));
 //! ```
 //!
@@ -221,9 +236,10 @@
 //! ```
 
 use std::fmt::{Display, Formatter, Result};
-use std::ops::{Index, IndexMut, Mul};
+use std::ops::{Index, IndexMut, Mul, MulAssign};
 
 use crate::tuple::Tuple;
+use crate::EPSILON;
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Matrix {
@@ -233,16 +249,16 @@ pub struct Matrix {
 impl Matrix {
     /// Returns a new matrix with the given values. Row major.
     pub fn new(data: &[f64; 16]) -> Self {
-        return Self {
+        Self {
             data: data.to_vec(),
-        };
+        }
     }
 
     /// Returns the identity matrix.
     pub fn eye() -> Self {
-        return Self::new(&[
+        Self::new(&[
             1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
-        ]);
+        ])
     }
 
     /// Returns a translation matrix which translates points, but not vectors.
@@ -259,6 +275,50 @@ impl Matrix {
         ])
     }
 
+    /// Returns a translation matrix which translates points by the vector `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::matrix::Matrix;
+    /// # use truster::tuple::Tuple;
+    /// assert_eq!(
+    ///     Matrix::translation_vec(Tuple::vector(1.0, 2.0, 3.0)),
+    ///     Matrix::translation(1.0, 2.0, 3.0),
+    /// );
+    /// ```
+    pub fn translation_vec(v: Tuple) -> Self {
+        Self::translation(v.x(), v.y(), v.z())
+    }
+
+    /// Returns a scaling matrix which scales each axis by the corresponding component of `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::matrix::Matrix;
+    /// # use truster::tuple::Tuple;
+    /// assert_eq!(
+    ///     Matrix::scaling_vec(Tuple::vector(1.0, 2.0, 3.0)),
+    ///     Matrix::scaling(1.0, 2.0, 3.0),
+    /// );
+    /// ```
+    pub fn scaling_vec(v: Tuple) -> Self {
+        Self::scaling(v.x(), v.y(), v.z())
+    }
+
+    /// Returns a scaling matrix which scales all axes by `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::matrix::Matrix;
+    /// assert_eq!(Matrix::scale_uniform(2.0), Matrix::scaling(2.0, 2.0, 2.0));
+    /// ```
+    pub fn scale_uniform(s: f64) -> Self {
+        Self::scaling(s, s, s)
+    }
+
     /// Returns a matrix which rotates `theta` radians around the X axis.
     pub fn rotation_x(theta: f64) -> Self {
         Self::new(&[
@@ -337,6 +397,10 @@ impl Matrix {
     /// `from` at `at, where `up` is approximately pointing up, the resulting matrix will be it's
     /// transformation matrix.
     ///
+    /// If `up` is (nearly) parallel to the viewing direction, or zero, it can't tell which way is
+    /// up. Then the coordinate axis which is closest to perpendicular to the viewing direction is
+    /// used instead, so the matrix is still invertible. `from` and `at` must differ.
+    ///
     /// # Examples
     ///
     /// The transformation matrix for the default orientation.
@@ -371,10 +435,50 @@ impl Matrix {
     /// let t = Matrix::view_transform(from, to, up);
     /// assert_eq!(t, Matrix::translation(0.0, 0.0, -8.0));
     /// ```
+    ///
+    /// Looking straight up with the default up vector still gives a valid view.
+    /// ```
+    /// # use truster::matrix::Matrix;
+    /// use truster::tuple::Tuple;
+    /// let from = Tuple::point(0.0, 0.0, 0.0);
+    /// let at = Tuple::point(0.0, 5.0, 0.0);
+    /// let up = Tuple::vector(0.0, 1.0, 0.0);
+    /// let t = Matrix::view_transform(from, at, up);
+    /// assert_eq!(&t * Tuple::point(0.0, 1.0, 0.0), Tuple::point(0.0, 0.0, -1.0));
+    /// let inverse = t.inverse();
+    /// assert!((0..4).all(|i| (0..4).all(|j| inverse[[i, j]].is_finite())));
+    /// ```
+    ///
+    /// An up vector which is almost parallel to the view direction doesn't squash the view.
+    /// ```
+    /// # use truster::matrix::Matrix;
+    /// use truster::tuple::Tuple;
+    /// let from = Tuple::point(0.0, 0.0, 0.0);
+    /// let at = Tuple::point(0.0, 5.0, 0.0);
+    /// let up = Tuple::vector(0.001, 1.0, 0.0);
+    /// let t = Matrix::view_transform(from, at, up);
+    /// for v in &[Tuple::vector(1.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0)] {
+    ///     assert!(((&t * *v).norm() - 1.0).abs() < 1e-9);
+    /// }
+    /// ```
     pub fn view_transform(from: Tuple, at: Tuple, up: Tuple) -> Self {
         let forward = (at - from).normalized();
-        let up = up.normalized();
-        let left = forward.cross(up);
+        let mut left = forward.cross(up.normalized());
+        // A zero `up` gives a NaN `left`.
+        if left.norm().is_nan() || left.norm() < EPSILON {
+            let (x, y, z) = (forward.x().abs(), forward.y().abs(), forward.z().abs());
+            let axis = if x <= y && x <= z {
+                Tuple::vector(1.0, 0.0, 0.0)
+            } else if y <= z {
+                Tuple::vector(0.0, 1.0, 0.0)
+            } else {
+                Tuple::vector(0.0, 0.0, 1.0)
+            };
+            left = forward.cross(axis);
+        }
+        // Unless `up` is perpendicular to `forward`, `left` is shorter than 1, which would squash
+        // the image horizontally.
+        let left = left.normalized();
         let up = left.cross(forward);
         let orientation = Self::new(&[
             left.x(),
@@ -534,7 +638,7 @@ impl Matrix {
             result.data[i] *= det;
         }
 
-        return result;
+        result
     }
 }
 
@@ -633,6 +737,38 @@ impl Mul<&Self> for Matrix {
     }
 }
 
+/// Multiplies `self` by `rhs` in place, without allocating a new matrix.
+///
+/// # Examples
+///
+/// ```
+/// # use truster::matrix::Matrix;
+/// use std::f64::consts::PI;
+///
+/// let rotation = Matrix::rotation_x(PI / 2.0);
+/// let scaling = Matrix::scaling(5.0, 5.0, 5.0);
+/// let translation = Matrix::translation(10.0, 5.0, 7.0);
+///
+/// let mut t = translation.clone();
+/// t *= &scaling;
+/// t *= &rotation;
+/// assert_eq!(t, translation * &scaling * &rotation);
+/// ```
+impl MulAssign<&Self> for Matrix {
+    fn mul_assign(&mut self, rhs: &Self) {
+        let mut res = [0.0; 16];
+        for row in 0..=3 {
+            for col in 0..=3 {
+                res[row * 4 + col] = self[[row, 0]] * rhs[[0, col]]
+                    + self[[row, 1]] * rhs[[1, col]]
+                    + self[[row, 2]] * rhs[[2, col]]
+                    + self[[row, 3]] * rhs[[3, col]];
+            }
+        }
+        self.data.copy_from_slice(&res);
+    }
+}
+
 impl Mul<Self> for &Matrix {
     type Output = Matrix;
 
@@ -673,3 +809,27 @@ impl Mul<Tuple> for &Matrix {
         Tuple::new(x, y, z, w)
     }
 }
+
+impl Mul<&Tuple> for &Matrix {
+    type Output = Tuple;
+
+    fn mul(self, rhs: &Tuple) -> Tuple {
+        self * *rhs
+    }
+}
+
+impl Mul<Tuple> for Matrix {
+    type Output = Tuple;
+
+    fn mul(self, rhs: Tuple) -> Tuple {
+        &self * rhs
+    }
+}
+
+impl Mul<&Tuple> for Matrix {
+    type Output = Tuple;
+
+    fn mul(self, rhs: &Tuple) -> Tuple {
+        &self * *rhs
+    }
+}
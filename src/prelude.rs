@@ -0,0 +1,46 @@
+//! Re-exports the types needed by most scenes, so they can be imported at once.
+//!
+//! # Examples
+//!
+//! ```
+//! use truster::prelude::*;
+//! use std::rc::Rc;
+//!
+//! let mut world = World::new();
+//! let mut sphere = Sphere::new();
+//! sphere.set_transform(Transform::new().scale(0.5, 0.5, 0.5).build());
+//! sphere.set_material(Material::with_color(Color::RED));
+//! world.add_shape(Rc::new(sphere));
+//! world.add_light(Rc::new(PointLight::new(
+//!     Tuple::point(-10.0, 10.0, -10.0),
+//!     Color::WHITE,
+//! )));
+//!
+//! let camera = Camera::new(Config {
+//!     hsize: 11,
+//!     vsize: 11,
+//!     from: Tuple::point(0.0, 0.0, -5.0),
+//!     at: Tuple::ORIGIN,
+//!     ..Config::default()
+//! });
+//! let image: Canvas = camera.render(&world);
+//! assert_ne!(image[[5, 5]], Color::BLACK);
+//!
+//! let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+//! assert!(world.hit(&ray).is_some());
+//! assert_eq!(Matrix::eye() * Tuple::ORIGIN, Tuple::ORIGIN);
+//! ```
+
+pub use crate::camera::{Camera, Config};
+pub use crate::canvas::Canvas;
+pub use crate::color::Color;
+pub use crate::light::PointLight;
+pub use crate::material::Material;
+pub use crate::matrix::Matrix;
+pub use crate::ray::Ray;
+pub use crate::shape::{
+    Disk, Group, MovingSphere, Plane, Quad, Shape, SmoothTriangle, Sphere, Torus, Triangle,
+};
+pub use crate::transform::Transform;
+pub use crate::tuple::{Point, Tuple, Vector};
+pub use crate::world::World;
@@ -4,16 +4,43 @@ use crate::matrix::Matrix;
 use crate::tuple::Tuple;
 
 /// A ray which can be used for calculating intersections with shapes to render 3D scenes.
-#[derive(Debug, PartialEq, Clone)]
+/// Rays are cheap to copy, they only hold two tuples and a time.
+///
+/// The time is a point in the shutter interval, where 0 is the moment the shutter opens and 1 the
+/// moment it closes. Moving shapes, like [MovingSphere](crate::shape::moving_sphere::MovingSphere),
+/// use it for motion blur. Other shapes ignore it.
+///
+/// # Examples
+///
+/// ```
+/// # use truster::ray::Ray;
+/// use truster::tuple::Tuple;
+///
+/// let ray = Ray::new(Tuple::point(1.0, 2.0, 3.0), Tuple::vector(0.0, 1.0, 0.0));
+/// let copy = ray;
+/// assert_eq!(copy, ray);
+/// assert_eq!(ray.clone(), ray);
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Ray {
     origin: Tuple,
     direction: Tuple,
+    time: f64,
 }
 
 impl Ray {
-    /// Returns a new [Ray] with the given origin and direction.
+    /// Returns a new [Ray] with the given origin and direction, at time 0.
     pub fn new(origin: Tuple, direction: Tuple) -> Self {
-        Self { origin, direction }
+        Self::with_time(origin, direction, 0.0)
+    }
+
+    /// Returns a new [Ray] with the given origin and direction, at the given time.
+    pub fn with_time(origin: Tuple, direction: Tuple, time: f64) -> Self {
+        Self {
+            origin,
+            direction,
+            time,
+        }
     }
 
     /// Returns `self`'s origin.
@@ -26,6 +53,11 @@ impl Ray {
         self.direction
     }
 
+    /// Returns `self`'s time.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
     /// Returns the point at the given distance t along `self`.
     ///
     /// # Examples
@@ -44,8 +76,71 @@ impl Ray {
         self.origin + self.direction() * t
     }
 
+    /// Returns the point at the given distance t along `self`. This is an alias for [Ray::at].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let ray = Ray::new(Tuple::point(2.0, 3.0, 4.0), Tuple::vector(1.0, 0.0, 0.0));
+    /// for &t in &[0.0, 1.0, -1.0, 2.5] {
+    ///     assert_eq!(ray.position(t), ray.at(t));
+    /// }
+    /// ```
+    pub fn position(&self, t: f64) -> Tuple {
+        self.at(t)
+    }
+
+    /// Returns the points at all distances in `ts` along `self`, in the same order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let ray = Ray::new(Tuple::point(2.0, 3.0, 4.0), Tuple::vector(1.0, 0.0, 0.0));
+    /// assert_eq!(ray.at_many(&[0.0, 1.0]), [ray.at(0.0), ray.at(1.0)]);
+    /// assert!(ray.at_many(&[]).is_empty());
+    /// ```
+    pub fn at_many(&self, ts: &[f64]) -> Vec<Tuple> {
+        ts.iter().map(|&t| self.at(t)).collect()
+    }
+
+    /// Returns `n` evenly spaced points along `self`, from distance `t0` to `t1`. Both ends are
+    /// included, so the points are `(t1 - t0) / (n - 1)` apart. With `n` 1, this is just the point
+    /// at `t0`, and with `n` 0 it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 2.0, 0.0));
+    /// let points = ray.segment(1.0, 2.0, 5);
+    /// assert_eq!(points.len(), 5);
+    /// assert_eq!(points[0], ray.at(1.0));
+    /// assert_eq!(points[2], Tuple::point(0.0, 3.0, 0.0));
+    /// assert_eq!(points[4], ray.at(2.0));
+    /// assert_eq!(ray.segment(1.0, 2.0, 1), [ray.at(1.0)]);
+    /// ```
+    pub fn segment(&self, t0: f64, t1: f64, n: usize) -> Vec<Tuple> {
+        if n == 1 {
+            return vec![self.at(t0)];
+        }
+        (0..n)
+            .map(|i| {
+                let f = i as f64 / (n - 1) as f64;
+                self.at(t0 * (1.0 - f) + t1 * f)
+            })
+            .collect()
+    }
+
     /// Returns a new ray where both origin and direction are `self`'s origin and direction
-    /// transformed by `transform`.
+    /// transformed by `transform`. The time stays the same.
     ///
     /// # Examples
     ///
@@ -55,7 +150,7 @@ impl Ray {
     /// use truster::tuple::Tuple;
     /// use truster::matrix::Matrix;
     ///
-    ///	let ray = Ray::new(Tuple::point(1.0, 2.0, 3.0), Tuple::vector(0.0, 1.0, 0.0));
+    /// let ray = Ray::new(Tuple::point(1.0, 2.0, 3.0), Tuple::vector(0.0, 1.0, 0.0));
     /// let transform = Matrix::translation(3.0, 4.0, 5.0);
     /// let ray = ray.transform(&transform);
     ///
@@ -68,7 +163,7 @@ impl Ray {
     /// use truster::tuple::Tuple;
     /// use truster::matrix::Matrix;
     ///
-    ///	let ray = Ray::new(Tuple::point(1.0, 2.0, 3.0), Tuple::vector(0.0, 1.0, 0.0));
+    /// let ray = Ray::new(Tuple::point(1.0, 2.0, 3.0), Tuple::vector(0.0, 1.0, 0.0));
     /// let transform = Matrix::scaling(2.0, 3.0, 4.0);
     /// let ray = ray.transform(&transform);
     ///
@@ -78,6 +173,36 @@ impl Ray {
         Self {
             origin: transform * self.origin,
             direction: transform * self.direction,
+            time: self.time,
+        }
+    }
+
+    /// Returns `self` with its origin moved `eps` along `normal`, keeping the direction and time.
+    /// Rays spawned at a surface, like reflected and refracted rays, start a little off it this
+    /// way, so rounding errors don't make them hit the surface they start on. Use a negative `eps`
+    /// to move into the surface instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let ray = Ray::with_time(Tuple::point(1.0, 2.0, 3.0), Tuple::vector(1.0, 0.0, 0.0), 0.5);
+    /// let normal = Tuple::vector(0.0, 1.0, 0.0);
+    /// let offset = ray.offset_origin(normal, 0.25);
+    /// assert_eq!(offset.origin(), ray.origin() + normal * 0.25);
+    /// assert_eq!((offset.origin() - ray.origin()).norm(), 0.25);
+    /// assert_eq!(offset.direction(), ray.direction());
+    /// assert_eq!(offset.time(), ray.time());
+    ///
+    /// let under = ray.offset_origin(normal, -0.25);
+    /// assert_eq!(under.origin(), Tuple::point(1.0, 1.75, 3.0));
+    /// ```
+    pub fn offset_origin(&self, normal: Tuple, eps: f64) -> Self {
+        Self {
+            origin: self.origin + normal * eps,
+            ..*self
         }
     }
 }
@@ -0,0 +1,164 @@
+//! Deterministic sample generators, for anti-aliasing, soft shadows, ambient occlusion and the
+//! like.
+//!
+//! Every generator takes a seed, and gives the same samples for the same seed. Different seeds
+//! give unrelated samples.
+//!
+//! # Examples
+//!
+//! ```
+//! # use truster::sampling::{disk_samples, jittered_grid};
+//! assert_eq!(jittered_grid(4, 7), jittered_grid(4, 7));
+//! assert_ne!(jittered_grid(4, 7), jittered_grid(4, 8));
+//! assert_eq!(disk_samples(16, 0.5, 1).len(), 16);
+//! ```
+
+use std::f64::consts::PI;
+
+use crate::tuple::Tuple;
+
+/// Returns `n` by `n` points in the unit square [0, 1) x [0, 1), one at a random position in
+/// each cell of an `n` by `n` grid. This spreads samples more evenly than purely random ones,
+/// without the aliasing of a regular grid. The cells are in row major order.
+pub fn jittered_grid(n: usize, seed: u64) -> Vec<(f64, f64)> {
+    let mut rng = Rng::new(seed);
+    let step = 1.0 / n as f64;
+    let mut samples = Vec::with_capacity(n * n);
+    for j in 0..n {
+        for i in 0..n {
+            let x = (i as f64 + rng.next_f64()) * step;
+            let y = (j as f64 + rng.next_f64()) * step;
+            samples.push((x, y));
+        }
+    }
+    samples
+}
+
+/// Returns `n` points spread uniformly over the disk with `radius` around the origin, as (x, y).
+pub fn disk_samples(n: usize, radius: f64, seed: u64) -> Vec<(f64, f64)> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|_| {
+            let r = radius * rng.next_f64().sqrt();
+            let angle = 2.0 * PI * rng.next_f64();
+            (r * angle.cos(), r * angle.sin())
+        })
+        .collect()
+}
+
+/// Returns `n` unit vectors in the hemisphere around `normal`, which should be a unit vector.
+/// Directions close to `normal` are more likely, in proportion to the cosine of the angle with
+/// it. This is the distribution of light falling on a diffuse surface, so it suits ambient
+/// occlusion and diffuse bounces.
+pub fn hemisphere_cosine(n: usize, normal: Tuple, seed: u64) -> Vec<Tuple> {
+    let mut rng = Rng::new(seed);
+    let (u, v) = disk_basis(normal);
+    (0..n)
+        .map(|_| {
+            // Points uniform on the unit disk, lifted onto the hemisphere.
+            let r2 = rng.next_f64();
+            let r = r2.sqrt();
+            let angle = 2.0 * PI * rng.next_f64();
+            (u * angle.cos() + v * angle.sin()) * r + normal * (1.0 - r2).sqrt()
+        })
+        .collect()
+}
+
+/// Returns two unit vectors which are perpendicular to each other and to `normal`.
+pub(crate) fn disk_basis(normal: Tuple) -> (Tuple, Tuple) {
+    let helper = if normal.x().abs() < 0.9 {
+        Tuple::RIGHT
+    } else {
+        Tuple::UP
+    };
+    let u = normal.cross(helper).normalized();
+    let v = normal.cross(u);
+    (u, v)
+}
+
+/// A small xorshift pseudorandom number generator. It is not suited for anything but sampling.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        // The state can't be 0, and similar seeds should give unrelated sequences. Mix the seed
+        // with one step of splitmix64.
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        Self(if z == 0 { 1 } else { z })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number in [0, 1).
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jittered_grid_one_sample_per_cell() {
+        let n = 5;
+        let samples = jittered_grid(n, 3);
+        assert_eq!(samples.len(), n * n);
+        for (index, &(x, y)) in samples.iter().enumerate() {
+            let (i, j) = (index % n, index / n);
+            assert_eq!((x * n as f64).floor() as usize, i);
+            assert_eq!((y * n as f64).floor() as usize, j);
+        }
+    }
+
+    #[test]
+    fn disk_samples_within_radius() {
+        let radius = 2.5;
+        let samples = disk_samples(1000, radius, 11);
+        assert_eq!(samples.len(), 1000);
+        for &(x, y) in samples.iter() {
+            assert!(x * x + y * y <= radius * radius);
+        }
+        // Uniform over the area, so about a quarter falls within half the radius.
+        let inner = samples
+            .iter()
+            .filter(|&&(x, y)| x * x + y * y < radius * radius / 4.0)
+            .count();
+        assert!((200..300).contains(&inner), "{} samples inside", inner);
+    }
+
+    #[test]
+    fn hemisphere_cosine_above_surface() {
+        for &normal in &[
+            Tuple::UP,
+            Tuple::RIGHT,
+            Tuple::vector(1.0, -2.0, 0.5).normalized(),
+        ] {
+            let samples = hemisphere_cosine(500, normal, 5);
+            assert_eq!(samples.len(), 500);
+            for &sample in samples.iter() {
+                assert!(sample.dot(normal) > 0.0);
+                assert!((sample.norm() - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let normal = Tuple::UP;
+        assert_eq!(disk_samples(8, 1.0, 42), disk_samples(8, 1.0, 42));
+        assert_eq!(
+            hemisphere_cosine(8, normal, 42),
+            hemisphere_cosine(8, normal, 42)
+        );
+        assert_ne!(disk_samples(8, 1.0, 42), disk_samples(8, 1.0, 43));
+    }
+}
@@ -1,13 +1,35 @@
 //! Stores the [Shape] trait, as well as modules containing its implementation.
 
+use std::any;
+use std::fmt::{self, Debug, Formatter};
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
 use crate::intersection::Intersection;
 use crate::material::Material;
 use crate::matrix::Matrix;
 use crate::ray::Ray;
 use crate::tuple::Tuple;
 
+pub mod disk;
+pub mod group;
+pub mod moving_sphere;
 pub mod plane;
+pub mod quad;
+pub mod smooth_triangle;
 pub mod sphere;
+pub mod torus;
+pub mod triangle;
+
+pub use disk::Disk;
+pub use group::Group;
+pub use moving_sphere::MovingSphere;
+pub use plane::Plane;
+pub use quad::Quad;
+pub use smooth_triangle::SmoothTriangle;
+pub use sphere::Sphere;
+pub use torus::Torus;
+pub use triangle::Triangle;
 
 /// Represents a 3D shape with all methods to be able to render it, as well as methods for
 /// transforming it, and giving it a material.
@@ -23,15 +45,51 @@ pub mod sphere;
 /// the intersections. Intersections behind `ray` should also be in the list, but with a negative
 /// distance. This means they are at the front of the list. The intersections should be in local
 /// space. This means they should be calculated as if the shape where not transformed. The
-/// calculations for the transformation happen in [Shape::intersect], which should not be
-/// overwritten.
+/// calculations for the transformation happen in [Shape::intersect_rc], which should not be
+/// overwritten. `this` is a shared handle to the shape itself. The intersections should hold a
+/// clone of it, so no shape has to be copied for every intersection.
+///
+/// [Shape::intersect_rc] is what should be used when the shape is already behind an [Rc], like in
+/// a [World](crate::world::World). [Shape::intersect] is a convenience for shapes which are not
+/// shared. It copies the shape once, and should not be overwritten either.
 ///
 /// [Shape::local_normal_at] should return the surface normal of shape at `point`. The caller is
 /// responsible for making sure `point` is on the surface the shape. The resulting vector should
 /// be normalized. The normal should be in local space. This means they should be calculated as if
 /// the shape where not transformed. The calculations for the transformation happen in
-/// [Shape::normal_at], which should not be overwritten.
+/// [Shape::normal_at], which should not be overwritten. It also applies the normal texture of the
+/// shape's material, if there is one.
+///
+/// [Shape::name] returns the name of the shape's type, like `"Sphere"`, and can be overwritten to
+/// give shapes a more descriptive name. It is used when shapes are debug printed.
+///
+/// [Shape::kind] returns which of the shapes of this crate the shape is. Unlike the name, it is
+/// not meant to be overwritten, so it can identify shapes when storing them. The default is
+/// `None`, for shapes defined elsewhere.
+///
+/// [Shape::parent] should return the [Group] the shape is in, if any. [Shape::set_parent] should
+/// set it. It is called by [Group::add_child], so it should not be called directly. Shapes in a
+/// group are transformed by their own transform first, and then by those of all their parents.
+/// [Shape::world_to_object] and [Shape::normal_to_world] walk this chain, and should not be
+/// overwritten. [Shape::transform_normal] takes a normal from the shape's local space to that of
+/// its parent, and should not be overwritten either.
+///
+/// [Shape::bounds] should return the bounds of the shape in local space. The default is infinite
+/// bounds, which is always correct but keeps a [Bvh](crate::bvh::Bvh) from skipping the shape.
+/// [Shape::parent_space_bounds] returns the bounds after applying the shape's transform.
+///
+/// Shapes which move during the shutter interval override [Shape::transform_at], as well as
+/// [Shape::intersect_rc] and [Shape::normal_at_time] so they use the transform at the ray's time.
+/// For all other shapes, the defaults just use [Shape::transform].
+///
+/// [Shape::clone_box] returns a copy of the shape, or `None` if it can't be copied. Shapes which
+/// are [Clone] should return `Some(Box::new(self.clone()))`. The default is `None`.
 pub trait Shape {
+    fn name(&self) -> &'static str {
+        let name = any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     fn transform(&self) -> &Matrix;
     fn set_transform(&mut self, transform: Matrix);
     fn transform_inverse(&self) -> &Matrix;
@@ -39,30 +97,135 @@ pub trait Shape {
     fn material(&self) -> &Material;
     fn set_material(&mut self, material: Material);
 
-    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection>;
-    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
-        self.local_intersect(&ray.transform(&self.transform_inverse()))
+    fn parent(&self) -> Option<Rc<dyn Shape>>;
+    fn set_parent(&mut self, parent: Weak<dyn Shape>);
+
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection>;
+    fn intersect_rc(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        self.local_intersect(this, &ray.transform(self.transform_inverse()))
+    }
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection>
+    where
+        Self: Sized + Clone + 'static,
+    {
+        let this: Rc<dyn Shape> = Rc::new(self.clone());
+        self.intersect_rc(&this, ray)
     }
 
     fn local_normal_at(&self, point: Tuple) -> Tuple;
     fn normal_at(&self, point: Tuple) -> Tuple {
-        let point = self.transform_inverse() * point;
-        let normal = self.local_normal_at(point);
-        let normal = &self.transform_inverse().transpose() * normal;
-        let normal = Tuple::vector(normal.x(), normal.y(), normal.z());
-        normal.normalized()
+        let local_point = self.world_to_object(point);
+        let normal = self.local_normal_at(local_point);
+        let normal = self.normal_to_world(normal);
+        apply_normal_texture(self.material(), point, normal)
+    }
+
+    fn world_to_object(&self, point: Tuple) -> Tuple {
+        let point = match self.parent() {
+            Some(parent) => parent.world_to_object(point),
+            None => point,
+        };
+        self.transform_inverse() * point
+    }
+    fn transform_normal(&self, local_normal: Tuple) -> Tuple {
+        transform_normal(self.transform_inverse(), local_normal)
+    }
+    fn normal_to_world(&self, normal: Tuple) -> Tuple {
+        let normal = self.transform_normal(normal);
+        match self.parent() {
+            Some(parent) => parent.normal_to_world(normal),
+            None => normal,
+        }
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::infinite()
+    }
+    fn parent_space_bounds(&self) -> Bounds {
+        self.bounds().transform(self.transform())
+    }
+
+    fn transform_at(&self, _time: f64) -> Matrix {
+        self.transform().clone()
+    }
+    fn normal_at_time(&self, point: Tuple, _time: f64) -> Tuple {
+        self.normal_at(point)
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        None
+    }
+    fn kind(&self) -> Option<ShapeKind> {
+        None
+    }
+}
+
+/// The shapes of this crate, as returned by [Shape::kind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShapeKind {
+    /// A [Disk].
+    Disk,
+    /// A [Group].
+    Group,
+    /// A [MovingSphere].
+    MovingSphere,
+    /// A [Plane].
+    Plane,
+    /// A [Quad].
+    Quad,
+    /// A [SmoothTriangle].
+    SmoothTriangle,
+    /// A [Sphere].
+    Sphere,
+    /// A [Torus].
+    Torus,
+    /// A [Triangle].
+    Triangle,
+}
+
+/// Returns `normal` transformed by the transpose of `transform_inverse`, and normalized. This is
+/// how a normal has to be transformed for the surface to go through `transform`. Unlike
+/// transforming by `transform` itself, it keeps normals perpendicular to surfaces which are
+/// scaled non-uniformly.
+pub(crate) fn transform_normal(transform_inverse: &Matrix, normal: Tuple) -> Tuple {
+    let normal = &transform_inverse.transpose() * normal;
+    Tuple::vector(normal.x(), normal.y(), normal.z()).normalized()
+}
+
+/// Returns the world space `normal` at `point`, offset by the normal texture of `material` if it
+/// has one. See [Material::normal_texture].
+pub(crate) fn apply_normal_texture(material: &Material, point: Tuple, normal: Tuple) -> Tuple {
+    match &material.normal_texture {
+        Some(texture) => {
+            let offset = texture.color_at_texture(point);
+            (normal + Tuple::vector(offset.r(), offset.g(), offset.b())).normalized()
+        }
+        None => normal,
+    }
+}
+
+impl Debug for dyn Shape {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct(self.name())
+            .field("transform", self.transform())
+            .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::Color;
+    use crate::texture::solid_color::SolidColor;
     use std::cell::RefCell;
 
+    #[derive(Clone)]
     struct MockShape {
         transform: Matrix,
         transform_inverse: Matrix,
         material: Material,
+        parent: Option<Weak<dyn Shape>>,
         saved_ray: RefCell<Ray>,
     }
 
@@ -72,6 +235,7 @@ mod tests {
                 transform: Matrix::default(),
                 transform_inverse: Matrix::default(),
                 material: Material::default(),
+                parent: None,
                 saved_ray: RefCell::new(Ray::new(
                     Tuple::vector(0.0, 0.0, 0.0),
                     Tuple::vector(0.0, 0.0, 0.0),
@@ -99,8 +263,15 @@ mod tests {
             self.material = material
         }
 
-        fn local_intersect(&self, ray: &Ray) -> Vec<Intersection> {
-            *self.saved_ray.borrow_mut() = ray.clone();
+        fn parent(&self) -> Option<Rc<dyn Shape>> {
+            self.parent.as_ref().and_then(Weak::upgrade)
+        }
+        fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+            self.parent = Some(parent);
+        }
+
+        fn local_intersect(&self, _: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+            *self.saved_ray.borrow_mut() = *ray;
             Vec::new()
         }
 
@@ -109,6 +280,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn normal_at_zero_normal_texture() {
+        let mut shape = Sphere::new();
+        shape.set_material(
+            Material::builder()
+                .normal_texture(Rc::new(SolidColor::new(Color::BLACK)))
+                .build(),
+        );
+        let point = Tuple::point(0.0, 1.0, 0.0);
+        assert_eq!(shape.normal_at(point), Sphere::new().normal_at(point));
+    }
+
+    #[test]
+    fn normal_at_normal_texture() {
+        let mut shape = Sphere::new();
+        shape.set_material(
+            Material::builder()
+                .normal_texture(Rc::new(SolidColor::new(Color::new(1.0, 0.0, 0.0))))
+                .build(),
+        );
+        let normal = shape.normal_at(Tuple::point(0.0, 1.0, 0.0));
+        let v = 2.0_f64.sqrt() / 2.0;
+        assert!((normal.x() - v).abs() < 0.000_001);
+        assert!((normal.y() - v).abs() < 0.000_001);
+        assert_eq!(normal.z(), 0.0);
+        assert!((normal.norm() - 1.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(MockShape::new().name(), "MockShape");
+        assert_eq!(Sphere::new().name(), "Sphere");
+        let shape: Rc<dyn Shape> = Rc::new(Plane::new());
+        assert_eq!(shape.name(), "Plane");
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(MockShape::new().kind(), None);
+        assert_eq!(Sphere::new().kind(), Some(ShapeKind::Sphere));
+        let shape: Rc<dyn Shape> = Rc::new(Plane::new());
+        assert_eq!(shape.kind(), Some(ShapeKind::Plane));
+        assert_eq!(Group::new().kind(), Some(ShapeKind::Group));
+    }
+
+    #[test]
+    fn debug() {
+        let mut shape = Sphere::new();
+        shape.set_transform(Matrix::translation(1.0, 2.0, 3.0));
+        let shape: Rc<dyn Shape> = Rc::new(shape);
+        assert_eq!(
+            format!("{:?}", shape),
+            format!(
+                "Sphere {{ transform: {:?} }}",
+                Matrix::translation(1.0, 2.0, 3.0)
+            )
+        );
+    }
+
     #[test]
     fn intersect_scaled() {
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
@@ -134,6 +364,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn normal_at_translated() {
         let mut shape = MockShape::new();
         shape.set_transform(Matrix::translation(0.0, 1.0, 0.0));
@@ -145,6 +376,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn normal_at_transformed() {
         let mut shape = MockShape::new();
         shape.set_transform(
@@ -160,4 +392,21 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn transform_normal_non_uniform_scaling() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(Matrix::scaling(1.0, 0.5, 1.0));
+
+        // The surface is x² + 4y² + z² = 1, so the normal is along (x, 4y, z).
+        let v = 2.0_f64.sqrt() / 2.0;
+        let local_normal = Tuple::vector(v, v, 0.0);
+        let point = Tuple::point(v, v / 2.0, 0.0);
+        let expected = Tuple::vector(point.x(), 4.0 * point.y(), point.z()).normalized();
+
+        let normal = sphere.transform_normal(local_normal);
+        assert!((normal - expected).norm() < 1e-9);
+        assert!((normal.norm() - 1.0).abs() < 1e-9);
+        assert!((sphere.normal_at(point) - expected).norm() < 1e-9);
+    }
 }
@@ -0,0 +1,200 @@
+//! Holds the [Disk] struct;
+
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+use super::{Shape, ShapeKind};
+
+/// A flat disk in the X-Z plane, centered at the origin. A disk can have a hole in the middle,
+/// which turns it into an annulus (a ring).
+#[derive(Clone)]
+pub struct Disk {
+    transform: Matrix,
+    transform_inverse: Matrix,
+    material: Material,
+    parent: Option<Weak<dyn Shape>>,
+    inner_radius: f64,
+    outer_radius: f64,
+}
+
+impl Disk {
+    /// Returns a new disk with radius 1 and no hole.
+    /// Use [Disk::set_transform] to change it's orientation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new annulus, which covers the points with a distance from the origin between
+    /// `inner_radius` and `outer_radius`. An inner radius of 0 gives a full disk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inner_radius` is negative, or larger than `outer_radius`.
+    pub fn with_radii(inner_radius: f64, outer_radius: f64) -> Self {
+        if inner_radius < 0.0 || inner_radius > outer_radius {
+            panic!(
+                "invalid disk radii, inner: {}, outer: {}",
+                inner_radius, outer_radius
+            );
+        }
+        Self {
+            inner_radius,
+            outer_radius,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the radius of `self`'s hole. This is 0 if there is no hole.
+    pub fn inner_radius(&self) -> f64 {
+        self.inner_radius
+    }
+
+    /// Returns `self`'s radius.
+    pub fn outer_radius(&self) -> f64 {
+        self.outer_radius
+    }
+}
+
+impl Default for Disk {
+    fn default() -> Self {
+        Self {
+            transform: Matrix::default(),
+            transform_inverse: Matrix::default(),
+            material: Material::default(),
+            parent: None,
+            inner_radius: 0.0,
+            outer_radius: 1.0,
+        }
+    }
+}
+
+impl Shape for Disk {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+    fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Disk)
+    }
+
+    fn local_normal_at(&self, _: Tuple) -> Tuple {
+        Tuple::vector(0.0, 1.0, 0.0)
+    }
+
+    fn bounds(&self) -> Bounds {
+        let r = self.outer_radius;
+        Bounds::new(Tuple::point(-r, 0.0, -r), Tuple::point(r, 0.0, r))
+    }
+
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        if ray.direction().y().abs() < EPSILON {
+            return Vec::new();
+        }
+
+        let t = -ray.origin().y() / ray.direction().y();
+        let point = ray.at(t);
+        let distance_squared = point.x() * point.x() + point.z() * point.z();
+        if distance_squared < self.inner_radius * self.inner_radius
+            || distance_squared > self.outer_radius * self.outer_radius
+        {
+            return Vec::new();
+        }
+
+        vec![Intersection::new(t, Rc::clone(this))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn down_ray(x: f64, z: f64) -> Ray {
+        Ray::new(Tuple::point(x, 1.0, z), Tuple::vector(0.0, -1.0, 0.0))
+    }
+
+    #[test]
+    fn local_normal_at() {
+        let disk = Disk::new();
+        let normal = Tuple::vector(0.0, 1.0, 0.0);
+        assert_eq!(disk.local_normal_at(Tuple::point(0.0, 0.0, 0.0)), normal);
+        assert_eq!(disk.local_normal_at(Tuple::point(0.5, 0.0, -0.5)), normal);
+    }
+
+    #[test]
+    fn intersect_full_disk() {
+        let disk: Rc<dyn Shape> = Rc::new(Disk::new());
+        let intersections = disk.local_intersect(&disk, &down_ray(0.0, 0.0));
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 1.0);
+        assert_eq!(disk.local_intersect(&disk, &down_ray(0.6, 0.6)).len(), 1);
+        assert!(disk.local_intersect(&disk, &down_ray(0.8, 0.8)).is_empty());
+    }
+
+    #[test]
+    fn intersect_ring() {
+        let disk: Rc<dyn Shape> = Rc::new(Disk::with_radii(0.5, 2.0));
+        let intersections = disk.local_intersect(&disk, &down_ray(1.0, 0.0));
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 1.0);
+        assert_eq!(disk.local_intersect(&disk, &down_ray(0.0, -1.9)).len(), 1);
+    }
+
+    #[test]
+    fn intersect_hole() {
+        let disk: Rc<dyn Shape> = Rc::new(Disk::with_radii(0.5, 2.0));
+        assert!(disk.local_intersect(&disk, &down_ray(0.0, 0.0)).is_empty());
+        assert!(disk.local_intersect(&disk, &down_ray(0.3, -0.3)).is_empty());
+    }
+
+    #[test]
+    fn intersect_outside() {
+        let disk: Rc<dyn Shape> = Rc::new(Disk::with_radii(0.5, 2.0));
+        assert!(disk.local_intersect(&disk, &down_ray(2.1, 0.0)).is_empty());
+        assert!(disk.local_intersect(&disk, &down_ray(-1.5, 1.5)).is_empty());
+    }
+
+    #[test]
+    fn intersect_parallel() {
+        let disk: Rc<dyn Shape> = Rc::new(Disk::new());
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert!(disk.local_intersect(&disk, &ray).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid disk radii, inner: 2, outer: 1")]
+    fn with_radii_inner_larger_than_outer() {
+        Disk::with_radii(2.0, 1.0);
+    }
+}
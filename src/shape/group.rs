@@ -0,0 +1,245 @@
+//! Holds the [Group] struct.
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+use super::{Shape, ShapeKind};
+
+/// A collection of shapes which are transformed together. The transform of a group applies to all
+/// of its children, on top of their own transforms. Groups can be nested.
+///
+/// Children have to know their parent to compute their normals, so a group must be behind an [Rc]
+/// before children are added to it, and children must be fully set up before they are added. See
+/// [Group::add_child].
+///
+/// A group has no surface of its own, so its material is not used. Its children point back to it,
+/// so it can't be copied with [Shape::clone_box].
+#[derive(Default)]
+pub struct Group {
+    transform: Matrix,
+    transform_inverse: Matrix,
+    material: Material,
+    parent: Option<Weak<dyn Shape>>,
+    children: RefCell<Vec<Rc<dyn Shape>>>,
+}
+
+impl Group {
+    /// Returns a new empty group with the identity transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `child` to `group`, and makes `group` its parent. Returns a handle to the child, so a
+    /// nested group can get children of its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::shape::{Group, Shape, Sphere};
+    /// use std::rc::Rc;
+    /// use truster::matrix::Matrix;
+    ///
+    /// let outer = Rc::new(Group::new());
+    /// let inner = Group::add_child(&outer, Group::new());
+    /// let sphere = Group::add_child(&inner, Sphere::new());
+    ///
+    /// assert_eq!(outer.children().len(), 1);
+    /// assert_eq!(inner.children().len(), 1);
+    /// assert!(sphere.parent().is_some());
+    /// ```
+    pub fn add_child<S: Shape + 'static>(group: &Rc<Group>, mut child: S) -> Rc<S> {
+        let parent: Weak<dyn Shape> = Rc::downgrade(group) as Weak<Group>;
+        child.set_parent(parent);
+        let child = Rc::new(child);
+        group
+            .children
+            .borrow_mut()
+            .push(Rc::clone(&child) as Rc<dyn Shape>);
+        child
+    }
+
+    /// Returns the children of `self`, in the order they were added.
+    pub fn children(&self) -> Vec<Rc<dyn Shape>> {
+        self.children.borrow().clone()
+    }
+}
+
+impl Shape for Group {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+    fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    /// Returns the intersections of `ray` with all children of `self`, sorted by distance. The
+    /// intersections hold the child which was hit, not the group.
+    fn local_intersect(&self, _: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        let mut result = Vec::new();
+        for child in self.children.borrow().iter() {
+            result.append(&mut child.intersect_rc(child, ray));
+        }
+        result.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+
+    /// Returns the bounds containing all children of `self`.
+    fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::empty();
+        for child in self.children.borrow().iter() {
+            bounds.merge(&child.parent_space_bounds());
+        }
+        bounds
+    }
+
+    /// Groups have no surface, so this should never be called.
+    ///
+    /// # Panics
+    ///
+    /// Always panics.
+    fn local_normal_at(&self, _: Tuple) -> Tuple {
+        panic!("a group has no normal, ask one of its children instead");
+    }
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::sphere::Sphere;
+    use std::f64::consts::PI;
+
+    fn assert_tuple_eq(actual: Tuple, expected: Tuple) {
+        for i in 0..4 {
+            assert!(
+                (actual[i] - expected[i]).abs() < 0.0001,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    fn nested_sphere(scaling: Matrix) -> (Rc<Group>, Rc<Sphere>) {
+        let mut g1 = Group::new();
+        g1.set_transform(Matrix::rotation_y(PI / 2.0));
+        let g1 = Rc::new(g1);
+        let mut g2 = Group::new();
+        g2.set_transform(scaling);
+        let g2 = Group::add_child(&g1, g2);
+        let mut sphere = Sphere::new();
+        sphere.set_transform(Matrix::translation(5.0, 0.0, 0.0));
+        let sphere = Group::add_child(&g2, sphere);
+        (g1, sphere)
+    }
+
+    #[test]
+    fn add_child() {
+        let group = Rc::new(Group::new());
+        let sphere = Group::add_child(&group, Sphere::new());
+        let child: Rc<dyn Shape> = sphere.clone();
+
+        assert_eq!(group.children().len(), 1);
+        assert!(Rc::ptr_eq(&group.children()[0], &child));
+        let group_dyn: Rc<dyn Shape> = group.clone();
+        assert!(Rc::ptr_eq(&sphere.parent().unwrap(), &group_dyn));
+    }
+
+    #[test]
+    fn intersect_empty() {
+        let group: Rc<dyn Shape> = Rc::new(Group::new());
+        let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert!(group.intersect_rc(&group, &ray).is_empty());
+    }
+
+    #[test]
+    fn intersect_children() {
+        let group = Rc::new(Group::new());
+        let s1: Rc<dyn Shape> = Group::add_child(&group, Sphere::new());
+        let mut s2 = Sphere::new();
+        s2.set_transform(Matrix::translation(0.0, 0.0, -3.0));
+        let s2: Rc<dyn Shape> = Group::add_child(&group, s2);
+        let mut s3 = Sphere::new();
+        s3.set_transform(Matrix::translation(5.0, 0.0, 0.0));
+        Group::add_child(&group, s3);
+
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let group: Rc<dyn Shape> = group;
+        let intersections = group.intersect_rc(&group, &ray);
+
+        assert_eq!(intersections.len(), 4);
+        assert!(Rc::ptr_eq(&intersections[0].shape(), &s2));
+        assert!(Rc::ptr_eq(&intersections[1].shape(), &s2));
+        assert!(Rc::ptr_eq(&intersections[2].shape(), &s1));
+        assert!(Rc::ptr_eq(&intersections[3].shape(), &s1));
+    }
+
+    #[test]
+    fn intersect_transformed() {
+        let mut group = Group::new();
+        group.set_transform(Matrix::scaling(2.0, 2.0, 2.0));
+        let group = Rc::new(group);
+        let mut sphere = Sphere::new();
+        sphere.set_transform(Matrix::translation(5.0, 0.0, 0.0));
+        Group::add_child(&group, sphere);
+
+        let ray = Ray::new(Tuple::point(10.0, 0.0, -10.0), Tuple::vector(0.0, 0.0, 1.0));
+        let group: Rc<dyn Shape> = group;
+        assert_eq!(group.intersect_rc(&group, &ray).len(), 2);
+    }
+
+    #[test]
+    fn world_to_object() {
+        let (_group, sphere) = nested_sphere(Matrix::scaling(2.0, 2.0, 2.0));
+        assert_tuple_eq(
+            sphere.world_to_object(Tuple::point(-2.0, 0.0, -10.0)),
+            Tuple::point(0.0, 0.0, -1.0),
+        );
+    }
+
+    #[test]
+    fn normal_to_world() {
+        let (_group, sphere) = nested_sphere(Matrix::scaling(1.0, 2.0, 3.0));
+        let v = 3.0_f64.sqrt() / 3.0;
+        assert_tuple_eq(
+            sphere.normal_to_world(Tuple::vector(v, v, v)),
+            Tuple::vector(0.2857, 0.4286, -0.8571),
+        );
+    }
+
+    #[test]
+    fn normal_at_nested() {
+        let (_group, sphere) = nested_sphere(Matrix::scaling(1.0, 2.0, 3.0));
+        assert_tuple_eq(
+            sphere.normal_at(Tuple::point(1.7321, 1.1547, -5.5774)),
+            Tuple::vector(0.2857, 0.4286, -0.8571),
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Holds the [MovingSphere] struct.
+
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+use super::{apply_normal_texture, sphere::Sphere, transform_normal, Shape, ShapeKind};
+
+/// A sphere which moves while the shutter is open, to render motion blur. Its transform goes
+/// linearly from a start transform at time 0 to an end transform at time 1. Rays are intersected
+/// with the sphere as it is at the ray's [time](Ray::time).
+///
+/// The transforms are interpolated component-wise, which works well for translations and
+/// scalings. Rotations by large angles will deform the sphere halfway.
+///
+/// Normals, and the shadow, reflected and refracted rays sent from a hit, use the time of the ray
+/// as well. Textures don't: they are looked up with the transform at time 0, so a texture stays
+/// where the sphere starts while the sphere moves through it. Moving spheres look best with a
+/// single color.
+#[derive(Default, Clone)]
+pub struct MovingSphere {
+    sphere: Sphere,
+    end_transform: Matrix,
+}
+
+impl MovingSphere {
+    /// Returns a new unit sphere which moves from `start` at time 0 to `end` at time 1.
+    pub fn new(start: Matrix, end: Matrix) -> Self {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(start);
+        Self {
+            sphere,
+            end_transform: end,
+        }
+    }
+
+    /// Returns `self`'s transform at time 1.
+    pub fn end_transform(&self) -> &Matrix {
+        &self.end_transform
+    }
+
+    /// Sets `self`'s transform at time 1 to be `transform`.
+    pub fn set_end_transform(&mut self, transform: Matrix) {
+        self.end_transform = transform;
+    }
+}
+
+impl Shape for MovingSphere {
+    /// Returns the intersections of `ray` with `self` as it is at the time of `ray`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::shape::{Shape, MovingSphere};
+    /// use truster::matrix::Matrix;
+    /// use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let sphere = MovingSphere::new(Matrix::eye(), Matrix::translation(0.0, 0.0, 2.0));
+    /// let origin = Tuple::point(0.0, 0.0, -5.0);
+    /// let direction = Tuple::vector(0.0, 0.0, 1.0);
+    ///
+    /// let intersections = sphere.intersect(&Ray::with_time(origin, direction, 0.5));
+    /// assert_eq!(intersections[0].t(), 5.0);
+    /// assert_eq!(intersections[1].t(), 7.0);
+    /// ```
+    fn intersect_rc(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        let inverse = self.transform_at(ray.time()).inverse();
+        self.local_intersect(this, &ray.transform(&inverse))
+    }
+
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        self.sphere.local_intersect(this, ray)
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        self.sphere.local_normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.sphere.bounds()
+    }
+
+    /// Returns the bounds covering `self` over the whole shutter interval. Because the transform
+    /// is interpolated linearly, the bounds at the start and end contain all bounds in between.
+    fn parent_space_bounds(&self) -> Bounds {
+        let mut bounds = self.bounds().transform(self.transform());
+        bounds.merge(&self.bounds().transform(&self.end_transform));
+        bounds
+    }
+
+    /// Returns the surface normal of `self` at `point`, as it is at `time`. Like
+    /// [Shape::normal_at], the normal texture of the material is applied.
+    fn normal_at_time(&self, point: Tuple, time: f64) -> Tuple {
+        let parent_point = match self.parent() {
+            Some(parent) => parent.world_to_object(point),
+            None => point,
+        };
+        let inverse = self.transform_at(time).inverse();
+        let normal = transform_normal(&inverse, self.local_normal_at(&inverse * parent_point));
+        let normal = match self.parent() {
+            Some(parent) => parent.normal_to_world(normal),
+            None => normal,
+        };
+        apply_normal_texture(self.material(), point, normal)
+    }
+
+    /// Returns the transform of `self` at `time`, interpolated between the start and end
+    /// transform.
+    fn transform_at(&self, time: f64) -> Matrix {
+        let start = self.transform();
+        let end = &self.end_transform;
+        let mut result = Matrix::eye();
+        for i in 0..4 {
+            for j in 0..4 {
+                result[[i, j]] = start[[i, j]] + (end[[i, j]] - start[[i, j]]) * time;
+            }
+        }
+        result
+    }
+
+    /// Sets `self`'s transform at time 0 to be `transform`.
+    fn set_transform(&mut self, transform: Matrix) {
+        self.sphere.set_transform(transform);
+    }
+
+    /// Returns `self`'s transform at time 0.
+    fn transform(&self) -> &Matrix {
+        self.sphere.transform()
+    }
+
+    /// Returns the inverse of `self`'s transform at time 0.
+    fn transform_inverse(&self) -> &Matrix {
+        self.sphere.transform_inverse()
+    }
+
+    /// Returns `self`'s material.
+    fn material(&self) -> &Material {
+        self.sphere.material()
+    }
+
+    /// Sets `self`'s material to be `material`.
+    fn set_material(&mut self, material: Material) {
+        self.sphere.set_material(material);
+    }
+
+    /// Returns the group `self` is in, if any.
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.sphere.parent()
+    }
+
+    /// Sets the group `self` is in.
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.sphere.set_parent(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::MovingSphere)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect_at_different_times() {
+        let shape: Rc<dyn Shape> = Rc::new(MovingSphere::new(
+            Matrix::eye(),
+            Matrix::translation(0.0, 0.0, 2.0),
+        ));
+        let origin = Tuple::point(0.0, 0.0, -5.0);
+        let direction = Tuple::vector(0.0, 0.0, 1.0);
+
+        let ray0 = Ray::with_time(origin, direction, 0.0);
+        let ray1 = Ray::with_time(origin, direction, 1.0);
+        let hit0 = shape.intersect_rc(&shape, &ray0)[0].t();
+        let hit1 = shape.intersect_rc(&shape, &ray1)[0].t();
+
+        assert_eq!(ray0.at(hit0), Tuple::point(0.0, 0.0, -1.0));
+        assert_eq!(ray1.at(hit1), Tuple::point(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn intersect_leaves_sphere_behind() {
+        let shape: Rc<dyn Shape> = Rc::new(MovingSphere::new(
+            Matrix::eye(),
+            Matrix::translation(0.0, 3.0, 0.0),
+        ));
+        let origin = Tuple::point(0.0, 0.0, -5.0);
+        let direction = Tuple::vector(0.0, 0.0, 1.0);
+
+        assert_eq!(
+            shape
+                .intersect_rc(&shape, &Ray::with_time(origin, direction, 0.0))
+                .len(),
+            2
+        );
+        assert!(shape
+            .intersect_rc(&shape, &Ray::with_time(origin, direction, 1.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn normal_at_time() {
+        let shape = MovingSphere::new(Matrix::eye(), Matrix::translation(0.0, 2.0, 0.0));
+        let point = Tuple::point(0.0, 2.0, -1.0);
+        assert_eq!(
+            shape.normal_at_time(point, 1.0),
+            Tuple::vector(0.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn normal_at_time_normal_texture() {
+        use crate::color::Color;
+        use crate::texture::solid_color::SolidColor;
+
+        let mut shape = MovingSphere::new(Matrix::eye(), Matrix::translation(0.0, 2.0, 0.0));
+        shape.set_material(
+            Material::builder()
+                .normal_texture(Rc::new(SolidColor::new(Color::new(1.0, 0.0, 0.0))))
+                .build(),
+        );
+        let normal = shape.normal_at_time(Tuple::point(0.0, 3.0, 0.0), 1.0);
+        let v = 2.0_f64.sqrt() / 2.0;
+        assert!((normal.x() - v).abs() < 0.000_001);
+        assert!((normal.y() - v).abs() < 0.000_001);
+        assert_eq!(normal.z(), 0.0);
+    }
+
+    #[test]
+    fn texture_uses_start_transform() {
+        use crate::color::Color;
+        use crate::texture::{stripe::Stripe, Texture};
+
+        let texture = Stripe::colors(Color::WHITE, Color::BLACK);
+        let shape: Rc<dyn Shape> = Rc::new(MovingSphere::new(
+            Matrix::eye(),
+            Matrix::translation(1.0, 0.0, 0.0),
+        ));
+        // At time 1, (1.5, 0, 0) is the point (0.5, 0, 0) of the sphere, which is white. The
+        // texture is looked up at time 0 though, where it is the point (1.5, 0, 0).
+        let point = Tuple::point(1.5, 0.0, 0.0);
+        assert_eq!(
+            shape.normal_at_time(point, 1.0),
+            Tuple::vector(1.0, 0.0, 0.0)
+        );
+        assert_eq!(texture.color_at_shape(point, shape), Color::BLACK);
+    }
+
+    #[test]
+    fn transform_at() {
+        let shape = MovingSphere::new(Matrix::eye(), Matrix::translation(2.0, 4.0, 6.0));
+        assert_eq!(shape.transform_at(0.0), Matrix::eye());
+        assert_eq!(shape.transform_at(0.5), Matrix::translation(1.0, 2.0, 3.0));
+        assert_eq!(shape.transform_at(1.0), Matrix::translation(2.0, 4.0, 6.0));
+    }
+}
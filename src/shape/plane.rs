@@ -1,14 +1,16 @@
 //! Holds the [Plane] struct;
 
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
+use crate::bounds::Bounds;
 use crate::intersection::Intersection;
 use crate::material::Material;
 use crate::matrix::Matrix;
 use crate::ray::Ray;
 use crate::tuple::Tuple;
+use crate::EPSILON;
 
-use super::Shape;
+use super::{Shape, ShapeKind};
 
 /// A 3D plane.
 #[derive(Default, Clone)]
@@ -16,6 +18,7 @@ pub struct Plane {
     transform: Matrix,
     transform_inverse: Matrix,
     material: Material,
+    parent: Option<Weak<dyn Shape>>,
 }
 
 impl Plane {
@@ -26,8 +29,6 @@ impl Plane {
     }
 }
 
-const EPS: f64 = 0.000_001;
-
 impl Shape for Plane {
     fn transform(&self) -> &Matrix {
         &self.transform
@@ -47,18 +48,41 @@ impl Shape for Plane {
         self.material = material;
     }
 
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Plane)
+    }
+
     fn local_normal_at(&self, _: Tuple) -> Tuple {
         Tuple::vector(0.0, 1.0, 0.0)
     }
 
-    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection> {
-        if ray.direction().y().abs() < EPS {
+    /// Returns bounds which are infinite along the x and z axes, and flat along the y axis.
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        if ray.direction().y().abs() < EPSILON {
             return Vec::new();
         }
 
         let t = -ray.origin().y() / ray.direction().y();
 
-        vec![Intersection::new(t, Rc::new(self.clone()))]
+        vec![Intersection::new(t, Rc::clone(this))]
     }
 }
 
@@ -78,36 +102,46 @@ mod tests {
         assert_eq!(n3, normal);
     }
 
+    #[test]
+    fn bounds() {
+        let bounds = Plane::new().bounds();
+        assert!(bounds.is_infinite());
+        assert_eq!(bounds.min().x(), f64::NEG_INFINITY);
+        assert_eq!(bounds.max().z(), f64::INFINITY);
+        assert_eq!(bounds.min().y(), 0.0);
+        assert_eq!(bounds.max().y(), 0.0);
+    }
+
     #[test]
     fn intersect_parallel() {
-        let plane = Plane::new();
+        let plane: Rc<dyn Shape> = Rc::new(Plane::new());
         let ray = Ray::new(Tuple::point(0.0, 10.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
-        let intersections = plane.local_intersect(&ray);
+        let intersections = plane.local_intersect(&plane, &ray);
         assert_eq!(intersections.len(), 0);
     }
 
     #[test]
     fn intersect_coplanar() {
-        let plane = Plane::new();
+        let plane: Rc<dyn Shape> = Rc::new(Plane::new());
         let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
-        let intersections = plane.local_intersect(&ray);
+        let intersections = plane.local_intersect(&plane, &ray);
         assert_eq!(intersections.len(), 0);
     }
 
     #[test]
     fn intersect_above() {
-        let plane = Plane::new();
+        let plane: Rc<dyn Shape> = Rc::new(Plane::new());
         let ray = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
-        let intersections = plane.local_intersect(&ray);
+        let intersections = plane.local_intersect(&plane, &ray);
         assert_eq!(intersections.len(), 1);
         assert_eq!(intersections[0].t(), 1.0);
     }
 
     #[test]
     fn intersect_below() {
-        let plane = Plane::new();
+        let plane: Rc<dyn Shape> = Rc::new(Plane::new());
         let ray = Ray::new(Tuple::point(0.0, -1.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
-        let intersections = plane.local_intersect(&ray);
+        let intersections = plane.local_intersect(&plane, &ray);
         assert_eq!(intersections.len(), 1);
         assert_eq!(intersections[0].t(), 1.0);
     }
@@ -0,0 +1,159 @@
+//! Holds the [Quad] struct;
+
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+use super::{Shape, ShapeKind};
+
+/// A flat square in the X-Z plane, covering x and z between -1 and 1. Unlike a
+/// [Plane](super::Plane), a quad has finite bounds, so it can be put in a [Bvh](crate::bvh::Bvh),
+/// and makes for walls and floors which end where they should.
+///
+/// Intersections carry uv coordinates, going from (0, 0) at (-1, 0, -1) to (1, 1) at (1, 0, 1).
+#[derive(Default, Clone)]
+pub struct Quad {
+    transform: Matrix,
+    transform_inverse: Matrix,
+    material: Material,
+    parent: Option<Weak<dyn Shape>>,
+}
+
+impl Quad {
+    /// Returns a new quad. Use [Quad::set_transform] to change it's size and orientation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Shape for Quad {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+    fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Quad)
+    }
+
+    fn local_normal_at(&self, _: Tuple) -> Tuple {
+        Tuple::UP
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(Tuple::point(-1.0, 0.0, -1.0), Tuple::point(1.0, 0.0, 1.0))
+    }
+
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        if ray.direction().y().abs() < EPSILON {
+            return Vec::new();
+        }
+
+        let t = -ray.origin().y() / ray.direction().y();
+        let point = ray.at(t);
+        if point.x().abs() > 1.0 || point.z().abs() > 1.0 {
+            return Vec::new();
+        }
+
+        let u = (point.x() + 1.0) / 2.0;
+        let v = (point.z() + 1.0) / 2.0;
+        vec![Intersection::with_uv(t, Rc::clone(this), u, v)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn down_ray(x: f64, z: f64) -> Ray {
+        Ray::new(Tuple::point(x, 1.0, z), Tuple::vector(0.0, -1.0, 0.0))
+    }
+
+    #[test]
+    fn local_normal_at() {
+        let quad = Quad::new();
+        let normal = Tuple::vector(0.0, 1.0, 0.0);
+        assert_eq!(quad.local_normal_at(Tuple::point(0.0, 0.0, 0.0)), normal);
+        assert_eq!(quad.local_normal_at(Tuple::point(0.9, 0.0, -0.9)), normal);
+    }
+
+    #[test]
+    fn intersect_inside() {
+        let quad: Rc<dyn Shape> = Rc::new(Quad::new());
+        let intersections = quad.local_intersect(&quad, &down_ray(0.0, 0.0));
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 1.0);
+        assert_eq!(intersections[0].u(), Some(0.5));
+        assert_eq!(intersections[0].v(), Some(0.5));
+
+        let intersections = quad.local_intersect(&quad, &down_ray(0.99, -0.99));
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(quad.local_intersect(&quad, &down_ray(-0.9, 0.9)).len(), 1);
+    }
+
+    #[test]
+    fn intersect_just_outside_each_edge() {
+        let quad: Rc<dyn Shape> = Rc::new(Quad::new());
+        assert!(quad.local_intersect(&quad, &down_ray(1.01, 0.0)).is_empty());
+        assert!(quad
+            .local_intersect(&quad, &down_ray(-1.01, 0.0))
+            .is_empty());
+        assert!(quad.local_intersect(&quad, &down_ray(0.0, 1.01)).is_empty());
+        assert!(quad
+            .local_intersect(&quad, &down_ray(0.0, -1.01))
+            .is_empty());
+    }
+
+    #[test]
+    fn intersect_from_below() {
+        let quad: Rc<dyn Shape> = Rc::new(Quad::new());
+        let ray = Ray::new(Tuple::point(0.5, -2.0, 0.5), Tuple::vector(0.0, 1.0, 0.0));
+        let intersections = quad.local_intersect(&quad, &ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t(), 2.0);
+    }
+
+    #[test]
+    fn intersect_parallel() {
+        let quad: Rc<dyn Shape> = Rc::new(Quad::new());
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert!(quad.local_intersect(&quad, &ray).is_empty());
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds = Quad::new().bounds();
+        assert_eq!(bounds.min(), Tuple::point(-1.0, 0.0, -1.0));
+        assert_eq!(bounds.max(), Tuple::point(1.0, 0.0, 1.0));
+    }
+}
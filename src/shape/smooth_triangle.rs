@@ -0,0 +1,155 @@
+//! Holds the [SmoothTriangle] struct.
+
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+use super::{triangle::Triangle, Shape, ShapeKind};
+
+/// A triangle with a normal at each corner. The normal anywhere else on the triangle is
+/// interpolated between them, which makes meshes of triangles look smooth.
+#[derive(Clone)]
+pub struct SmoothTriangle {
+    triangle: Triangle,
+    n1: Tuple,
+    n2: Tuple,
+    n3: Tuple,
+}
+
+impl SmoothTriangle {
+    /// Returns a new triangle with corners `p1`, `p2` and `p3`, with normals `n1`, `n2` and `n3`
+    /// respectively.
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple, n1: Tuple, n2: Tuple, n3: Tuple) -> Self {
+        Self {
+            triangle: Triangle::new(p1, p2, p3),
+            n1,
+            n2,
+            n3,
+        }
+    }
+
+    /// Returns the flat triangle `self` is built on.
+    pub fn triangle(&self) -> &Triangle {
+        &self.triangle
+    }
+
+    /// Returns the normal at `self`'s first corner.
+    pub fn n1(&self) -> Tuple {
+        self.n1
+    }
+
+    /// Returns the normal at `self`'s second corner.
+    pub fn n2(&self) -> Tuple {
+        self.n2
+    }
+
+    /// Returns the normal at `self`'s third corner.
+    pub fn n3(&self) -> Tuple {
+        self.n3
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn transform(&self) -> &Matrix {
+        self.triangle.transform()
+    }
+    fn transform_inverse(&self) -> &Matrix {
+        self.triangle.transform_inverse()
+    }
+    fn set_transform(&mut self, transform: Matrix) {
+        self.triangle.set_transform(transform);
+    }
+
+    fn material(&self) -> &Material {
+        self.triangle.material()
+    }
+    fn set_material(&mut self, material: Material) {
+        self.triangle.set_material(material);
+    }
+
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.triangle.parent()
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.triangle.set_parent(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::SmoothTriangle)
+    }
+
+    /// Returns the normal at `point`, interpolated between the corner normals with the
+    /// barycentric coordinates of `point`.
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let (u, v) = self.triangle.barycentric(point);
+        self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v)
+    }
+
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        self.triangle.local_intersect(this, ray)
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.triangle.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn intersect_stores_uv() {
+        let triangle: Rc<dyn Shape> = Rc::new(triangle());
+        let ray = Ray::new(Tuple::point(-0.2, 0.3, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+        let intersections = triangle.local_intersect(&triangle, &ray);
+        assert_eq!(intersections.len(), 1);
+        assert!((intersections[0].u().unwrap() - 0.45).abs() < 1e-6);
+        assert!((intersections[0].v().unwrap() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normal_at_interpolates() {
+        let triangle = triangle();
+        let normal = triangle.normal_at(Tuple::point(-0.2, 0.3, 0.0));
+        let expected = Tuple::vector(-0.5547, 0.83205, 0.0);
+        assert!((normal - expected).norm() < 1e-4);
+    }
+
+    #[test]
+    fn normal_at_corners() {
+        let triangle = triangle();
+        assert_eq!(
+            triangle.local_normal_at(Tuple::point(0.0, 1.0, 0.0)),
+            triangle.n1()
+        );
+        assert_eq!(
+            triangle.local_normal_at(Tuple::point(-1.0, 0.0, 0.0)),
+            triangle.n2()
+        );
+        assert_eq!(
+            triangle.local_normal_at(Tuple::point(1.0, 0.0, 0.0)),
+            triangle.n3()
+        );
+    }
+}
@@ -1,14 +1,15 @@
 //! Holds the [Sphere] struct;
 
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
+use crate::bounds::Bounds;
 use crate::intersection::Intersection;
 use crate::material::Material;
 use crate::matrix::Matrix;
 use crate::ray::Ray;
 use crate::tuple::Tuple;
 
-use super::Shape;
+use super::{Shape, ShapeKind};
 
 /// A 3D ellipsoid (spheroid).
 #[derive(Default, Clone)]
@@ -16,6 +17,7 @@ pub struct Sphere {
     transform: Matrix,
     transform_inverse: Matrix,
     material: Material,
+    parent: Option<Weak<dyn Shape>>,
 }
 
 impl Sphere {
@@ -34,72 +36,80 @@ impl Shape for Sphere {
     /// A ray intersects a sphere at two points.
     /// ```
     /// # use truster::shape::{Shape, sphere::Sphere};
+    /// use truster::intersection::Distances;
     /// use truster::ray::Ray;
     /// use truster::tuple::Tuple;
     ///
     /// let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
     /// let sphere = Sphere::new();
     /// let intersections = sphere.intersect(&ray);
-    /// assert_eq!(intersections.len(), 2);
-    /// assert_eq!(intersections[0].t(), 4.0);
-    /// assert_eq!(intersections[1].t(), 6.0);
+    /// assert_eq!(intersections.ts(), [4.0, 6.0]);
     /// ```
     ///
     /// A ray intersects a sphere at a tangent.
     /// ```
     /// # use truster::shape::{Shape, sphere::Sphere};
+    /// use truster::intersection::Distances;
     /// use truster::ray::Ray;
     /// use truster::tuple::Tuple;
     ///
     /// let ray = Ray::new(Tuple::point(0.0, 1.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
     /// let sphere = Sphere::new();
     /// let intersections = sphere.intersect(&ray);
-    /// assert_eq!(intersections.len(), 2);
-    /// assert_eq!(intersections[0].t(), 5.0);
-    /// assert_eq!(intersections[1].t(), 5.0);
+    /// assert_eq!(intersections.ts(), [5.0, 5.0]);
     /// ```
     ///
     /// A ray misses a sphere.
     /// ```
     /// # use truster::shape::{Shape, sphere::Sphere};
+    /// use truster::intersection::Distances;
     /// use truster::ray::Ray;
     /// use truster::tuple::Tuple;
     ///
     /// let ray = Ray::new(Tuple::point(0.0, 2.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
     /// let sphere = Sphere::new();
     /// let intersections = sphere.intersect(&ray);
-    /// assert_eq!(intersections.len(), 0);
+    /// assert!(intersections.ts().is_empty());
     /// ```
     ///
     /// A ray originates inside a sphere.
     /// ```
     /// # use truster::shape::{Shape, sphere::Sphere};
+    /// use truster::intersection::Distances;
     /// use truster::ray::Ray;
     /// use truster::tuple::Tuple;
     ///
     /// let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
     /// let sphere = Sphere::new();
     /// let intersections = sphere.intersect(&ray);
-    /// assert_eq!(intersections.len(), 2);
-    /// assert_eq!(intersections[0].t(), -1.0);
-    /// assert_eq!(intersections[1].t(), 1.0);
+    /// assert_eq!(intersections.ts(), [-1.0, 1.0]);
     /// ```
     ///
     /// A ray is behind a sphere.
     /// ```
     /// # use truster::shape::{Shape, sphere::Sphere};
+    /// use truster::intersection::Distances;
     /// use truster::ray::Ray;
     /// use truster::tuple::Tuple;
     ///
     /// let ray = Ray::new(Tuple::point(0.0, 0.0, 5.0), Tuple::vector(0.0, 0.0, 1.0));
     /// let sphere = Sphere::new();
     /// let intersections = sphere.intersect(&ray);
-    /// assert_eq!(intersections.len(), 2);
-    /// assert_eq!(intersections[0].t(), -6.0);
-    /// assert_eq!(intersections[1].t(), -4.0);
+    /// assert_eq!(intersections.ts(), [-6.0, -4.0]);
     /// ```
-    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection> {
-        let oc = ray.origin() - Tuple::point(0.0, 0.0, 0.0);
+    ///
+    /// A ray without a direction misses.
+    /// ```
+    /// # use truster::shape::{Shape, sphere::Sphere};
+    /// use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let ray = Ray::new(Tuple::point(0.0, 0.0, 0.5), Tuple::vector(0.0, 0.0, 0.0));
+    /// let sphere = Sphere::new();
+    /// assert!(sphere.intersect(&ray).is_empty());
+    /// ```
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        let oc = ray.origin() - Tuple::ORIGIN;
 
         let a = ray.direction().norm_squared();
         let b = ray.direction().dot(oc);
@@ -107,7 +117,8 @@ impl Shape for Sphere {
 
         let d = b * b - a * c;
 
-        if d < 0.0 {
+        // Directions which are zero, or too short or long to square, would give NaN distances.
+        if d.is_nan() || d < 0.0 || !a.is_normal() {
             return Vec::new();
         }
 
@@ -116,8 +127,8 @@ impl Shape for Sphere {
         let t2 = (-b + sqrtd) / a;
 
         vec![
-            Intersection::new(t1, Rc::new(self.clone())),
-            Intersection::new(t2, Rc::new(self.clone())),
+            Intersection::new(t1, Rc::clone(this)),
+            Intersection::new(t2, Rc::clone(this)),
         ]
     }
 
@@ -173,7 +184,11 @@ impl Shape for Sphere {
     /// ));
     /// ```
     fn local_normal_at(&self, point: Tuple) -> Tuple {
-        (point - Tuple::point(0.0, 0.0, 0.0)).normalized()
+        (point - Tuple::ORIGIN).normalized()
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
     }
 
     /// Sets `self`'s transform to be `transform`.
@@ -201,4 +216,22 @@ impl Shape for Sphere {
     fn set_material(&mut self, material: Material) {
         self.material = material;
     }
+
+    /// Returns the group `self` is in, if any.
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+
+    /// Sets the group `self` is in.
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Sphere)
+    }
 }
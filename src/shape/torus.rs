@@ -0,0 +1,348 @@
+//! Holds the [Torus] struct;
+
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+use super::{Shape, ShapeKind};
+
+/// A torus (donut shape) around the Y axis, centered at the origin. The major radius is the
+/// distance from the origin to the center of the tube. The minor radius is the radius of the tube.
+#[derive(Clone)]
+pub struct Torus {
+    transform: Matrix,
+    transform_inverse: Matrix,
+    material: Material,
+    parent: Option<Weak<dyn Shape>>,
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+impl Torus {
+    /// Returns a new torus with major radius 1 and minor radius 0.25.
+    /// Use [Torus::set_transform] to transform it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new torus with the given radii.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either radius is not positive.
+    pub fn with_radii(major_radius: f64, minor_radius: f64) -> Self {
+        if major_radius <= 0.0 || minor_radius <= 0.0 {
+            panic!(
+                "torus radii should be positive, major: {}, minor: {}",
+                major_radius, minor_radius
+            );
+        }
+        Self {
+            major_radius,
+            minor_radius,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the distance from the center of `self` to the center of its tube.
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    /// Returns the radius of `self`'s tube.
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+}
+
+impl Default for Torus {
+    fn default() -> Self {
+        Self {
+            transform: Matrix::default(),
+            transform_inverse: Matrix::default(),
+            material: Material::default(),
+            parent: None,
+            major_radius: 1.0,
+            minor_radius: 0.25,
+        }
+    }
+}
+
+impl Shape for Torus {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+    fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Torus)
+    }
+
+    /// Returns the gradient of the implicit torus equation
+    /// `(x² + y² + z² + R² - r²)² - 4R²(x² + z²) = 0` at `point`, normalized.
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let radii_squared =
+            self.major_radius * self.major_radius + self.minor_radius * self.minor_radius;
+        let s = point.x() * point.x() + point.y() * point.y() + point.z() * point.z();
+        Tuple::vector(
+            point.x() * (s - radii_squared),
+            point.y() * (s - radii_squared + 2.0 * self.major_radius * self.major_radius),
+            point.z() * (s - radii_squared),
+        )
+        .normalized()
+    }
+
+    fn bounds(&self) -> Bounds {
+        let outer = self.major_radius + self.minor_radius;
+        Bounds::new(
+            Tuple::point(-outer, -self.minor_radius, -outer),
+            Tuple::point(outer, self.minor_radius, outer),
+        )
+    }
+
+    /// Substitutes the ray in the implicit torus equation, which gives a quartic in `t`. All of
+    /// its real roots are intersections.
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        let origin = ray.origin() - Tuple::ORIGIN;
+        let direction = ray.direction();
+        let major_squared = self.major_radius * self.major_radius;
+        let minor_squared = self.minor_radius * self.minor_radius;
+
+        let dd = direction.norm_squared();
+        let e = origin.norm_squared() - major_squared - minor_squared;
+        let f = origin.dot(direction);
+        let four_major_squared = 4.0 * major_squared;
+
+        let coefficients = [
+            e * e - four_major_squared * (minor_squared - origin.y() * origin.y()),
+            4.0 * f * e + 2.0 * four_major_squared * origin.y() * direction.y(),
+            2.0 * dd * e + 4.0 * f * f + four_major_squared * direction.y() * direction.y(),
+            4.0 * dd * f,
+            dd * dd,
+        ];
+
+        real_roots(&coefficients)
+            .into_iter()
+            .map(|t| Intersection::new(t, Rc::clone(this)))
+            .collect()
+    }
+}
+
+/// Returns the real roots of the polynomial `coefficients[0] + coefficients[1] * t + ...`, sorted.
+/// Roots where the polynomial only touches 0 are returned twice, like a ray tangent to a sphere.
+///
+/// The roots of the derivative split the real line into pieces where the polynomial is monotonic.
+/// Each piece holds at most one root, which is found by bisection.
+fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+    let degree = match coefficients.iter().rposition(|&c| c != 0.0) {
+        Some(degree) => degree,
+        None => return Vec::new(),
+    };
+    let leading = coefficients[degree];
+    let coefficients: Vec<f64> = coefficients[..=degree]
+        .iter()
+        .map(|c| c / leading)
+        .collect();
+
+    match degree {
+        0 => return Vec::new(),
+        1 => return vec![-coefficients[0]],
+        _ => (),
+    }
+
+    let evaluate = |t: f64| coefficients.iter().rev().fold(0.0, |acc, &c| acc * t + c);
+    let tolerance = 1e-10 * coefficients.iter().fold(1.0_f64, |acc, c| acc.max(c.abs()));
+    let sign = |value: f64| {
+        if value.abs() <= tolerance {
+            0.0
+        } else {
+            value.signum()
+        }
+    };
+
+    // All roots lie within the Cauchy bound.
+    let bound = 1.0
+        + coefficients[..degree]
+            .iter()
+            .fold(0.0_f64, |acc, c| acc.max(c.abs()));
+    let derivative: Vec<f64> = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| c * i as f64)
+        .collect();
+    let mut points = vec![-bound];
+    points.extend(
+        real_roots(&derivative)
+            .into_iter()
+            .filter(|t| t.abs() < bound),
+    );
+    points.push(bound);
+    points.dedup();
+
+    let mut roots = Vec::new();
+    for (i, pair) in points.windows(2).enumerate() {
+        let (mut low, mut high) = (pair[0], pair[1]);
+        if i > 0 && sign(evaluate(low)) == 0.0 {
+            roots.push(low);
+            roots.push(low);
+        }
+        let low_sign = sign(evaluate(low));
+        if low_sign * sign(evaluate(high)) >= 0.0 {
+            continue;
+        }
+        for _ in 0..100 {
+            let mid = (low + high) / 2.0;
+            if sign(evaluate(mid)) == low_sign {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        roots.push((low + high) / 2.0);
+    }
+    roots.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.000_001,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn intersect(ray: &Ray) -> Vec<f64> {
+        let torus: Rc<dyn Shape> = Rc::new(Torus::new());
+        torus
+            .local_intersect(&torus, ray)
+            .iter()
+            .map(|i| i.t())
+            .collect()
+    }
+
+    #[test]
+    fn real_roots_quadratic() {
+        // (t - 1)(t + 2) = t² + t - 2
+        let roots = real_roots(&[-2.0, 1.0, 1.0]);
+        assert_eq!(roots.len(), 2);
+        assert_close(roots[0], -2.0);
+        assert_close(roots[1], 1.0);
+        assert!(real_roots(&[1.0, 0.0, 1.0]).is_empty());
+    }
+
+    #[test]
+    fn real_roots_quartic() {
+        // (t - 1)(t - 2)(t - 3)(t - 4)
+        let roots = real_roots(&[24.0, -50.0, 35.0, -10.0, 1.0]);
+        assert_eq!(roots.len(), 4);
+        for (root, expected) in roots.into_iter().zip(1..=4) {
+            assert_close(root, expected as f64);
+        }
+    }
+
+    #[test]
+    fn intersect_through_hole_and_tube() {
+        let ray = Ray::new(Tuple::point(-5.0, 0.0, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+        let ts = intersect(&ray);
+        assert_eq!(ts.len(), 4);
+        for (t, expected) in ts.into_iter().zip([3.75, 4.25, 5.75, 6.25]) {
+            assert_close(t, expected);
+        }
+    }
+
+    #[test]
+    fn intersect_down_the_tube() {
+        let ray = Ray::new(Tuple::point(1.0, 5.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        let ts = intersect(&ray);
+        assert_eq!(ts.len(), 2);
+        assert_close(ts[0], 4.75);
+        assert_close(ts[1], 5.25);
+    }
+
+    #[test]
+    fn intersect_down_the_hole() {
+        let ray = Ray::new(Tuple::point(0.0, 5.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        assert!(intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn intersect_miss() {
+        let ray = Ray::new(Tuple::point(-5.0, 1.0, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+        assert!(intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn intersect_tangent() {
+        let ray = Ray::new(Tuple::point(-5.0, 0.25, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+        let ts = intersect(&ray);
+        assert!(!ts.is_empty());
+        assert!(ts.iter().any(|t| (t - 4.0).abs() < 0.001));
+        assert!(ts.iter().any(|t| (t - 6.0).abs() < 0.001));
+        assert!(ts
+            .iter()
+            .all(|t| (t - 4.0).abs() < 0.001 || (t - 6.0).abs() < 0.001));
+    }
+
+    #[test]
+    fn local_normal_at() {
+        let torus = Torus::new();
+        assert_eq!(
+            torus.local_normal_at(Tuple::point(1.25, 0.0, 0.0)),
+            Tuple::vector(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            torus.local_normal_at(Tuple::point(0.75, 0.0, 0.0)),
+            Tuple::vector(-1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            torus.local_normal_at(Tuple::point(1.0, 0.25, 0.0)),
+            Tuple::vector(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            torus.local_normal_at(Tuple::point(0.0, 0.0, -0.75)),
+            Tuple::vector(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "torus radii should be positive, major: 1, minor: 0")]
+    fn with_radii_not_positive() {
+        Torus::with_radii(1.0, 0.0);
+    }
+}
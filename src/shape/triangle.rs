@@ -0,0 +1,270 @@
+//! Holds the [Triangle] struct.
+
+use std::rc::{Rc, Weak};
+
+use crate::bounds::Bounds;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::EPSILON;
+
+use super::{Shape, ShapeKind};
+
+/// A flat triangle between three points. Intersections with a triangle carry the barycentric
+/// coordinates of the hit as (u, v), see [Intersection::with_uv]. `u` is the weight of the second
+/// point, `v` the weight of the third point.
+#[derive(Clone)]
+pub struct Triangle {
+    transform: Matrix,
+    transform_inverse: Matrix,
+    material: Material,
+    parent: Option<Weak<dyn Shape>>,
+    p1: Tuple,
+    p2: Tuple,
+    p3: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+    normal: Tuple,
+}
+
+impl Triangle {
+    /// Returns a new triangle with corners `p1`, `p2` and `p3`.
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        Self {
+            transform: Matrix::default(),
+            transform_inverse: Matrix::default(),
+            material: Material::default(),
+            parent: None,
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross(e1).normalized(),
+        }
+    }
+
+    /// Returns `self`'s first corner.
+    pub fn p1(&self) -> Tuple {
+        self.p1
+    }
+
+    /// Returns `self`'s second corner.
+    pub fn p2(&self) -> Tuple {
+        self.p2
+    }
+
+    /// Returns `self`'s third corner.
+    pub fn p3(&self) -> Tuple {
+        self.p3
+    }
+
+    /// Returns the edge from `self`'s first to second corner.
+    pub fn e1(&self) -> Tuple {
+        self.e1
+    }
+
+    /// Returns the edge from `self`'s first to third corner.
+    pub fn e2(&self) -> Tuple {
+        self.e2
+    }
+
+    /// Returns `self`'s normal, which is the same everywhere on the triangle.
+    pub fn normal(&self) -> Tuple {
+        self.normal
+    }
+
+    /// Returns the barycentric coordinates (u, v) of `point`, which should lie in the plane of
+    /// `self`.
+    pub fn barycentric(&self, point: Tuple) -> (f64, f64) {
+        let w = point - self.p1;
+        let d11 = self.e1.dot(self.e1);
+        let d12 = self.e1.dot(self.e2);
+        let d22 = self.e2.dot(self.e2);
+        let dw1 = w.dot(self.e1);
+        let dw2 = w.dot(self.e2);
+        let denominator = d11 * d22 - d12 * d12;
+        let u = (d22 * dw1 - d12 * dw2) / denominator;
+        let v = (d11 * dw2 - d12 * dw1) / denominator;
+        (u, v)
+    }
+}
+
+impl Shape for Triangle {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+    fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    fn parent(&self) -> Option<Rc<dyn Shape>> {
+        self.parent.as_ref().and_then(Weak::upgrade)
+    }
+    fn set_parent(&mut self, parent: Weak<dyn Shape>) {
+        self.parent = Some(parent);
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn kind(&self) -> Option<ShapeKind> {
+        Some(ShapeKind::Triangle)
+    }
+
+    fn local_normal_at(&self, _: Tuple) -> Tuple {
+        self.normal
+    }
+
+    fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::empty();
+        bounds.add_point(self.p1);
+        bounds.add_point(self.p2);
+        bounds.add_point(self.p3);
+        bounds
+    }
+
+    /// Returns the intersection of `ray` with `self`, using the Möller-Trumbore algorithm. The
+    /// intersection holds the barycentric coordinates of the hit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::shape::{Shape, Triangle};
+    /// use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let triangle = Triangle::new(
+    ///     Tuple::point(0.0, 1.0, 0.0),
+    ///     Tuple::point(-1.0, 0.0, 0.0),
+    ///     Tuple::point(1.0, 0.0, 0.0),
+    /// );
+    /// let ray = Ray::new(Tuple::point(0.0, 0.5, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+    /// let intersections = triangle.intersect(&ray);
+    /// assert_eq!(intersections.len(), 1);
+    /// assert_eq!(intersections[0].t(), 2.0);
+    /// assert_eq!(intersections[0].u(), Some(0.25));
+    /// assert_eq!(intersections[0].v(), Some(0.25));
+    /// ```
+    fn local_intersect(&self, this: &Rc<dyn Shape>, ray: &Ray) -> Vec<Intersection> {
+        let dir_cross_e2 = ray.direction().cross(self.e2);
+        let det = self.e1.dot(dir_cross_e2);
+        if det.abs() < EPSILON {
+            return Vec::new();
+        }
+
+        let f = 1.0 / det;
+        let p1_to_origin = ray.origin() - self.p1;
+        let u = f * p1_to_origin.dot(dir_cross_e2);
+        if !(0.0..=1.0).contains(&u) {
+            return Vec::new();
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross(self.e1);
+        let v = f * ray.direction().dot(origin_cross_e1);
+        if v < 0.0 || u + v > 1.0 {
+            return Vec::new();
+        }
+
+        let t = f * self.e2.dot(origin_cross_e1);
+        vec![Intersection::with_uv(t, Rc::clone(this), u, v)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn new() {
+        let triangle = triangle();
+        assert_eq!(triangle.e1(), Tuple::vector(-1.0, -1.0, 0.0));
+        assert_eq!(triangle.e2(), Tuple::vector(1.0, -1.0, 0.0));
+        assert_eq!(triangle.normal(), Tuple::vector(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn local_normal_at() {
+        let triangle = triangle();
+        let normal = triangle.normal();
+        assert_eq!(
+            triangle.local_normal_at(Tuple::point(0.0, 0.5, 0.0)),
+            normal
+        );
+        assert_eq!(
+            triangle.local_normal_at(Tuple::point(-0.5, 0.75, 0.0)),
+            normal
+        );
+        assert_eq!(
+            triangle.local_normal_at(Tuple::point(0.5, 0.25, 0.0)),
+            normal
+        );
+    }
+
+    #[test]
+    fn intersect_parallel() {
+        let triangle: Rc<dyn Shape> = Rc::new(triangle());
+        let ray = Ray::new(Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 1.0, 0.0));
+        assert!(triangle.local_intersect(&triangle, &ray).is_empty());
+    }
+
+    #[test]
+    fn intersect_misses_edges() {
+        let triangle: Rc<dyn Shape> = Rc::new(triangle());
+        let direction = Tuple::vector(0.0, 0.0, 1.0);
+        for &(x, y) in &[(1.0, 1.0), (-1.0, 1.0), (0.0, -1.0)] {
+            let ray = Ray::new(Tuple::point(x, y, -2.0), direction);
+            assert!(triangle.local_intersect(&triangle, &ray).is_empty());
+        }
+    }
+
+    #[test]
+    fn intersect_centroid() {
+        let triangle: Rc<dyn Shape> = Rc::new(triangle());
+        let ray = Ray::new(
+            Tuple::point(0.0, 1.0 / 3.0, -2.0),
+            Tuple::vector(0.0, 0.0, 1.0),
+        );
+        let intersections = triangle.local_intersect(&triangle, &ray);
+        assert_eq!(intersections.len(), 1);
+        assert!((intersections[0].t() - 2.0).abs() < 1e-6);
+        assert!((intersections[0].u().unwrap() - 1.0 / 3.0).abs() < 1e-6);
+        assert!((intersections[0].v().unwrap() - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn barycentric() {
+        let triangle = triangle();
+        assert_eq!(triangle.barycentric(triangle.p1()), (0.0, 0.0));
+        assert_eq!(triangle.barycentric(triangle.p2()), (1.0, 0.0));
+        assert_eq!(triangle.barycentric(triangle.p3()), (0.0, 1.0));
+        assert_eq!(
+            triangle.barycentric(Tuple::point(0.0, 0.5, 0.0)),
+            (0.25, 0.25)
+        );
+    }
+}
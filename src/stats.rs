@@ -0,0 +1,86 @@
+//! Counters for the work done while rendering.
+//!
+//! A [World](crate::world::World) counts the rays it traces and the intersection tests it does.
+//! [Camera::render_with_stats](crate::camera::Camera::render_with_stats) returns them as
+//! [RenderStats] together with the rendered image, which helps to see where time goes, and how
+//! much a change to the scene saves.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of rays traced and intersection tests done during a render.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct RenderStats {
+    /// Rays sent from the camera, one per sample per pixel.
+    pub primary_rays: u64,
+    /// Rays sent towards lights to find shadows, including those for soft shadows and ambient
+    /// occlusion.
+    pub shadow_rays: u64,
+    /// Rays sent off reflective surfaces.
+    pub reflection_rays: u64,
+    /// Rays sent through transparent surfaces.
+    pub refraction_rays: u64,
+    /// Rays tested against a shape in the world. Most rays are tested against every shape in the
+    /// world, so this grows with both. Shadow rays stop at the first shape which blocks them.
+    /// Tests against the children of a group are not counted.
+    pub intersection_tests: u64,
+}
+
+impl RenderStats {
+    /// Returns the total number of rays traced.
+    pub fn rays(&self) -> u64 {
+        self.primary_rays + self.shadow_rays + self.reflection_rays + self.refraction_rays
+    }
+}
+
+/// The counters behind [RenderStats]. They are atomic so they can be increased through a shared
+/// reference.
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    primary_rays: AtomicU64,
+    shadow_rays: AtomicU64,
+    reflection_rays: AtomicU64,
+    refraction_rays: AtomicU64,
+    intersection_tests: AtomicU64,
+}
+
+impl Counters {
+    pub(crate) fn add_primary_rays(&self, n: u64) {
+        self.primary_rays.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_shadow_ray(&self) {
+        self.shadow_rays.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_reflection_ray(&self) {
+        self.reflection_rays.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_refraction_ray(&self) {
+        self.refraction_rays.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_intersection_tests(&self, n: u64) {
+        self.intersection_tests.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Returns the current counts.
+    pub(crate) fn snapshot(&self) -> RenderStats {
+        RenderStats {
+            primary_rays: self.primary_rays.load(Ordering::Relaxed),
+            shadow_rays: self.shadow_rays.load(Ordering::Relaxed),
+            reflection_rays: self.reflection_rays.load(Ordering::Relaxed),
+            refraction_rays: self.refraction_rays.load(Ordering::Relaxed),
+            intersection_tests: self.intersection_tests.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets all counts back to 0.
+    pub(crate) fn reset(&self) {
+        self.primary_rays.store(0, Ordering::Relaxed);
+        self.shadow_rays.store(0, Ordering::Relaxed);
+        self.reflection_rays.store(0, Ordering::Relaxed);
+        self.refraction_rays.store(0, Ordering::Relaxed);
+        self.intersection_tests.store(0, Ordering::Relaxed);
+    }
+}
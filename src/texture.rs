@@ -1,14 +1,22 @@
 //! Holds the [Texture] trait, as well as some common textures which implement it.
 
+use std::any;
 use std::rc::Rc;
 
 use crate::color::Color;
+use crate::intersection::HitRecord;
 use crate::matrix::Matrix;
 use crate::shape::Shape;
 use crate::tuple::Tuple;
 
+pub mod blend;
+pub mod checker2d;
+pub mod cube_map;
+pub mod multi_stripe;
 pub mod solid_color;
 pub mod stripe;
+pub mod uv;
+pub mod uv_map;
 
 /// A basic texture implementation. There is no UV mapping or anything like that. The method
 /// [Texture::color_at] should just map a point in 3D space to a color. Textures can be
@@ -17,9 +25,23 @@ pub mod stripe;
 /// [Texture::transform] should return the texture transform matrix,
 /// [Texture::transform_inverse] should return it's inverse. [Texture::set_transform] should set
 /// the texture transform to be `transform`.
+///
+/// [Texture::name] returns the name of the texture's type, like `"Stripe"`.
+///
+/// [Texture::kind] returns which of the textures of this crate the texture is. Unlike the name, it
+/// is not meant to be overwritten, so it can identify textures when storing them. The default is
+/// `None`, for textures defined elsewhere.
+///
+/// Flat textures can also be looked up by (u, v) coordinates with [Texture::color_at_uv]. See the
+/// [uv] module for functions which map points on shapes to (u, v).
 pub trait Texture {
+    fn name(&self) -> &'static str {
+        let name = any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     fn color_at_shape(&self, point: Tuple, shape: Rc<dyn Shape>) -> Color {
-        let point = shape.transform_inverse() * point;
+        let point = shape.world_to_object(point);
         let point = self.transform_inverse() * point;
         self.color_at(point)
     }
@@ -28,10 +50,48 @@ pub trait Texture {
         self.color_at(point)
     }
     fn color_at(&self, point: Tuple) -> Color;
+    /// Returns the color at (`u`, `v`). By default, this looks up the point (u, 0, v), so textures
+    /// in the x-z plane, like [checker2d::Checker2d], work with uv coordinates as is.
+    fn color_at_uv(&self, u: f64, v: f64) -> Color {
+        self.color_at_texture(Tuple::point(u, 0.0, v))
+    }
+    /// Returns the color at the hit `rec`. If the hit has (u, v) coordinates, like hits on
+    /// triangles, they are looked up with [Texture::color_at_uv]. Otherwise, this is the same as
+    /// [Texture::color_at_shape].
+    fn color_at_hit(&self, rec: &HitRecord) -> Color {
+        match (rec.u(), rec.v()) {
+            (Some(u), Some(v)) => self.color_at_uv(u, v),
+            _ => self.color_at_shape(rec.point(), rec.shape()),
+        }
+    }
 
     fn transform(&self) -> &Matrix;
     fn transform_inverse(&self) -> &Matrix;
     fn set_transform(&mut self, transform: Matrix);
+
+    fn kind(&self) -> Option<TextureKind> {
+        None
+    }
+}
+
+/// The textures of this crate, as returned by [Texture::kind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TextureKind {
+    /// A [Blend](blend::Blend).
+    Blend,
+    /// A [Checker2d](checker2d::Checker2d).
+    Checker2d,
+    /// A [CubeMap](cube_map::CubeMap).
+    CubeMap,
+    /// A [MultiStripe](multi_stripe::MultiStripe).
+    MultiStripe,
+    /// A [SolidColor](solid_color::SolidColor).
+    SolidColor,
+    /// A [Stripe](stripe::Stripe).
+    Stripe,
+    /// A [UvMap](uv_map::UvMap).
+    UvMap,
 }
 
 #[cfg(test)]
@@ -99,4 +159,44 @@ mod tests {
         let color = texture.color_at_shape(Tuple::point(2.5, 3.0, 3.5), Rc::new(shape));
         assert_eq!(color, Color::new(0.75, 0.5, 0.25));
     }
+
+    #[test]
+    fn color_at_uv_default() {
+        let mut texture = MockTexture::new();
+        texture.set_transform(Matrix::scaling(0.5, 0.5, 0.5));
+        assert_eq!(texture.color_at_uv(0.25, 0.5), Color::new(0.5, 0.0, 1.0));
+    }
+
+    #[test]
+    fn color_at_hit_uses_uv() {
+        use crate::intersection::Intersection;
+        use crate::ray::Ray;
+        use crate::shape::Triangle;
+
+        let triangle: Rc<dyn Shape> = Rc::new(Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+        ));
+        let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+        let ray = Ray::new(Tuple::point(0.0, 0.5, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+        let texture = MockTexture::new();
+
+        let rec = HitRecord::new(&Intersection::with_uv(2.0, triangle, 0.25, 0.5), &ray);
+        assert_eq!(texture.color_at_hit(&rec), Color::new(0.25, 0.0, 0.5));
+
+        let rec = HitRecord::new(&Intersection::new(2.0, sphere), &ray);
+        assert_eq!(texture.color_at_hit(&rec), Color::new(0.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(MockTexture::new().kind(), None);
+        assert_eq!(
+            solid_color::SolidColor::new(Color::WHITE).kind(),
+            Some(TextureKind::SolidColor)
+        );
+        let texture: Rc<dyn Texture> = Rc::new(stripe::Stripe::colors(Color::WHITE, Color::BLACK));
+        assert_eq!(texture.kind(), Some(TextureKind::Stripe));
+    }
 }
@@ -0,0 +1,117 @@
+//! Holds the [Blend] struct, which implements the [Texture].
+
+use std::rc::Rc;
+
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::tuple::Tuple;
+
+use super::{Texture, TextureKind};
+
+/// Combines 2 other textures by mixing their colors at every point. A ratio of 0 gives the first
+/// texture, a ratio of 1 gives the second one.
+pub struct Blend {
+    texture1: Rc<dyn Texture>,
+    texture2: Rc<dyn Texture>,
+    ratio: f64,
+    transform: Matrix,
+    transform_inverse: Matrix,
+}
+
+impl Blend {
+    /// Returns a new texture which mixes `texture1` and `texture2` as
+    /// `texture1 * (1 - ratio) + texture2 * ratio`.
+    pub fn new(texture1: Rc<dyn Texture>, texture2: Rc<dyn Texture>, ratio: f64) -> Self {
+        Self {
+            texture1,
+            texture2,
+            ratio,
+            transform: Matrix::eye(),
+            transform_inverse: Matrix::eye(),
+        }
+    }
+
+    /// Returns `self`'s ratio.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+impl Texture for Blend {
+    fn color_at(&self, point: Tuple) -> Color {
+        self.texture1.color_at_texture(point) * (1.0 - self.ratio)
+            + self.texture2.color_at_texture(point) * self.ratio
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::Blend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::{solid_color::SolidColor, stripe::Stripe};
+
+    fn blend(ratio: f64) -> Blend {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        Blend::new(
+            Rc::new(Stripe::colors(white, black)),
+            Rc::new(SolidColor::new(Color::new(0.2, 0.4, 0.6))),
+            ratio,
+        )
+    }
+
+    #[test]
+    fn color_at_ratio_0() {
+        let texture = blend(0.0);
+        assert_eq!(
+            texture.color_at(Tuple::point(0.5, 0.0, 0.0)),
+            Color::new(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            texture.color_at(Tuple::point(1.5, 0.0, 0.0)),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn color_at_ratio_1() {
+        let texture = blend(1.0);
+        assert_eq!(
+            texture.color_at(Tuple::point(0.5, 0.0, 0.0)),
+            Color::new(0.2, 0.4, 0.6)
+        );
+        assert_eq!(
+            texture.color_at(Tuple::point(1.5, 0.0, 0.0)),
+            Color::new(0.2, 0.4, 0.6)
+        );
+    }
+
+    #[test]
+    fn color_at_ratio_half() {
+        let texture = blend(0.5);
+        assert_eq!(
+            texture.color_at(Tuple::point(0.5, 0.0, 0.0)),
+            Color::new(0.6, 0.7, 0.8)
+        );
+        assert_eq!(
+            texture.color_at(Tuple::point(1.5, 0.0, 0.0)),
+            Color::new(0.1, 0.2, 0.3)
+        );
+    }
+}
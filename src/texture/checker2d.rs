@@ -0,0 +1,122 @@
+//! Holds the [Checker2d] struct, which implements the [Texture].
+
+use std::rc::Rc;
+
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::tuple::Tuple;
+
+use super::{solid_color::SolidColor, Texture, TextureKind};
+
+/// Combines 2 other textures and lies them out in a flat checkerboard in the x-z plane. The y
+/// coordinate is ignored, so a plane shows a clean grid of unit squares, even when points are
+/// slightly above or below it.
+pub struct Checker2d {
+    texture1: Rc<dyn Texture>,
+    texture2: Rc<dyn Texture>,
+    transform: Matrix,
+    transform_inverse: Matrix,
+}
+
+impl Checker2d {
+    /// Returns a new checkerboard of `texture1` and `texture2`. The square at the origin uses
+    /// `texture1`.
+    pub fn new(texture1: Rc<dyn Texture>, texture2: Rc<dyn Texture>) -> Self {
+        Self {
+            texture1,
+            texture2,
+            transform: Matrix::eye(),
+            transform_inverse: Matrix::eye(),
+        }
+    }
+
+    /// Returns a new checkerboard of two solid colors.
+    pub fn colors(color1: Color, color2: Color) -> Self {
+        Self::new(
+            Rc::new(SolidColor::new(color1)),
+            Rc::new(SolidColor::new(color2)),
+        )
+    }
+}
+
+impl Texture for Checker2d {
+    fn color_at(&self, point: Tuple) -> Color {
+        if (point.x().floor() + point.z().floor()) as i32 % 2 == 0 {
+            self.texture1.color_at_texture(point)
+        } else {
+            self.texture2.color_at_texture(point)
+        }
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::Checker2d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_at_alternating_x() {
+        let texture = Checker2d::colors(Color::WHITE, Color::BLACK);
+
+        assert_eq!(texture.color_at(Tuple::point(0.0, 0.0, 0.0)), Color::WHITE);
+        assert_eq!(texture.color_at(Tuple::point(0.99, 0.0, 0.0)), Color::WHITE);
+        assert_eq!(texture.color_at(Tuple::point(1.01, 0.0, 0.0)), Color::BLACK);
+        assert_eq!(texture.color_at(Tuple::point(-0.5, 0.0, 0.0)), Color::BLACK);
+    }
+
+    #[test]
+    fn color_at_alternating_z() {
+        let texture = Checker2d::colors(Color::WHITE, Color::BLACK);
+
+        assert_eq!(texture.color_at(Tuple::point(0.0, 0.0, 0.99)), Color::WHITE);
+        assert_eq!(texture.color_at(Tuple::point(0.0, 0.0, 1.01)), Color::BLACK);
+        assert_eq!(texture.color_at(Tuple::point(1.5, 0.0, 1.5)), Color::WHITE);
+        assert_eq!(
+            texture.color_at(Tuple::point(-0.5, 0.0, -0.5)),
+            Color::WHITE
+        );
+    }
+
+    #[test]
+    fn color_at_ignores_y() {
+        let texture = Checker2d::colors(Color::WHITE, Color::BLACK);
+
+        for &(x, z) in &[(0.5, 0.5), (1.5, 0.5), (-0.5, 2.5), (-3.2, -1.7)] {
+            assert_eq!(
+                texture.color_at(Tuple::point(x, 0.0, z)),
+                texture.color_at(Tuple::point(x, 0.4, z)),
+            );
+            assert_eq!(
+                texture.color_at(Tuple::point(x, 0.0, z)),
+                texture.color_at(Tuple::point(x, -0.000_001, z)),
+            );
+        }
+    }
+
+    #[test]
+    fn color_at_uv() {
+        let mut texture = Checker2d::colors(Color::WHITE, Color::BLACK);
+        texture.set_transform(Matrix::scaling(0.5, 1.0, 0.5));
+
+        assert_eq!(texture.color_at_uv(0.0, 0.0), Color::WHITE);
+        assert_eq!(texture.color_at_uv(0.6, 0.0), Color::BLACK);
+        assert_eq!(texture.color_at_uv(0.0, 0.6), Color::BLACK);
+        assert_eq!(texture.color_at_uv(0.6, 0.6), Color::WHITE);
+    }
+}
@@ -0,0 +1,158 @@
+//! Holds the [CubeMap] struct, which implements the [Texture].
+
+use std::rc::Rc;
+
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::tuple::Tuple;
+
+use super::uv::{cube_uv, Face};
+use super::{Texture, TextureKind};
+
+/// Wraps six flat textures around a cube, one on each face. Points are mapped to a face and
+/// (u, v) on it with [cube_uv], and looked up in that face's texture with [Texture::color_at_uv].
+/// Use it for dice, or for skyboxes seen from inside a cube.
+///
+/// The mapping works in object space, after `self`'s own transform, on the cube from -1 to 1 on
+/// each axis.
+///
+/// # Examples
+///
+/// ```
+/// # use truster::texture::cube_map::CubeMap;
+/// use std::rc::Rc;
+/// use truster::color::Color;
+/// use truster::texture::{solid_color::SolidColor, Texture};
+/// use truster::tuple::Tuple;
+///
+/// let solid = |color| -> Rc<dyn Texture> { Rc::new(SolidColor::new(color)) };
+/// let texture = CubeMap::new([
+///     solid(Color::RED),
+///     solid(Color::GREEN),
+///     solid(Color::BLUE),
+///     solid(Color::WHITE),
+///     solid(Color::BLACK),
+///     solid(Color::gray(0.5)),
+/// ]);
+/// assert_eq!(texture.color_at(Tuple::point(0.2, 0.3, 1.0)), Color::RED);
+/// assert_eq!(texture.color_at(Tuple::point(0.2, -1.0, 0.3)), Color::gray(0.5));
+/// ```
+pub struct CubeMap {
+    faces: [Rc<dyn Texture>; 6],
+    transform: Matrix,
+    transform_inverse: Matrix,
+}
+
+impl CubeMap {
+    /// Returns a new texture with a texture for each face, in the order of [Face::ALL]: front,
+    /// back, left, right, up and down.
+    pub fn new(faces: [Rc<dyn Texture>; 6]) -> Self {
+        Self {
+            faces,
+            transform: Matrix::eye(),
+            transform_inverse: Matrix::eye(),
+        }
+    }
+
+    /// Returns the texture on `face`.
+    pub fn face(&self, face: Face) -> Rc<dyn Texture> {
+        Rc::clone(&self.faces[face as usize])
+    }
+}
+
+impl Texture for CubeMap {
+    fn color_at(&self, point: Tuple) -> Color {
+        let (face, u, v) = cube_uv(point);
+        self.faces[face as usize].color_at_uv(u, v)
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::CubeMap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::{checker2d::Checker2d, solid_color::SolidColor};
+
+    fn colors() -> [Color; 6] {
+        [
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::WHITE,
+            Color::BLACK,
+            Color::new(1.0, 1.0, 0.0),
+        ]
+    }
+
+    fn solid_cube() -> CubeMap {
+        let colors = colors();
+        let solid = |i: usize| -> Rc<dyn Texture> { Rc::new(SolidColor::new(colors[i])) };
+        CubeMap::new([solid(0), solid(1), solid(2), solid(3), solid(4), solid(5)])
+    }
+
+    #[test]
+    fn face_centers() {
+        let texture = solid_cube();
+        let centers = [
+            Tuple::point(0.0, 0.0, 1.0),
+            Tuple::point(0.0, 0.0, -1.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(0.0, -1.0, 0.0),
+        ];
+        for (i, &face) in Face::ALL.iter().enumerate() {
+            assert_eq!(texture.color_at(centers[i]), colors()[i], "{:?}", face);
+            assert_eq!(
+                texture.face(face).color_at(Tuple::ORIGIN),
+                colors()[i],
+                "{:?}",
+                face
+            );
+        }
+    }
+
+    #[test]
+    fn uv_within_face() {
+        let mut checker = Checker2d::colors(Color::WHITE, Color::BLACK);
+        checker.set_transform(Matrix::scaling(0.5, 1.0, 0.5));
+        let checker: Rc<dyn Texture> = Rc::new(checker);
+        let black: Rc<dyn Texture> = Rc::new(SolidColor::new(Color::BLACK));
+        let texture = CubeMap::new([
+            checker,
+            Rc::clone(&black),
+            Rc::clone(&black),
+            Rc::clone(&black),
+            Rc::clone(&black),
+            black,
+        ]);
+
+        // The front face is split into four quadrants.
+        assert_eq!(
+            texture.color_at(Tuple::point(-0.5, -0.5, 1.0)),
+            Color::WHITE
+        );
+        assert_eq!(texture.color_at(Tuple::point(0.5, -0.5, 1.0)), Color::BLACK);
+        assert_eq!(texture.color_at(Tuple::point(0.5, 0.5, 1.0)), Color::WHITE);
+        assert_eq!(
+            texture.color_at(Tuple::point(-0.5, 0.5, -1.0)),
+            Color::BLACK
+        );
+    }
+}
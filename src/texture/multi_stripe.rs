@@ -0,0 +1,120 @@
+//! Holds the [MultiStripe] struct, which implements the [Texture].
+
+use std::rc::Rc;
+
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::tuple::Tuple;
+
+use super::{solid_color::SolidColor, Texture, TextureKind};
+
+/// Combines any number of other textures and lies them out in stripes of width 1, cycling through
+/// them in order. The stripes are perpendicular to the x axis. With 2 textures this is the same as
+/// [Stripe](super::stripe::Stripe).
+pub struct MultiStripe {
+    textures: Vec<Rc<dyn Texture>>,
+    transform: Matrix,
+    transform_inverse: Matrix,
+}
+
+impl MultiStripe {
+    /// Returns a new texture with a stripe for each of `textures`. The stripe starting at x = 0
+    /// uses the first texture.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `textures` is empty.
+    pub fn new(textures: Vec<Rc<dyn Texture>>) -> Self {
+        if textures.is_empty() {
+            panic!("MultiStripe needs at least one texture");
+        }
+        Self {
+            textures,
+            transform: Matrix::eye(),
+            transform_inverse: Matrix::eye(),
+        }
+    }
+
+    /// Returns a new texture with a solid stripe for each of `colors`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is empty.
+    pub fn colors(colors: &[Color]) -> Self {
+        Self::new(
+            colors
+                .iter()
+                .map(|&color| Rc::new(SolidColor::new(color)) as Rc<dyn Texture>)
+                .collect(),
+        )
+    }
+}
+
+impl Texture for MultiStripe {
+    fn color_at(&self, point: Tuple) -> Color {
+        let index = point.x().floor().rem_euclid(self.textures.len() as f64) as usize;
+        self.textures[index].color_at_texture(point)
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::MultiStripe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_at_three_colors() {
+        let texture = MultiStripe::colors(&[Color::RED, Color::GREEN, Color::BLUE]);
+
+        assert_eq!(texture.color_at(Tuple::point(0.0, 0.0, 0.0)), Color::RED);
+        assert_eq!(texture.color_at(Tuple::point(0.9, 0.0, 0.0)), Color::RED);
+        assert_eq!(texture.color_at(Tuple::point(1.0, 0.0, 0.0)), Color::GREEN);
+        assert_eq!(texture.color_at(Tuple::point(2.5, 0.0, 0.0)), Color::BLUE);
+        assert_eq!(texture.color_at(Tuple::point(3.0, 0.0, 0.0)), Color::RED);
+        assert_eq!(texture.color_at(Tuple::point(4.2, 1.0, -3.0)), Color::GREEN);
+    }
+
+    #[test]
+    fn color_at_three_colors_negative_x() {
+        let texture = MultiStripe::colors(&[Color::RED, Color::GREEN, Color::BLUE]);
+
+        assert_eq!(texture.color_at(Tuple::point(-0.1, 0.0, 0.0)), Color::BLUE);
+        assert_eq!(texture.color_at(Tuple::point(-1.0, 0.0, 0.0)), Color::BLUE);
+        assert_eq!(texture.color_at(Tuple::point(-1.1, 0.0, 0.0)), Color::GREEN);
+        assert_eq!(texture.color_at(Tuple::point(-2.5, 0.0, 0.0)), Color::RED);
+        assert_eq!(texture.color_at(Tuple::point(-3.5, 0.0, 0.0)), Color::BLUE);
+    }
+
+    #[test]
+    fn color_at_with_transform() {
+        let mut texture = MultiStripe::colors(&[Color::RED, Color::GREEN, Color::BLUE]);
+        texture.set_transform(Matrix::scaling(0.5, 1.0, 1.0));
+
+        assert_eq!(
+            texture.color_at_texture(Tuple::point(1.1, 0.0, 0.0)),
+            Color::BLUE
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "MultiStripe needs at least one texture")]
+    fn new_empty() {
+        MultiStripe::new(Vec::new());
+    }
+}
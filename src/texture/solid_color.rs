@@ -4,7 +4,7 @@ use crate::color::Color;
 use crate::matrix::Matrix;
 use crate::tuple::Tuple;
 
-use super::Texture;
+use super::{Texture, TextureKind};
 
 /// Represents a solid color. The simplest texture which is just a single color.
 #[derive(Default)]
@@ -37,6 +37,10 @@ impl Texture for SolidColor {
     fn color_at(&self, _: Tuple) -> Color {
         self.color
     }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::SolidColor)
+    }
 }
 
 #[cfg(test)]
@@ -6,7 +6,7 @@ use crate::color::Color;
 use crate::matrix::Matrix;
 use crate::tuple::Tuple;
 
-use super::{solid_color::SolidColor, Texture};
+use super::{solid_color::SolidColor, Texture, TextureKind};
 
 /// Combines 2 other textures and lies them out in stripes. The stripes are perpendicular to the
 /// x axis.
@@ -58,6 +58,10 @@ impl Texture for Stripe {
         self.transform_inverse = transform.inverse();
         self.transform = transform;
     }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::Stripe)
+    }
 }
 
 #[cfg(test)]
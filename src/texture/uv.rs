@@ -0,0 +1,281 @@
+//! Functions to map points in 3D space to 2D (u, v) coordinates. They can be used with
+//! [Texture::color_at_uv](super::Texture::color_at_uv) to wrap flat textures around shapes.
+//! All functions work in object space and return `u` and `v` in the range [0, 1]. Both ends are
+//! included: seams and poles can map to exactly 1, for example `v` at the north pole of
+//! [spherical_uv], so textures should handle 1 as well as 0.
+//!
+//! # Examples
+//!
+//! ```
+//! # use truster::texture::uv::planar_uv;
+//! # use truster::tuple::Tuple;
+//! assert_eq!(planar_uv(Tuple::point(1.25, 0.0, 0.5)), (0.25, 0.5));
+//! ```
+
+use std::f64::consts::PI;
+
+use crate::tuple::Tuple;
+
+/// Chooses one of the functions in this module, to be used by [UvMap](super::uv_map::UvMap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UvMapping {
+    /// Uses [spherical_uv].
+    Spherical,
+    /// Uses [planar_uv].
+    Planar,
+    /// Uses [cylindrical_uv].
+    Cylindrical,
+    /// Uses [cube_uv], with the same (u, v) on every face. Use
+    /// [CubeMap](super::cube_map::CubeMap) for a different texture on each face.
+    Cubic,
+}
+
+impl UvMapping {
+    /// Maps `point` to (u, v) with the function `self` stands for.
+    pub fn uv(self, point: Tuple) -> (f64, f64) {
+        match self {
+            Self::Spherical => spherical_uv(point),
+            Self::Planar => planar_uv(point),
+            Self::Cylindrical => cylindrical_uv(point),
+            Self::Cubic => {
+                let (_, u, v) = cube_uv(point);
+                (u, v)
+            }
+        }
+    }
+}
+
+/// Maps `point` on a sphere centered at the origin to (u, v). `u` goes around the y axis, `v` goes
+/// from the south pole (0) to the north pole (1).
+pub fn spherical_uv(point: Tuple) -> (f64, f64) {
+    let theta = point.x().atan2(point.z());
+    let radius = Tuple::vector(point.x(), point.y(), point.z()).norm();
+    let phi = (point.y() / radius).acos();
+    let raw_u = theta / (2.0 * PI);
+    let u = 1.0 - (raw_u + 0.5);
+    let v = 1.0 - phi / PI;
+    (u, v)
+}
+
+/// Maps `point` on the x-z plane to (u, v). The texture repeats every unit, so `u` is the
+/// fractional part of x and `v` is the fractional part of z.
+pub fn planar_uv(point: Tuple) -> (f64, f64) {
+    (point.x().rem_euclid(1.0), point.z().rem_euclid(1.0))
+}
+
+/// Maps `point` on a cylinder around the y axis to (u, v). `u` goes around the y axis, `v` is the
+/// fractional part of the height, so the texture repeats every unit along the cylinder.
+pub fn cylindrical_uv(point: Tuple) -> (f64, f64) {
+    let theta = point.x().atan2(point.z());
+    let raw_u = theta / (2.0 * PI);
+    let u = 1.0 - (raw_u + 0.5);
+    let v = point.y().rem_euclid(1.0);
+    (u, v)
+}
+
+/// A face of the cube from -1 to 1 on each axis, as returned by [cube_uv].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    /// The face at z = 1.
+    Front,
+    /// The face at z = -1.
+    Back,
+    /// The face at x = -1.
+    Left,
+    /// The face at x = 1.
+    Right,
+    /// The face at y = 1.
+    Up,
+    /// The face at y = -1.
+    Down,
+}
+
+impl Face {
+    /// All faces, in the order of their declaration.
+    pub const ALL: [Self; 6] = [
+        Self::Front,
+        Self::Back,
+        Self::Left,
+        Self::Right,
+        Self::Up,
+        Self::Down,
+    ];
+}
+
+/// Maps `point` on the cube from -1 to 1 on each axis to the face it is on and (u, v) on that
+/// face. The face is the one of the coordinate furthest from 0. Each face is unwrapped as seen
+/// from outside the cube, with `v` going up, or away from the front for the top and bottom faces.
+///
+/// # Examples
+///
+/// ```
+/// # use truster::texture::uv::{cube_uv, Face};
+/// # use truster::tuple::Tuple;
+/// assert_eq!(cube_uv(Tuple::point(-0.5, 0.5, 1.0)), (Face::Front, 0.25, 0.75));
+/// assert_eq!(cube_uv(Tuple::point(1.0, 0.0, 0.5)), (Face::Right, 0.25, 0.5));
+/// ```
+pub fn cube_uv(point: Tuple) -> (Face, f64, f64) {
+    let (x, y, z) = (point.x(), point.y(), point.z());
+    let coord = x.abs().max(y.abs()).max(z.abs());
+    let side = |a: f64| (a + 1.0).rem_euclid(2.0) / 2.0;
+    if coord == x {
+        (Face::Right, side(-z), side(y))
+    } else if coord == -x {
+        (Face::Left, side(z), side(y))
+    } else if coord == y {
+        (Face::Up, side(x), side(-z))
+    } else if coord == -y {
+        (Face::Down, side(x), side(z))
+    } else if coord == z {
+        (Face::Front, side(x), side(y))
+    } else {
+        (Face::Back, side(-x), side(y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_uv_eq(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 0.0001 && (actual.1 - expected.1).abs() < 0.0001,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn spherical() {
+        let cases = [
+            (Tuple::point(0.0, 0.0, -1.0), (0.0, 0.5)),
+            (Tuple::point(1.0, 0.0, 0.0), (0.25, 0.5)),
+            (Tuple::point(0.0, 0.0, 1.0), (0.5, 0.5)),
+            (Tuple::point(-1.0, 0.0, 0.0), (0.75, 0.5)),
+            (Tuple::point(0.0, 1.0, 0.0), (0.5, 1.0)),
+            (Tuple::point(0.0, -1.0, 0.0), (0.5, 0.0)),
+            (Tuple::point(0.70711, 0.70711, 0.0), (0.25, 0.75)),
+        ];
+        for (point, uv) in cases {
+            assert_uv_eq(spherical_uv(point), uv);
+        }
+    }
+
+    #[test]
+    fn planar() {
+        let cases = [
+            (Tuple::point(0.25, 0.0, 0.5), (0.25, 0.5)),
+            (Tuple::point(0.25, 0.0, -0.25), (0.25, 0.75)),
+            (Tuple::point(0.25, 0.5, -0.25), (0.25, 0.75)),
+            (Tuple::point(1.25, 0.0, 0.5), (0.25, 0.5)),
+            (Tuple::point(0.25, 0.0, -1.75), (0.25, 0.25)),
+            (Tuple::point(1.0, 0.0, -1.0), (0.0, 0.0)),
+            (Tuple::point(0.0, 0.0, 0.0), (0.0, 0.0)),
+        ];
+        for (point, uv) in cases {
+            assert_uv_eq(planar_uv(point), uv);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn cylindrical() {
+        let cases = [
+            (Tuple::point(0.0, 0.0, -1.0), (0.0, 0.0)),
+            (Tuple::point(0.0, 0.5, -1.0), (0.0, 0.5)),
+            (Tuple::point(0.0, 1.0, -1.0), (0.0, 0.0)),
+            (Tuple::point(0.70711, 0.5, -0.70711), (0.125, 0.5)),
+            (Tuple::point(1.0, 0.5, 0.0), (0.25, 0.5)),
+            (Tuple::point(0.70711, 0.5, 0.70711), (0.375, 0.5)),
+            (Tuple::point(0.0, -0.25, 1.0), (0.5, 0.75)),
+            (Tuple::point(-0.70711, 0.5, 0.70711), (0.625, 0.5)),
+            (Tuple::point(-1.0, 1.25, 0.0), (0.75, 0.25)),
+            (Tuple::point(-0.70711, 0.5, -0.70711), (0.875, 0.5)),
+        ];
+        for (point, uv) in cases {
+            assert_uv_eq(cylindrical_uv(point), uv);
+        }
+    }
+
+    #[test]
+    fn cube_face_centers() {
+        let cases = [
+            (Tuple::point(0.0, 0.0, 1.0), Face::Front),
+            (Tuple::point(0.0, 0.0, -1.0), Face::Back),
+            (Tuple::point(-1.0, 0.0, 0.0), Face::Left),
+            (Tuple::point(1.0, 0.0, 0.0), Face::Right),
+            (Tuple::point(0.0, 1.0, 0.0), Face::Up),
+            (Tuple::point(0.0, -1.0, 0.0), Face::Down),
+        ];
+        for (point, face) in cases {
+            assert_eq!(cube_uv(point), (face, 0.5, 0.5), "{}", point);
+        }
+    }
+
+    #[test]
+    fn cube_faces() {
+        let cases = [
+            (Tuple::point(-1.0, 0.5, -0.25), Face::Left),
+            (Tuple::point(1.1, -0.75, 0.8), Face::Right),
+            (Tuple::point(0.1, 0.6, 0.9), Face::Front),
+            (Tuple::point(-0.7, 0.0, -2.0), Face::Back),
+            (Tuple::point(0.5, 1.0, 0.9), Face::Up),
+            (Tuple::point(-0.2, -1.3, 1.1), Face::Down),
+        ];
+        for (point, face) in cases {
+            assert_eq!(cube_uv(point).0, face, "{}", point);
+        }
+    }
+
+    #[test]
+    fn cube_uv_on_faces() {
+        let cases = [
+            (Tuple::point(-0.5, 0.5, 1.0), (Face::Front, 0.25, 0.75)),
+            (Tuple::point(0.5, -0.5, 1.0), (Face::Front, 0.75, 0.25)),
+            (Tuple::point(0.5, 0.5, -1.0), (Face::Back, 0.25, 0.75)),
+            (Tuple::point(-0.5, -0.5, -1.0), (Face::Back, 0.75, 0.25)),
+            (Tuple::point(-1.0, 0.5, -0.5), (Face::Left, 0.25, 0.75)),
+            (Tuple::point(-1.0, -0.5, 0.5), (Face::Left, 0.75, 0.25)),
+            (Tuple::point(1.0, 0.5, 0.5), (Face::Right, 0.25, 0.75)),
+            (Tuple::point(1.0, -0.5, -0.5), (Face::Right, 0.75, 0.25)),
+            (Tuple::point(-0.5, 1.0, -0.5), (Face::Up, 0.25, 0.75)),
+            (Tuple::point(0.5, 1.0, 0.5), (Face::Up, 0.75, 0.25)),
+            (Tuple::point(-0.5, -1.0, 0.5), (Face::Down, 0.25, 0.75)),
+            (Tuple::point(0.5, -1.0, -0.5), (Face::Down, 0.75, 0.25)),
+        ];
+        for (point, expected) in cases {
+            assert_eq!(cube_uv(point), expected, "{}", point);
+        }
+        assert_eq!(
+            UvMapping::Cubic.uv(Tuple::point(0.5, -1.0, -0.5)),
+            (0.75, 0.25)
+        );
+    }
+
+    #[test]
+    fn seams_and_poles_in_range() {
+        let in_range = |(u, v): (f64, f64)| (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v);
+        let seam = [
+            Tuple::point(0.0, 0.0, -1.0),
+            Tuple::point(-0.0, 0.0, -1.0),
+            Tuple::point(-1e-300, 0.5, -1.0),
+        ];
+        for &point in seam.iter() {
+            assert!(in_range(spherical_uv(point)), "{:?}", point);
+            assert!(in_range(cylindrical_uv(point)), "{:?}", point);
+        }
+        assert_eq!(spherical_uv(Tuple::point(-0.0, 0.0, -1.0)).0, 1.0);
+
+        assert_eq!(spherical_uv(Tuple::point(0.0, 1.0, 0.0)).1, 1.0);
+        assert_eq!(spherical_uv(Tuple::point(0.0, -1.0, 0.0)).1, 0.0);
+
+        let tiny = Tuple::point(-1e-18, 0.0, -1e-18);
+        assert!(in_range(planar_uv(tiny)));
+        assert_eq!(planar_uv(tiny), (1.0, 1.0));
+
+        let (_, u, v) = cube_uv(Tuple::point(-1.0 - 1e-16, 1.0, 1.0));
+        assert!(in_range((u, v)));
+    }
+}
@@ -0,0 +1,171 @@
+//! Holds the [UvMap] struct, which implements the [Texture].
+
+use std::rc::Rc;
+
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::tuple::Tuple;
+
+use super::uv::UvMapping;
+use super::{Texture, TextureKind};
+
+/// Wraps a flat texture around a shape. Points are mapped to (u, v) coordinates on the surface of
+/// the shape, which are looked up in the wrapped texture with [Texture::color_at_uv]. Unlike 3D
+/// textures, which color space itself, the pattern follows the surface, so stripes on a sphere
+/// run along lines of longitude instead of cutting through it.
+///
+/// The mapping works in object space, after `self`'s own transform. The wrapped texture's
+/// transform is applied to (u, 0, v), so it can scale the pattern to the unit square.
+///
+/// # Examples
+///
+/// Four stripes around a sphere:
+/// ```
+/// # use truster::texture::uv_map::UvMap;
+/// use std::rc::Rc;
+/// use truster::color::Color;
+/// use truster::matrix::Matrix;
+/// use truster::texture::{stripe::Stripe, uv::UvMapping, Texture};
+/// use truster::tuple::Tuple;
+///
+/// let mut stripe = Stripe::colors(Color::WHITE, Color::BLACK);
+/// stripe.set_transform(Matrix::scaling(0.25, 1.0, 1.0));
+/// let texture = UvMap::new(Rc::new(stripe), UvMapping::Spherical);
+/// assert_eq!(texture.color_at(Tuple::point(0.0, 0.0, -1.0)), Color::WHITE);
+/// assert_eq!(texture.color_at(Tuple::point(1.0, 0.0, 0.0)), Color::BLACK);
+/// assert_eq!(texture.color_at(Tuple::point(0.0, 0.0, 1.0)), Color::WHITE);
+/// ```
+pub struct UvMap {
+    texture: Rc<dyn Texture>,
+    mapping: UvMapping,
+    transform: Matrix,
+    transform_inverse: Matrix,
+}
+
+impl UvMap {
+    /// Returns a new texture which wraps `texture` around shapes with `mapping`.
+    pub fn new(texture: Rc<dyn Texture>, mapping: UvMapping) -> Self {
+        Self {
+            texture,
+            mapping,
+            transform: Matrix::eye(),
+            transform_inverse: Matrix::eye(),
+        }
+    }
+
+    /// Returns the mapping `self` uses.
+    pub fn mapping(&self) -> UvMapping {
+        self.mapping
+    }
+}
+
+impl Texture for UvMap {
+    fn color_at(&self, point: Tuple) -> Color {
+        let (u, v) = self.mapping.uv(point);
+        self.texture.color_at_uv(u, v)
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn transform_inverse(&self) -> &Matrix {
+        &self.transform_inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform_inverse = transform.inverse();
+        self.transform = transform;
+    }
+
+    fn kind(&self) -> Option<TextureKind> {
+        Some(TextureKind::UvMap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::shape::{Shape, Sphere};
+    use crate::texture::{checker2d::Checker2d, stripe::Stripe};
+
+    fn stripes(bands: f64) -> UvMap {
+        let mut stripe = Stripe::colors(Color::WHITE, Color::BLACK);
+        stripe.set_transform(Matrix::scaling(1.0 / bands, 1.0, 1.0));
+        UvMap::new(Rc::new(stripe), UvMapping::Spherical)
+    }
+
+    #[test]
+    fn stripes_on_rotated_sphere_follow_longitude() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(Matrix::rotation_z(PI / 3.0) * &Matrix::rotation_y(0.4));
+        let sphere: Rc<dyn Shape> = Rc::new(sphere);
+        let texture = stripes(8.0);
+
+        for i in 0..20 {
+            for j in 1..10 {
+                let longitude = i as f64 * 0.31 + 0.05;
+                let latitude = j as f64 * PI / 10.0 - PI / 2.0;
+                let local = Tuple::point(
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin(),
+                    latitude.cos() * longitude.cos(),
+                );
+                let world = sphere.transform() * local;
+
+                // u is 0 at -z and goes up with the angle from there around y.
+                let u = (longitude + PI) / (2.0 * PI);
+                let u = 1.0 - u.rem_euclid(1.0);
+                let expected = if (u * 8.0).floor() as i32 % 2 == 0 {
+                    Color::WHITE
+                } else {
+                    Color::BLACK
+                };
+                assert_eq!(
+                    texture.color_at_shape(world, Rc::clone(&sphere)),
+                    expected,
+                    "longitude {}, latitude {}",
+                    longitude,
+                    latitude
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stripes_do_not_depend_on_latitude() {
+        let texture = stripes(4.0);
+        let longitude = 0.3_f64;
+        let color = texture.color_at(Tuple::point(longitude.sin(), 0.0, longitude.cos()));
+        for &latitude in &[-1.2_f64, -0.5, 0.4, 1.3] {
+            let point = Tuple::point(
+                latitude.cos() * longitude.sin(),
+                latitude.sin(),
+                latitude.cos() * longitude.cos(),
+            );
+            assert_eq!(texture.color_at(point), color);
+        }
+    }
+
+    #[test]
+    fn planar_checkers() {
+        let mut checker = Checker2d::colors(Color::WHITE, Color::BLACK);
+        checker.set_transform(Matrix::scaling(0.5, 1.0, 0.5));
+        let texture = UvMap::new(Rc::new(checker), UvMapping::Planar);
+        assert_eq!(texture.mapping(), UvMapping::Planar);
+        assert_eq!(
+            texture.color_at(Tuple::point(0.25, 0.0, 0.25)),
+            Color::WHITE
+        );
+        assert_eq!(
+            texture.color_at(Tuple::point(0.75, 0.0, 0.25)),
+            Color::BLACK
+        );
+        assert_eq!(
+            texture.color_at(Tuple::point(2.75, 5.0, 0.75)),
+            Color::WHITE
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Holds the [Transform] builder, which composes transformation matrices in reading order.
+//!
+//! # Examples
+//!
+//! Matrices apply right to left, so the last matrix in a product is applied first. A [Transform]
+//! applies its steps in the order they are written instead:
+//! ```
+//! # use truster::transform::Transform;
+//! use std::f64::consts::PI;
+//! use truster::matrix::Matrix;
+//! use truster::tuple::Tuple;
+//!
+//! let transform = Transform::new()
+//!     .rotate_x(PI / 2.0)
+//!     .scale(5.0, 5.0, 5.0)
+//!     .translate(10.0, 5.0, 7.0)
+//!     .build();
+//! let by_hand = Matrix::translation(10.0, 5.0, 7.0)
+//!     * &Matrix::scaling(5.0, 5.0, 5.0)
+//!     * &Matrix::rotation_x(PI / 2.0);
+//! assert_eq!(transform, by_hand);
+//! ```
+
+use crate::matrix::Matrix;
+
+/// Builds a transformation [Matrix] step by step. Every step is applied after the previous ones.
+/// See the module's documentation for more info.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Transform {
+    matrix: Matrix,
+}
+
+impl Transform {
+    /// Returns a new transform which doesn't change anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `matrix` after the steps so far.
+    pub fn then(mut self, matrix: &Matrix) -> Self {
+        self.matrix = matrix * &self.matrix;
+        self
+    }
+
+    /// Translates by (`x`, `y`, `z`). See [Matrix::translation].
+    pub fn translate(self, x: f64, y: f64, z: f64) -> Self {
+        self.then(&Matrix::translation(x, y, z))
+    }
+
+    /// Scales by (`x`, `y`, `z`). See [Matrix::scaling].
+    pub fn scale(self, x: f64, y: f64, z: f64) -> Self {
+        self.then(&Matrix::scaling(x, y, z))
+    }
+
+    /// Rotates by `theta` radians around the x axis. See [Matrix::rotation_x].
+    pub fn rotate_x(self, theta: f64) -> Self {
+        self.then(&Matrix::rotation_x(theta))
+    }
+
+    /// Rotates by `theta` radians around the y axis. See [Matrix::rotation_y].
+    pub fn rotate_y(self, theta: f64) -> Self {
+        self.then(&Matrix::rotation_y(theta))
+    }
+
+    /// Rotates by `theta` radians around the z axis. See [Matrix::rotation_z].
+    pub fn rotate_z(self, theta: f64) -> Self {
+        self.then(&Matrix::rotation_z(theta))
+    }
+
+    /// Shears by the given factors. See [Matrix::shearing].
+    pub fn shear(self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        self.then(&Matrix::shearing(xy, xz, yx, yz, zx, zy))
+    }
+
+    /// Returns the matrix which applies all steps.
+    pub fn build(self) -> Matrix {
+        self.matrix
+    }
+}
+
+impl From<Transform> for Matrix {
+    fn from(transform: Transform) -> Self {
+        transform.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuple::Tuple;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn new_is_identity() {
+        assert_eq!(Transform::new().build(), Matrix::eye());
+    }
+
+    #[test]
+    fn reading_order() {
+        let a = PI / 3.0;
+        let transform = Transform::new()
+            .rotate_z(a)
+            .scale(2.0, 3.0, 4.0)
+            .translate(1.0, -2.0, 5.0)
+            .build();
+        let by_hand = Matrix::translation(1.0, -2.0, 5.0)
+            * &Matrix::scaling(2.0, 3.0, 4.0)
+            * &Matrix::rotation_z(a);
+        assert_eq!(transform, by_hand);
+    }
+
+    #[test]
+    fn applies_steps_in_order() {
+        let transform: Matrix = Transform::new()
+            .translate(1.0, 0.0, 0.0)
+            .scale(2.0, 2.0, 2.0)
+            .into();
+        assert_eq!(
+            transform * Tuple::point(1.0, 1.0, 1.0),
+            Tuple::point(4.0, 2.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn shear() {
+        let transform = Transform::new().shear(1.0, 0.0, 0.0, 0.0, 0.0, 0.0).build();
+        assert_eq!(transform, Matrix::shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0));
+    }
+}
@@ -41,6 +41,35 @@
 //! assert_eq!(p[0], 1.0);
 //! assert_eq!(p[1], 4.2);
 //! assert_eq!(p[2], -3.7);
+//! assert_eq!(p[3], 1.0);
+//! ```
+//!
+//! Index 3 is the w component, which is 1 for points and 0 for vectors. It can also be written:
+//! ```
+//! # use truster::tuple::Tuple;
+//! let mut p = Tuple::point(1.0, 4.2, -3.7);
+//! let mut v = Tuple::vector(1.0, 4.2, -3.7);
+//! assert_eq!(v[3], 0.0);
+//! p[3] = 0.0;
+//! v[3] = 1.0;
+//! assert_eq!(p, Tuple::vector(1.0, 4.2, -3.7));
+//! assert_eq!(v, Tuple::point(1.0, 4.2, -3.7));
+//! ```
+//!
+//! ## Conversions
+//!
+//! Tuples can be converted from and to arrays. A 3 element array becomes a vector:
+//! ```
+//! # use truster::tuple::Tuple;
+//! let v = Tuple::from([1.0, 2.0, 3.0]);
+//! assert_eq!(v, Tuple::vector(1.0, 2.0, 3.0));
+//!
+//! let p: Tuple = [1.0, 2.0, 3.0, 1.0].into();
+//! assert_eq!(p, Tuple::point(1.0, 2.0, 3.0));
+//!
+//! let a: [f64; 4] = p.into();
+//! assert_eq!(a, [1.0, 2.0, 3.0, 1.0]);
+//! assert_eq!(Tuple::from(a), p);
 //! ```
 //!
 //! ## Arithmetic
@@ -48,8 +77,9 @@
 //! Tuples support all common arithmetic operations. However, be careful, as for example points
 //! can't be added to points. You have to add vectors to points to get another point. This library
 //! won't check this for you, because of simplicity and for performance reasons. You should make
-//! sure you handle everything correctly to avoid bugs. All operations which support operator
-//! overloading support mutable assignment. The available operations are:
+//! sure you handle everything correctly to avoid bugs, or use [Point] and [Vector], which do check
+//! this at compile time. See the [typed] module for more information. All operations which
+//! support operator overloading support mutable assignment. The available operations are:
 //!
 //! - Addition (p+v -> v, v+p -> p, v+v -> v)
 //! ```
@@ -142,6 +172,10 @@
 //! assert_eq!(v, Tuple::vector(1.0 / sqrt14, 2.0 / sqrt14, 3.0 / sqrt14));
 //! ```
 
+pub mod typed;
+
+pub use typed::{Point, Vector};
+
 use std::fmt::Display;
 use std::ops::{
     Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
@@ -157,19 +191,48 @@ pub struct Tuple {
 }
 
 impl Tuple {
+    /// The origin, the point (0, 0, 0).
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// assert!(Tuple::ORIGIN.is_point());
+    /// assert_eq!(Tuple::ORIGIN, Tuple::point(0.0, 0.0, 0.0));
+    /// ```
+    pub const ORIGIN: Self = Self::point(0.0, 0.0, 0.0);
+    /// The zero vector, (0, 0, 0).
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// assert!(Tuple::ZERO.is_vector());
+    /// assert_eq!(Tuple::ZERO, Tuple::vector(0.0, 0.0, 0.0));
+    /// ```
+    pub const ZERO: Self = Self::vector(0.0, 0.0, 0.0);
+    /// The unit vector pointing up, (0, 1, 0).
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// assert_eq!(Tuple::UP, Tuple::vector(0.0, 1.0, 0.0));
+    /// ```
+    pub const UP: Self = Self::vector(0.0, 1.0, 0.0);
+    /// The unit vector pointing right, (1, 0, 0).
+    pub const RIGHT: Self = Self::vector(1.0, 0.0, 0.0);
+    /// The unit vector pointing forward, (0, 0, -1). This is the direction a
+    /// [camera](crate::camera::Camera) looks in by default.
+    pub const FORWARD: Self = Self::vector(0.0, 0.0, -1.0);
+
     /// Returns a new tuple with the given components. You should use [Tuple::point] and
     /// [Tuple::vector] instead.
-    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
         Self { x, y, z, w }
     }
 
     /// Returns a new point with the given coordinates.
-    pub fn point(x: f64, y: f64, z: f64) -> Self {
+    pub const fn point(x: f64, y: f64, z: f64) -> Self {
         Self::new(x, y, z, 1.0)
     }
 
     /// Returns a new vector with the given coordinates.
-    pub fn vector(x: f64, y: f64, z: f64) -> Self {
+    pub const fn vector(x: f64, y: f64, z: f64) -> Self {
         Self::new(x, y, z, 0.0)
     }
 
@@ -193,6 +256,11 @@ impl Tuple {
         self.w
     }
 
+    /// Returns `self`'s components as an array, in the order x, y, z, w.
+    pub fn as_array(&self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
     /// Returns true if `self` represents a point, false otherwise.
     pub fn is_point(&self) -> bool {
         self.w == 1.0
@@ -247,6 +315,115 @@ impl Tuple {
     pub fn reflect(self, normal: Self) -> Self {
         self - normal * 2.0 * self.dot(normal)
     }
+
+    /// Returns the euclidean distance between `self` and `other`. Only works for points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// let p1 = Tuple::point(1.0, 2.0, 3.0);
+    /// let p2 = Tuple::point(4.0, 6.0, 3.0);
+    /// assert_eq!(p1.distance(p2), 5.0);
+    /// assert_eq!(p2.distance(p1), 5.0);
+    /// ```
+    pub fn distance(self, other: Self) -> f64 {
+        (self - other).norm()
+    }
+
+    /// Returns the angle in radians between `self` and `other`. The result is between 0 and pi.
+    /// Only works for vectors, not points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v1 = Tuple::vector(1.0, 0.0, 0.0);
+    /// let v2 = Tuple::vector(0.0, 0.0, 3.0);
+    /// assert_eq!(v1.angle_between(v2), PI / 2.0);
+    /// assert_eq!(v1.angle_between(-v1), PI);
+    /// ```
+    pub fn angle_between(self, other: Self) -> f64 {
+        let cos = self.dot(other) / (self.norm() * other.norm());
+        cos.clamp(-1.0, 1.0).acos()
+    }
+
+    /// Returns the linear interpolation between `self` and `other`. A `t` of 0 gives `self`, a
+    /// `t` of 1 gives `other`. Works for points and vectors, as long as both are the same kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// let p1 = Tuple::point(0.0, 2.0, -4.0);
+    /// let p2 = Tuple::point(2.0, 4.0, 4.0);
+    /// assert_eq!(p1.lerp(p2, 0.0), p1);
+    /// assert_eq!(p1.lerp(p2, 0.5), Tuple::point(1.0, 3.0, 0.0));
+    /// assert_eq!(p1.lerp(p2, 1.0), p2);
+    /// ```
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+
+    /// Returns the component-wise minimum of `self` and `other`. The w component is that of
+    /// `self`, so the minimum of two points is a point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// let p1 = Tuple::point(1.0, -2.0, 3.0);
+    /// let p2 = Tuple::point(-1.0, 5.0, 3.5);
+    /// assert_eq!(p1.min(p2), Tuple::point(-1.0, -2.0, 3.0));
+    /// assert_eq!(p2.min(p1), Tuple::point(-1.0, -2.0, 3.0));
+    /// ```
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+            self.w,
+        )
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`. The w component is that of
+    /// `self`, so the maximum of two points is a point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// let p1 = Tuple::point(1.0, -2.0, 3.0);
+    /// let p2 = Tuple::point(-1.0, 5.0, 3.5);
+    /// assert_eq!(p1.max(p2), Tuple::point(1.0, 5.0, 3.5));
+    /// assert_eq!(p2.max(p1), Tuple::point(1.0, 5.0, 3.5));
+    /// ```
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+            self.w,
+        )
+    }
+
+    /// Returns `self` with the absolute value of each of x, y and z. The w component is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::tuple::Tuple;
+    /// assert_eq!(
+    ///     Tuple::vector(-1.0, 2.0, -0.5).abs(),
+    ///     Tuple::vector(1.0, 2.0, 0.5),
+    /// );
+    /// assert_eq!(Tuple::point(-3.0, 0.0, 4.0).abs(), Tuple::point(3.0, 0.0, 4.0));
+    /// ```
+    pub fn abs(self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs(), self.w)
+    }
 }
 
 impl Display for Tuple {
@@ -261,6 +438,25 @@ impl Display for Tuple {
     }
 }
 
+impl From<[f64; 3]> for Tuple {
+    /// Returns a vector with the given coordinates.
+    fn from(array: [f64; 3]) -> Self {
+        Self::vector(array[0], array[1], array[2])
+    }
+}
+
+impl From<[f64; 4]> for Tuple {
+    fn from(array: [f64; 4]) -> Self {
+        Self::new(array[0], array[1], array[2], array[3])
+    }
+}
+
+impl From<Tuple> for [f64; 4] {
+    fn from(tuple: Tuple) -> Self {
+        tuple.as_array()
+    }
+}
+
 impl Add for Tuple {
     type Output = Self;
 
@@ -353,6 +549,7 @@ impl Index<usize> for Tuple {
             0 => &self.x,
             1 => &self.y,
             2 => &self.z,
+            3 => &self.w,
             _ => panic!("Index out of bounds for tuple, got {}", index),
         }
     }
@@ -364,6 +561,7 @@ impl IndexMut<usize> for Tuple {
             0 => &mut self.x,
             1 => &mut self.y,
             2 => &mut self.z,
+            3 => &mut self.w,
             _ => panic!("Index out of bounds for tuple, got {}", index),
         }
     }
@@ -0,0 +1,380 @@
+//! Points and vectors as separate types.
+//!
+//! [Tuple] can hold both points and vectors, and leaves it to you to combine them correctly.
+//! [Point] and [Vector] wrap a [Tuple], and only implement the operations which make sense for
+//! them, so mistakes are caught by the compiler. They are thin wrappers, so converting between
+//! them and [Tuple] is free. Use whichever fits best, the rest of the library works with [Tuple].
+//!
+//! # Examples
+//!
+//! ```
+//! # use truster::tuple::{Point, Tuple, Vector};
+//! let p1 = Point::new(3.0, 2.0, 1.0);
+//! let p2 = Point::new(5.0, 6.0, 7.0);
+//! let v: Vector = p2 - p1;
+//! assert_eq!(v, Vector::new(2.0, 4.0, 6.0));
+//! assert_eq!(p1 + v, p2);
+//! assert_eq!(p2 - v, p1);
+//! assert_eq!(Tuple::from(p1), Tuple::point(3.0, 2.0, 1.0));
+//! ```
+//!
+//! Points can't be added together:
+//! ```compile_fail
+//! # use truster::tuple::Point;
+//! let p = Point::new(1.0, 2.0, 3.0) + Point::new(1.0, 2.0, 3.0);
+//! ```
+//!
+//! Points can't be subtracted from vectors:
+//! ```compile_fail
+//! # use truster::tuple::{Point, Vector};
+//! let v = Vector::new(1.0, 2.0, 3.0) - Point::new(1.0, 2.0, 3.0);
+//! ```
+//!
+//! Points can't be scaled:
+//! ```compile_fail
+//! # use truster::tuple::Point;
+//! let p = Point::new(1.0, 2.0, 3.0) * 2.0;
+//! ```
+//!
+//! Points have no norm:
+//! ```compile_fail
+//! # use truster::tuple::Point;
+//! let n = Point::new(1.0, 2.0, 3.0).norm();
+//! ```
+//!
+//! Tuples can only become a point or vector if they are one:
+//! ```
+//! # use truster::tuple::{Point, Tuple, Vector};
+//! use std::convert::TryFrom;
+//!
+//! assert!(Point::try_from(Tuple::point(1.0, 2.0, 3.0)).is_ok());
+//! assert!(Point::try_from(Tuple::vector(1.0, 2.0, 3.0)).is_err());
+//! assert!(Vector::try_from(Tuple::vector(1.0, 2.0, 3.0)).is_ok());
+//! assert!(Vector::try_from(Tuple::point(1.0, 2.0, 3.0)).is_err());
+//! ```
+//!
+//! Transforming keeps the type:
+//! ```
+//! # use truster::tuple::{Point, Vector};
+//! # use truster::matrix::Matrix;
+//! let transform = Matrix::translation(1.0, 2.0, 3.0);
+//! assert_eq!(&transform * Point::new(0.0, 0.0, 0.0), Point::new(1.0, 2.0, 3.0));
+//! assert_eq!(&transform * Vector::new(1.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::matrix::Matrix;
+
+use super::Tuple;
+
+/// A point in 3D space. See the module's documentation for more information.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Point(Tuple);
+
+/// A displacement in 3D space. See the module's documentation for more information.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Vector(Tuple);
+
+impl Point {
+    /// The origin, (0, 0, 0).
+    pub const ORIGIN: Self = Self(Tuple::ORIGIN);
+
+    /// Creates a new point with the given coordinates.
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self(Tuple::point(x, y, z))
+    }
+
+    /// Returns `self`'s x coordinate.
+    pub fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    /// Returns `self`'s y coordinate.
+    pub fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    /// Returns `self`'s z coordinate.
+    pub fn z(&self) -> f64 {
+        self.0.z()
+    }
+
+    /// Returns `self` as a [Tuple].
+    pub fn tuple(self) -> Tuple {
+        self.0
+    }
+
+    /// Returns the euclidean distance between `self` and `other`.
+    pub fn distance(self, other: Self) -> f64 {
+        self.0.distance(other.0)
+    }
+
+    /// Returns the linear interpolation between `self` and `other`. See [Tuple::lerp].
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        Self(self.0.lerp(other.0, t))
+    }
+}
+
+impl Vector {
+    /// The zero vector, (0, 0, 0).
+    pub const ZERO: Self = Self(Tuple::ZERO);
+    /// The unit vector pointing up, (0, 1, 0).
+    pub const UP: Self = Self(Tuple::UP);
+    /// The unit vector pointing right, (1, 0, 0).
+    pub const RIGHT: Self = Self(Tuple::RIGHT);
+    /// The unit vector pointing forward, (0, 0, -1).
+    pub const FORWARD: Self = Self(Tuple::FORWARD);
+
+    /// Creates a new vector with the given coordinates.
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self(Tuple::vector(x, y, z))
+    }
+
+    /// Returns `self`'s x coordinate.
+    pub fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    /// Returns `self`'s y coordinate.
+    pub fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    /// Returns `self`'s z coordinate.
+    pub fn z(&self) -> f64 {
+        self.0.z()
+    }
+
+    /// Returns `self` as a [Tuple].
+    pub fn tuple(self) -> Tuple {
+        self.0
+    }
+
+    /// Returns the dot product between `self` and `other`.
+    pub fn dot(self, other: Self) -> f64 {
+        self.0.dot(other.0)
+    }
+
+    /// Returns the cross product between `self` and `other`.
+    pub fn cross(self, other: Self) -> Self {
+        Self(self.0.cross(other.0))
+    }
+
+    /// Returns the square of the euclidean norm of `self`.
+    pub fn norm_squared(self) -> f64 {
+        self.0.norm_squared()
+    }
+
+    /// Returns the euclidean norm of `self`.
+    pub fn norm(self) -> f64 {
+        self.0.norm()
+    }
+
+    /// Returns a vector in the same direction as `self`, but with euclidean norm of one.
+    pub fn normalized(self) -> Self {
+        Self(self.0.normalized())
+    }
+
+    /// Changes `self` to have a euclidean norm of one, while keeping its direction.
+    pub fn normalize(&mut self) {
+        self.0.normalize();
+    }
+
+    /// Reflects `self` along `normal`.
+    pub fn reflect(self, normal: Self) -> Self {
+        Self(self.0.reflect(normal.0))
+    }
+
+    /// Returns the angle in radians between `self` and `other`. See [Tuple::angle_between].
+    pub fn angle_between(self, other: Self) -> f64 {
+        self.0.angle_between(other.0)
+    }
+
+    /// Returns the linear interpolation between `self` and `other`. See [Tuple::lerp].
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        Self(self.0.lerp(other.0, t))
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+impl From<Point> for Tuple {
+    fn from(point: Point) -> Self {
+        point.0
+    }
+}
+
+impl From<Vector> for Tuple {
+    fn from(vector: Vector) -> Self {
+        vector.0
+    }
+}
+
+impl TryFrom<Tuple> for Point {
+    type Error = Tuple;
+
+    /// Returns `tuple` as a point, or gives it back if it is not a point.
+    fn try_from(tuple: Tuple) -> Result<Self, Self::Error> {
+        if tuple.is_point() {
+            Ok(Self(tuple))
+        } else {
+            Err(tuple)
+        }
+    }
+}
+
+impl TryFrom<Tuple> for Vector {
+    type Error = Tuple;
+
+    /// Returns `tuple` as a vector, or gives it back if it is not a vector.
+    fn try_from(tuple: Tuple) -> Result<Self, Self::Error> {
+        if tuple.is_vector() {
+            Ok(Self(tuple))
+        } else {
+            Err(tuple)
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector(self.0 - rhs.0)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Add<Point> for Vector {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point(self.0 + rhs.0)
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl MulAssign<f64> for Vector {
+    fn mul_assign(&mut self, rhs: f64) {
+        self.0 *= rhs;
+    }
+}
+
+impl Div<f64> for Vector {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+impl DivAssign<f64> for Vector {
+    fn div_assign(&mut self, rhs: f64) {
+        self.0 /= rhs;
+    }
+}
+
+impl Mul<Point> for &Matrix {
+    type Output = Point;
+
+    fn mul(self, rhs: Point) -> Point {
+        Point(self * rhs.0)
+    }
+}
+
+impl Mul<Vector> for &Matrix {
+    type Output = Vector;
+
+    fn mul(self, rhs: Vector) -> Vector {
+        Vector(self * rhs.0)
+    }
+}
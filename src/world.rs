@@ -2,29 +2,131 @@
 
 use std::rc::Rc;
 
+use crate::background::{Background, SolidBackground};
 use crate::color::Color;
 use crate::intersection::{Hit, HitRecord, Intersection};
 use crate::light::PointLight;
+use crate::material::Material;
+use crate::matrix::Matrix;
 use crate::ray::Ray;
-use crate::shape::Shape;
+use crate::sampling::{disk_basis, disk_samples, hemisphere_cosine};
+use crate::shape::{sphere::Sphere, Shape};
+use crate::stats::{Counters, RenderStats};
+use crate::texture::solid_color::SolidColor;
 use crate::tuple::Tuple;
+use crate::EPSILON;
+
+mod serialize;
+
+pub use serialize::SceneError;
 
 /// A 3D world which has shapes and lights.
-#[derive(Default)]
+///
+/// Rays which miss every shape get their color from the background, which is black by default.
+///
+/// Reflective and transparent materials make rays bounce, up to [MAX_DEPTH] times.
+///
+/// Point lights cast hard shadows by default. [World::set_soft_shadows] fakes a penumbra by
+/// casting several shadow rays per light to points around it.
+///
+/// Ambient occlusion is off by default. [World::set_ambient_occlusion] turns it on, which darkens
+/// the ambient light in corners and crevices.
+///
+/// Light scattering is off by default too. [World::set_scattering] turns it on, which adds cheap
+/// light shafts, as if the air were slightly hazy. See [World::in_scattering].
+///
+/// Shapes can be turned off without removing them with [World::set_shape_enabled]. Rays pass
+/// through disabled shapes, and they cast no shadows.
+///
+/// The world counts the rays it traces and the intersection tests it does. See [World::stats].
 pub struct World {
     shapes: Vec<Rc<dyn Shape>>,
+    enabled: Vec<bool>,
     lights: Vec<Rc<PointLight>>,
+    background: Rc<dyn Background>,
+    soft_shadow_samples: usize,
+    soft_shadow_radius: f64,
+    ao_samples: usize,
+    ao_radius: f64,
+    scattering_density: f64,
+    scattering_steps: usize,
+    check_energy: bool,
+    stats: Counters,
 }
 
+/// The number of times a ray can bounce off reflective or through transparent surfaces in
+/// [World::color_at].
+pub const MAX_DEPTH: usize = 5;
+
+// Soft shadows and ambient occlusion use the same samples at every point, so renders are
+// deterministic and noise doesn't flicker between frames.
+const SAMPLE_SEED: u64 = 0;
+
 impl World {
-    /// Returns a new empty [World].
+    /// Returns a new empty [World], with hard shadows.
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Returns a [WorldBuilder] which starts out with an empty world, like [World::new].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::world::World;
+    /// use truster::color::Color;
+    /// use truster::light::PointLight;
+    /// use truster::shape::{Plane, Sphere};
+    /// use truster::tuple::Tuple;
+    ///
+    /// let world = World::builder()
+    ///     .shape(Plane::new())
+    ///     .shape(Sphere::new())
+    ///     .light(PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::WHITE))
+    ///     .build();
+    /// assert_eq!(world.shapes().len(), 2);
+    /// assert_eq!(world.lights().len(), 1);
+    /// ```
+    pub fn builder() -> WorldBuilder {
+        WorldBuilder::default()
+    }
+
+    /// Returns the default world from the book, which is useful in tests and demos. It has a
+    /// white light at (-10, 10, -10), a unit sphere at the origin with color (0.8, 1.0, 0.6),
+    /// diffuse 0.7 and specular 0.2, and a sphere with the default material scaled by 0.5 inside
+    /// of it.
+    pub fn default_world() -> Self {
+        let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
+
+        let mut sphere1 = Sphere::new();
+        sphere1.set_material(Material {
+            texture: Rc::new(SolidColor::new(Color::new(0.8, 1.0, 0.6))),
+            diffuse: 0.7,
+            specular: 0.2,
+            ..Material::default()
+        });
+
+        let mut sphere2 = Sphere::new();
+        sphere2.set_transform(Matrix::scaling(0.5, 0.5, 0.5));
+
+        let mut world = Self::new();
+        world.add_light(Rc::new(light));
+        world.add_shape(Rc::new(sphere1));
+        world.add_shape(Rc::new(sphere2));
+
+        world
+    }
+
     /// Adds `shape` to `self`.
     pub fn add_shape(&mut self, shape: Rc<dyn Shape>) {
         self.shapes.push(shape);
+        self.enabled.push(true);
+    }
+
+    /// Adds all of `shapes` to `self`, in order.
+    pub fn add_shapes(&mut self, shapes: impl IntoIterator<Item = Rc<dyn Shape>>) {
+        self.shapes.extend(shapes);
+        self.enabled.resize(self.shapes.len(), true);
     }
 
     /// Adds `light` to `self`.
@@ -32,100 +134,806 @@ impl World {
         self.lights.push(light)
     }
 
+    /// Removes the shape at `index` from `self` and returns it. The shapes after it shift one
+    /// index down. Adding shapes never changes the index of shapes which are already in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_shape(&mut self, index: usize) -> Rc<dyn Shape> {
+        self.enabled.remove(index);
+        self.shapes.remove(index)
+    }
+
+    /// Removes all shapes from `self`. The lights are kept.
+    pub fn clear_shapes(&mut self) {
+        self.shapes.clear();
+        self.enabled.clear();
+    }
+
+    /// Returns the shapes in `self`, in the order they were added.
+    pub fn shapes(&self) -> &[Rc<dyn Shape>] {
+        &self.shapes
+    }
+
+    /// Returns the shapes in `self`, so they can be replaced.
+    pub fn shapes_mut(&mut self) -> &mut [Rc<dyn Shape>] {
+        &mut self.shapes
+    }
+
+    /// Turns the shape at `index` on or off. Disabled shapes stay in `self` with the same index,
+    /// but rays don't see them, so they don't show up and cast no shadows. This makes it quick to
+    /// find out which shape causes a problem. Shapes are enabled when they are added. Saving
+    /// `self` with [World::save] keeps which shapes are disabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::world::World;
+    /// use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let mut world = World::default_world();
+    /// let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+    /// assert_eq!(world.intersect(&ray).len(), 4);
+    ///
+    /// world.set_shape_enabled(0, false);
+    /// assert!(!world.is_shape_enabled(0));
+    /// assert_eq!(world.intersect(&ray).len(), 2);
+    /// ```
+    pub fn set_shape_enabled(&mut self, index: usize, enabled: bool) {
+        assert!(
+            index < self.shapes.len(),
+            "shape index {} out of bounds for {} shapes",
+            index,
+            self.shapes.len()
+        );
+        self.enabled[index] = enabled;
+    }
+
+    /// Returns whether the shape at `index` is enabled. See [World::set_shape_enabled].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn is_shape_enabled(&self, index: usize) -> bool {
+        self.enabled[index]
+    }
+
+    /// Returns the shapes which rays can hit.
+    fn enabled_shapes(&self) -> impl Iterator<Item = &Rc<dyn Shape>> {
+        self.shapes
+            .iter()
+            .zip(&self.enabled)
+            .filter(|(_, &enabled)| enabled)
+            .map(|(shape, _)| shape)
+    }
+
+    /// Returns a copy of `self` where the shape at `index` has `transform` instead. The other
+    /// shapes are shared with `self`, and `self` doesn't change. This is handy for animations,
+    /// where every frame moves a shape a little.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, or if the shape can't be copied (see
+    /// [Shape::clone_box]), like a [Group](crate::shape::Group).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::world::World;
+    /// use truster::matrix::Matrix;
+    ///
+    /// let world = World::default_world();
+    /// let frames: Vec<World> = (0..3)
+    ///     .map(|i| world.with_shape_transform(1, Matrix::translation(i as f64, 0.0, 0.0)))
+    ///     .collect();
+    /// assert_eq!(frames[2].shapes()[1].transform(), &Matrix::translation(2.0, 0.0, 0.0));
+    /// assert_eq!(world.shapes()[1].transform(), &Matrix::scale_uniform(0.5));
+    /// ```
+    pub fn with_shape_transform(&self, index: usize, transform: Matrix) -> Self {
+        let shape = &self.shapes[index];
+        let mut copy = shape.clone_box().unwrap_or_else(|| {
+            panic!("can't copy shape {} at index {}", shape.name(), index);
+        });
+        copy.set_transform(transform);
+
+        let mut world = self.clone();
+        world.shapes[index] = Rc::from(copy);
+        world
+    }
+
+    /// Returns the lights in `self`, in the order they were added.
+    pub fn lights(&self) -> &[Rc<PointLight>] {
+        &self.lights
+    }
+
+    /// Returns the lights in `self`, so they can be replaced.
+    pub fn lights_mut(&mut self) -> &mut [Rc<PointLight>] {
+        &mut self.lights
+    }
+
+    /// Returns the background of `self`.
+    pub fn background(&self) -> Rc<dyn Background> {
+        Rc::clone(&self.background)
+    }
+
+    /// Sets the background of `self`, which colors rays that hit nothing.
+    pub fn set_background(&mut self, background: Rc<dyn Background>) {
+        self.background = background;
+    }
+
+    /// Returns the number of shadow rays cast per light for every shaded point.
+    pub fn soft_shadow_samples(&self) -> usize {
+        self.soft_shadow_samples
+    }
+
+    /// Returns the radius around each light in which the shadow rays are spread.
+    pub fn soft_shadow_radius(&self) -> f64 {
+        self.soft_shadow_radius
+    }
+
+    /// Makes `self` cast `samples` shadow rays per light, aimed at points within `radius` of the
+    /// light, and average the result. The points lie on a disk facing the shaded point. They come
+    /// from [disk_samples](crate::sampling::disk_samples) with a fixed seed, so renders stay
+    /// deterministic. With 1 sample (the default), shadows are hard and `radius` is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is 0 or `radius` is negative.
+    pub fn set_soft_shadows(&mut self, samples: usize, radius: f64) {
+        if samples == 0 {
+            panic!("soft shadows need at least 1 sample");
+        }
+        if radius < 0.0 {
+            panic!("soft shadow radius should not be negative, got {}", radius);
+        }
+        self.soft_shadow_samples = samples;
+        self.soft_shadow_radius = radius;
+    }
+
+    /// Returns the number of rays cast for ambient occlusion at every shaded point. It is off when
+    /// this is 0, which is the default.
+    pub fn ao_samples(&self) -> usize {
+        self.ao_samples
+    }
+
+    /// Returns the distance within which geometry occludes ambient light.
+    pub fn ao_radius(&self) -> f64 {
+        self.ao_radius
+    }
+
+    /// Makes `self` cast `samples` rays over the hemisphere around the normal at every shaded
+    /// point. The ambient light is darkened by the fraction of rays which hit something within
+    /// `radius`. The rays come from [hemisphere_cosine](crate::sampling::hemisphere_cosine) with a
+    /// fixed seed, so renders stay deterministic. With 0 samples, ambient occlusion is off. It is
+    /// expensive, so use few samples for previews.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radius` is not positive.
+    pub fn set_ambient_occlusion(&mut self, samples: usize, radius: f64) {
+        if radius <= 0.0 {
+            panic!(
+                "ambient occlusion radius should be positive, got {}",
+                radius
+            );
+        }
+        self.ao_samples = samples;
+        self.ao_radius = radius;
+    }
+
+    /// Returns the fraction of ambient light blocked at the hit of `rec`, between 0 and 1. This
+    /// is always 0 when ambient occlusion is off. See [World::set_ambient_occlusion].
+    pub fn ambient_occlusion(&self, rec: &HitRecord) -> f64 {
+        let samples = self.ao_samples;
+        if samples == 0 {
+            return 0.0;
+        }
+
+        // Cosine weighted directions give more rays close to the normal, where occluders matter
+        // most.
+        let mut blocked = 0;
+        for direction in hemisphere_cosine(samples, rec.normal(), SAMPLE_SEED) {
+            let target = rec.over_point() + direction * self.ao_radius;
+            if self.is_occluded(rec.over_point(), target, rec.time()) {
+                blocked += 1;
+            }
+        }
+        blocked as f64 / samples as f64
+    }
+
+    /// Returns how much light the air in `self` scatters per unit of distance.
+    pub fn scattering_density(&self) -> f64 {
+        self.scattering_density
+    }
+
+    /// Returns the number of points at which [World::in_scattering] samples light along each
+    /// camera ray. Scattering is off when this is 0, which is the default.
+    pub fn scattering_steps(&self) -> usize {
+        self.scattering_steps
+    }
+
+    /// Makes the air in `self` scatter `density` of the light passing through it per unit of
+    /// distance, sampled at `steps` points along each camera ray.
+    /// [Camera::render](crate::camera::Camera::render) then adds [World::in_scattering] to every
+    /// pixel. This is not real volumetric lighting, but gives light shafts where shapes block the
+    /// light. Small densities, like 0.01, work best. With 0 steps, scattering is off.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `density` is negative.
+    pub fn set_scattering(&mut self, density: f64, steps: usize) {
+        if density < 0.0 {
+            panic!("scattering density should not be negative, got {}", density);
+        }
+        self.scattering_density = density;
+        self.scattering_steps = steps;
+    }
+
+    /// Returns the light scattered towards the origin of `ray` by the air between it and the
+    /// nearest hit. The light of every light is sampled at `steps` evenly spaced points, using
+    /// the same shadow rays as surfaces, and scaled by [World::scattering_density]. If `ray`
+    /// misses everything, it is marched to twice the distance at which it passes closest to a
+    /// light. Returns black if the density or `steps` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::world::World;
+    /// use truster::ray::Ray;
+    /// use truster::tuple::Tuple;
+    ///
+    /// let mut world = World::default_world();
+    /// let ray = Ray::new(Tuple::point(-5.0, 5.0, -5.0), Tuple::vector(0.0, 0.0, -1.0));
+    /// assert_eq!(world.in_scattering(&ray, 16).r(), 0.0);
+    /// world.set_scattering(0.01, 16);
+    /// assert!(world.in_scattering(&ray, 16).r() > 0.0);
+    /// ```
+    pub fn in_scattering(&self, ray: &Ray, steps: usize) -> Color {
+        let mut result = Color::BLACK;
+        if steps == 0 || self.scattering_density == 0.0 {
+            return result;
+        }
+
+        let direction = ray.direction().normalized();
+        let end = match self.intersect(ray).hit() {
+            Some(hit) => hit.t() * ray.direction().norm(),
+            None => {
+                let closest = self
+                    .lights
+                    .iter()
+                    .map(|light| (light.position() - ray.origin()).dot(direction))
+                    .fold(0.0, f64::max);
+                2.0 * closest
+            }
+        };
+        if end <= 0.0 {
+            return result;
+        }
+
+        let step = end / steps as f64;
+        for i in 0..steps {
+            let point = ray.origin() + direction * ((i as f64 + 0.5) * step);
+            for light in self.lights.iter() {
+                if self.is_occluded(point, light.position(), ray.time()) {
+                    continue;
+                }
+                let distance = (light.position() - point).norm();
+                result += light.radiance() * light.attenuation_at(distance);
+            }
+        }
+        result * (self.scattering_density * step)
+    }
+
+    /// Returns the number of rays traced and intersection tests done by `self` since it was
+    /// created, or since the last call to [World::reset_stats]. Primary rays are counted by
+    /// [Camera::render](crate::camera::Camera::render) and the other render methods of
+    /// [Camera](crate::camera::Camera).
+    pub fn stats(&self) -> RenderStats {
+        self.stats.snapshot()
+    }
+
+    /// Sets all counts of [World::stats] back to 0.
+    pub fn reset_stats(&self) {
+        self.stats.reset();
+    }
+
+    /// Adds `n` primary rays to [World::stats].
+    pub(crate) fn count_primary_rays(&self, n: u64) {
+        self.stats.add_primary_rays(n);
+    }
+
     /// Returns a list of all intersections the ray makes with any shape in the world.
     /// The list is sorted by distance.
     pub fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
         let mut result = Vec::new();
-        for shape in self.shapes.iter() {
-            let mut intersections = shape.intersect(ray);
-            result.append(&mut intersections);
-        }
-        result.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        self.intersect_into(ray, &mut result);
         result
     }
 
+    /// Like [World::intersect], but puts the intersections in `buffer` instead of a new list.
+    /// `buffer` is cleared first, so one buffer can be reused for many rays without allocating
+    /// the list every time. The shapes still return their intersections in lists of their own.
+    pub fn intersect_into(&self, ray: &Ray, buffer: &mut Vec<Intersection>) {
+        buffer.clear();
+        for shape in self.enabled_shapes() {
+            self.stats.add_intersection_tests(1);
+            buffer.append(&mut shape.intersect_rc(shape, ray));
+        }
+        buffer.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    }
+
+    /// Returns the [name](Shape::name) of the shape and the distance of every intersection of `ray`
+    /// with `self`, sorted by distance. This is meant for finding out why a scene doesn't look as
+    /// expected.
+    pub fn trace_debug(&self, ray: &Ray) -> Vec<(String, f64)> {
+        self.intersect(ray)
+            .iter()
+            .map(|i| (i.shape().name().to_string(), i.t()))
+            .collect()
+    }
+
+    /// Returns the [HitRecord] of the nearest intersection in front of `ray`, or `None` if `ray`
+    /// misses everything. The record is built from all intersections, so it knows the refractive
+    /// indices on both sides of the hit. This is the record [World::color_at_depth] shades, so
+    /// use it to trace rays yourself the same way.
+    pub fn hit(&self, ray: &Ray) -> Option<HitRecord> {
+        self.hit_into(ray, &mut Vec::new())
+    }
+
+    /// Like [World::hit], but intersects `ray` into `buffer` with [World::intersect_into].
+    pub fn hit_into(&self, ray: &Ray, buffer: &mut Vec<Intersection>) -> Option<HitRecord> {
+        self.intersect_into(ray, buffer);
+        let hit = buffer.hit()?;
+        Some(HitRecord::with_intersections(hit, ray, buffer))
+    }
+
     /// Returns the color at the intersection encapsulated by `rec` in `self`, as if the light at
     /// index `light_index` where the only one.
+    ///
+    /// This is only the surface color: the ambient, diffuse and specular light, computed from the
+    /// color of the material's texture. Emitted, reflected and refracted light are added by
+    /// [World::color_at_depth], once for all lights.
     pub fn shade_hit(&self, light_index: usize, rec: HitRecord) -> Color {
-        rec.shape().material().lighting(
-            Rc::clone(&rec.shape()),
-            &self.lights[light_index],
-            rec.point(),
-            rec.eye(),
-            rec.normal(),
-            self.is_shadowed(light_index, rec.over_point()),
-        )
+        let occlusion = self.ambient_occlusion(&rec);
+        self.shade_hit_occluded(light_index, rec, occlusion)
+    }
+
+    /// Like [World::shade_hit], with the ambient part darkened by `occlusion`.
+    fn shade_hit_occluded(&self, light_index: usize, rec: HitRecord, occlusion: f64) -> Color {
+        let shape = rec.shape();
+        let material = shape.material();
+        let light = &self.lights[light_index];
+        let visibility =
+            1.0 - self.shadow_fraction_at_time(light_index, rec.over_point(), rec.time());
+        let color = material.lighting_at_hit(light, &rec, visibility);
+        if occlusion == 0.0 {
+            return color;
+        }
+
+        let ambient = material.texture.color_at_hit(&rec) * light.radiance() * material.ambient;
+        color - ambient * occlusion
     }
 
-    /// Returns the color the `self` shows at the intersection point with `ray`.
+    /// Returns the color the `self` shows at the intersection point with `ray`. If `ray` hits
+    /// nothing, this is the color of the background.
     pub fn color_at(&self, ray: &Ray) -> Color {
-        let intersections = self.intersect(ray);
-        let hit = if let Some(hit) = intersections.hit() {
-            hit
+        self.color_at_depth(ray, MAX_DEPTH)
+    }
+
+    /// Like [World::color_at], but reflected and refracted rays may only bounce `remaining` more
+    /// times. With 0, reflective and transparent surfaces are shaded as if they were opaque.
+    ///
+    /// The surface color is the emission of the material plus the sum of [World::shade_hit] over
+    /// all lights, so an emissive surface glows even without lights. The reflected and refracted
+    /// colors are added once. They are not tinted by the surface color: a red mirror
+    /// reflects a green floor as green, not brown. The color of the material only shows in the
+    /// surface color, so a tinted mirror needs a non-zero diffuse or ambient. For materials which
+    /// are both reflective and transparent, they are weighted by [HitRecord::schlick], like a
+    /// Fresnel effect.
+    ///
+    /// # Panics
+    ///
+    /// If energy checks are on (see [World::set_check_energy]), panics if a channel of the
+    /// result is more than 1 above the surface color.
+    pub fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        self.color_at_depth_into(ray, remaining, &mut Vec::new())
+    }
+
+    /// Like [World::color_at_depth], but intersects `ray` and all reflected and refracted rays
+    /// into `buffer` with [World::intersect_into]. A render loop can pass the same buffer for
+    /// every ray.
+    pub fn color_at_depth_into(
+        &self,
+        ray: &Ray,
+        remaining: usize,
+        buffer: &mut Vec<Intersection>,
+    ) -> Color {
+        let rec = if let Some(rec) = self.hit_into(ray, buffer) {
+            rec
         } else {
-            return Color::new(0.0, 0.0, 0.0);
+            return self.background.color_for_ray(ray);
         };
 
-        let rec = HitRecord::new(&hit, ray);
-        let mut result = Color::new(0.0, 0.0, 0.0);
+        let shape = rec.shape();
+        let material = shape.material();
+        let occlusion = self.ambient_occlusion(&rec);
+        let mut surface = material.emission;
         for (i, _) in self.lights.iter().enumerate() {
-            let color = self.shade_hit(i, HitRecord::clone(&rec));
-            result += color;
+            let color = self.shade_hit_occluded(i, HitRecord::clone(&rec), occlusion);
+            surface += color;
         }
+
+        let reflected = self.reflected_color_into(&rec, remaining, buffer);
+        let refracted = self.refracted_color_into(&rec, remaining, buffer);
+
+        let result = if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = rec.schlick();
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        };
+
+        if self.check_energy {
+            for i in 0..3 {
+                if result[i] > surface[i] + 1.0 + EPSILON {
+                    panic!(
+                        "reflected and refracted light add more than 1 to the surface color {} at \
+                         channel {}, got {}",
+                        surface[i], i, result[i]
+                    );
+                }
+            }
+        }
+
         result
     }
 
+    /// Returns the color reflected at the hit of `rec`, scaled by how reflective the material is.
+    /// The color of the material itself doesn't affect it. Returns black if the material is not
+    /// reflective, or if `remaining` is 0.
+    pub fn reflected_color(&self, rec: &HitRecord, remaining: usize) -> Color {
+        self.reflected_color_into(rec, remaining, &mut Vec::new())
+    }
+
+    fn reflected_color_into(
+        &self,
+        rec: &HitRecord,
+        remaining: usize,
+        buffer: &mut Vec<Intersection>,
+    ) -> Color {
+        let reflective = rec.shape().material().reflective;
+        if remaining == 0 || reflective == 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let ray = Ray::with_time(rec.point(), rec.reflect(), rec.time())
+            .offset_origin(rec.normal(), rec.bias());
+        self.stats.add_reflection_ray();
+        self.color_at_depth_into(&ray, remaining - 1, buffer) * reflective
+    }
+
+    /// Returns the color seen through the hit of `rec`, scaled by how transparent the material is.
+    /// The refracted ray bends according to Snell's law with the refractive indices of `rec`.
+    /// Returns black if the material is opaque, if `remaining` is 0, or if there is total internal
+    /// reflection.
+    pub fn refracted_color(&self, rec: &HitRecord, remaining: usize) -> Color {
+        self.refracted_color_into(rec, remaining, &mut Vec::new())
+    }
+
+    fn refracted_color_into(
+        &self,
+        rec: &HitRecord,
+        remaining: usize,
+        buffer: &mut Vec<Intersection>,
+    ) -> Color {
+        let transparency = rec.shape().material().transparency;
+        if remaining == 0 || transparency == 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let ratio = rec.n1() / rec.n2();
+        let cos_i = rec.eye().dot(rec.normal());
+        let sin2_t = ratio * ratio * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction = rec.normal() * (ratio * cos_i - cos_t) - rec.eye() * ratio;
+        let ray = Ray::with_time(rec.point(), direction, rec.time())
+            .offset_origin(rec.normal(), -rec.bias());
+        self.stats.add_refraction_ray();
+        self.color_at_depth_into(&ray, remaining - 1, buffer) * transparency
+    }
+
+    /// Turns checks for physically implausible results of [World::color_at_depth] on or off.
+    /// They are off by default. They catch bugs where reflected and refracted light add more
+    /// than 1 to a channel of the surface color. This assumes no light is brighter than white.
+    pub fn set_check_energy(&mut self, check_energy: bool) {
+        self.check_energy = check_energy;
+    }
+
     /// Returns true if `point` is in the shadow of the light at index `light_index`, false
-    /// otherwise.
+    /// otherwise. Shapes whose material doesn't cast shadows are ignored. Moving shapes are where
+    /// they are at time 0.
     pub fn is_shadowed(&self, light_index: usize, point: Tuple) -> bool {
-        let v = self.lights[light_index].position() - point;
+        self.is_occluded(point, self.lights[light_index].position(), 0.0)
+    }
+
+    /// Returns the fraction of shadow rays from `point` to the light at index `light_index` that
+    /// are blocked, between 0 (fully lit) and 1 (fully in shadow). With a single soft shadow
+    /// sample, this is 1 if [World::is_shadowed] is true and 0 otherwise. Moving shapes are where
+    /// they are at time 0.
+    pub fn shadow_fraction(&self, light_index: usize, point: Tuple) -> f64 {
+        self.shadow_fraction_at_time(light_index, point, 0.0)
+    }
+
+    /// Like [World::shadow_fraction], with the shadow rays sent at `time`.
+    fn shadow_fraction_at_time(&self, light_index: usize, point: Tuple, time: f64) -> f64 {
+        let samples = self.soft_shadow_samples;
+        if samples <= 1 || self.soft_shadow_radius == 0.0 {
+            let position = self.lights[light_index].position();
+            return if self.is_occluded(point, position, time) {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        let position = self.lights[light_index].position();
+        let (u, v) = disk_basis((position - point).normalized());
+        let mut blocked = 0;
+        for (x, y) in disk_samples(samples, self.soft_shadow_radius, SAMPLE_SEED) {
+            let target = position + u * x + v * y;
+            if self.is_occluded(point, target, time) {
+                blocked += 1;
+            }
+        }
+        blocked as f64 / samples as f64
+    }
+
+    fn is_occluded(&self, point: Tuple, target: Tuple, time: f64) -> bool {
+        let v = target - point;
         let distance = v.norm();
         let direction = v / distance;
 
-        let ray = Ray::new(point, direction);
-        let intersections = self.intersect(&ray);
+        let ray = Ray::with_time(point, direction, time);
+        self.stats.add_shadow_ray();
+        self.any_hit_before_where(&ray, distance, |i| i.shape().material().casts_shadow)
+    }
+
+    /// Returns true if `ray` intersects any shape in `self` at a distance strictly between 0 and
+    /// `max_t`. This stops at the first such intersection, without collecting and sorting all
+    /// intersections like [World::intersect], so it is cheaper when only the answer matters, like
+    /// for shadows.
+    pub fn any_hit_before(&self, ray: &Ray, max_t: f64) -> bool {
+        self.any_hit_before_where(ray, max_t, |_| true)
+    }
 
-        match intersections.hit() {
-            Some(hit) if hit.t() < distance => true,
-            _ => false,
+    /// Like [World::any_hit_before], but only counts intersections for which `filter` is true.
+    fn any_hit_before_where(
+        &self,
+        ray: &Ray,
+        max_t: f64,
+        filter: impl Fn(&Intersection) -> bool,
+    ) -> bool {
+        for shape in self.enabled_shapes() {
+            self.stats.add_intersection_tests(1);
+            let hit = shape
+                .intersect_rc(shape, ray)
+                .iter()
+                .any(|i| i.t() > 0.0 && i.t() < max_t && filter(i));
+            if hit {
+                return true;
+            }
         }
+        false
+    }
+}
+
+/// Cloning a world is cheap: the shapes, lights and background are shared with the clone. The
+/// clone counts its rays and intersection tests from 0.
+impl Clone for World {
+    fn clone(&self) -> Self {
+        Self {
+            shapes: self.shapes.clone(),
+            enabled: self.enabled.clone(),
+            lights: self.lights.clone(),
+            background: Rc::clone(&self.background),
+            soft_shadow_samples: self.soft_shadow_samples,
+            soft_shadow_radius: self.soft_shadow_radius,
+            ao_samples: self.ao_samples,
+            ao_radius: self.ao_radius,
+            scattering_density: self.scattering_density,
+            scattering_steps: self.scattering_steps,
+            check_energy: self.check_energy,
+            stats: Counters::default(),
+        }
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self {
+            shapes: Vec::new(),
+            enabled: Vec::new(),
+            lights: Vec::new(),
+            background: Rc::new(SolidBackground(Color::BLACK)),
+            soft_shadow_samples: 1,
+            soft_shadow_radius: 0.0,
+            ao_samples: 0,
+            ao_radius: 1.0,
+            scattering_density: 0.0,
+            scattering_steps: 0,
+            check_energy: false,
+            stats: Counters::default(),
+        }
+    }
+}
+
+/// Builds a [World] one shape or light at a time. Get one with [World::builder]. Settings which are
+/// not set keep their value from [World::new].
+#[derive(Default)]
+pub struct WorldBuilder {
+    world: World,
+}
+
+impl WorldBuilder {
+    /// Adds `shape`.
+    pub fn shape(self, shape: impl Shape + 'static) -> Self {
+        self.shape_rc(Rc::new(shape))
+    }
+
+    /// Adds `shape`, which can still be shared with other owners.
+    pub fn shape_rc(mut self, shape: Rc<dyn Shape>) -> Self {
+        self.world.add_shape(shape);
+        self
+    }
+
+    /// Adds all of `shapes`, in order.
+    pub fn shapes(mut self, shapes: impl IntoIterator<Item = Rc<dyn Shape>>) -> Self {
+        self.world.add_shapes(shapes);
+        self
+    }
+
+    /// Adds `light`.
+    pub fn light(mut self, light: PointLight) -> Self {
+        self.world.add_light(Rc::new(light));
+        self
+    }
+
+    /// Sets the background. See [World::set_background].
+    pub fn background(mut self, background: impl Background + 'static) -> Self {
+        self.world.set_background(Rc::new(background));
+        self
+    }
+
+    /// Returns the world.
+    pub fn build(self) -> World {
+        self.world
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::PI;
+
     use super::*;
-    use crate::color::Color;
-    use crate::material::Material;
-    use crate::matrix::Matrix;
-    use crate::shape::sphere::Sphere;
-    use crate::texture::solid_color::SolidColor;
+    use crate::background::GradientBackground;
+    use crate::intersection::Distances;
+    use crate::shape::{Group, Plane};
+    use crate::texture::checker2d::Checker2d;
 
-    fn test_world() -> World {
-        let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
+    #[test]
+    fn remove_shape() {
+        let mut world = World::new();
+        let shapes: Vec<Rc<dyn Shape>> = vec![
+            Rc::new(Sphere::new()),
+            Rc::new(Sphere::new()),
+            Rc::new(Sphere::new()),
+        ];
+        for shape in shapes.iter() {
+            world.add_shape(Rc::clone(shape));
+        }
 
-        let mut sphere1 = Sphere::new();
-        sphere1.set_material(Material {
-            texture: Rc::new(SolidColor::new(Color::new(0.8, 1.0, 0.6))),
-            diffuse: 0.7,
-            specular: 0.2,
-            ..Material::default()
-        });
+        let removed = world.remove_shape(1);
 
-        let mut sphere2 = Sphere::new();
-        sphere2.set_transform(Matrix::scaling(0.5, 0.5, 0.5));
+        assert!(Rc::ptr_eq(&removed, &shapes[1]));
+        assert_eq!(world.shapes().len(), 2);
+        assert!(Rc::ptr_eq(&world.shapes()[0], &shapes[0]));
+        assert!(Rc::ptr_eq(&world.shapes()[1], &shapes[2]));
+    }
 
-        let mut world = World::new();
-        world.add_light(Rc::new(light));
-        world.add_shape(Rc::new(sphere1));
-        world.add_shape(Rc::new(sphere2));
+    #[test]
+    fn builder_matches_imperative() {
+        let sphere = || {
+            let mut sphere = Sphere::new();
+            sphere.set_transform(Matrix::translation(0.0, 1.0, 0.0));
+            sphere.set_material(Material::with_color(Color::RED));
+            sphere
+        };
+        let light = || PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::WHITE);
+        let background = || GradientBackground::new(Color::WHITE, Color::BLUE);
+        let small_spheres = || -> Vec<Rc<dyn Shape>> {
+            (0..3)
+                .map(|i| {
+                    let mut sphere = Sphere::new();
+                    sphere.set_transform(
+                        Matrix::translation(i as f64 - 1.0, 0.5, -2.0)
+                            * &Matrix::scaling(0.3, 0.3, 0.3),
+                    );
+                    Rc::new(sphere) as Rc<dyn Shape>
+                })
+                .collect()
+        };
 
-        world
+        let mut imperative = World::new();
+        imperative.add_shape(Rc::new(Plane::new()));
+        imperative.add_shape(Rc::new(sphere()));
+        imperative.add_shapes(small_spheres());
+        imperative.add_light(Rc::new(light()));
+        imperative.set_background(Rc::new(background()));
+
+        let built = World::builder()
+            .shape(Plane::new())
+            .shape_rc(Rc::new(sphere()))
+            .shapes(small_spheres())
+            .light(light())
+            .background(background())
+            .build();
+
+        assert_eq!(built.shapes().len(), imperative.shapes().len());
+        for (a, b) in built.shapes().iter().zip(imperative.shapes()) {
+            assert_eq!(a.name(), b.name());
+            assert_eq!(a.transform(), b.transform());
+        }
+        assert_eq!(built.lights().len(), 1);
+        assert_eq!(
+            built.lights()[0].position(),
+            imperative.lights()[0].position()
+        );
+        for &direction in &[
+            Tuple::vector(0.0, 0.0, 1.0),
+            Tuple::vector(0.0, -0.2, 1.0),
+            Tuple::vector(0.3, -0.1, 1.0),
+            Tuple::vector(0.0, 1.0, 0.2),
+        ] {
+            let ray = Ray::new(Tuple::point(0.0, 1.0, -5.0), direction.normalized());
+            assert_eq!(built.color_at(&ray), imperative.color_at(&ray));
+        }
+    }
+
+    #[test]
+    fn clear_shapes() {
+        let mut world = World::default_world();
+        world.clear_shapes();
+        assert!(world.shapes().is_empty());
+        assert_eq!(world.lights().len(), 1);
+    }
+
+    #[test]
+    fn shapes_mut_and_lights_mut() {
+        let mut world = World::default_world();
+        let sphere: Rc<dyn Shape> = Rc::new(Sphere::new());
+        world.shapes_mut()[0] = Rc::clone(&sphere);
+        world.lights_mut()[0] = Rc::new(PointLight::new(
+            Tuple::point(1.0, 2.0, 3.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+
+        assert!(Rc::ptr_eq(&world.shapes()[0], &sphere));
+        assert_eq!(world.lights()[0].position(), Tuple::point(1.0, 2.0, 3.0));
     }
 
     #[test]
     fn intersect() {
-        let world = test_world();
+        let world = World::default_world();
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
         let intersections = world.intersect(&ray);
 
@@ -136,9 +944,101 @@ mod tests {
         assert_eq!(intersections[3].t(), 6.0);
     }
 
+    #[test]
+    fn trace_debug() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let sphere = String::from("Sphere");
+        assert_eq!(
+            world.trace_debug(&ray),
+            vec![
+                (sphere.clone(), 4.0),
+                (sphere.clone(), 4.5),
+                (sphere.clone(), 5.5),
+                (sphere, 6.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn color_at_depth_into_prefilled_buffer() {
+        let mut world = World::default_world();
+        let mut plane = Plane::new();
+        plane.set_transform(Matrix::translation(0.0, -1.0, 0.0));
+        plane.set_material(Material::builder().reflective(0.5).build());
+        world.add_shape(Rc::new(plane));
+        let ray = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0),
+        );
+        let other = Ray::new(Tuple::point(0.0, 0.5, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let mut buffer = world.intersect(&other);
+        let color = world.color_at_depth_into(&ray, MAX_DEPTH, &mut buffer);
+        assert_eq!(color, world.color_at(&ray));
+        let rec = world.hit_into(&ray, &mut buffer).unwrap();
+        assert_eq!(rec.t(), world.hit(&ray).unwrap().t());
+    }
+
+    #[test]
+    fn intersect_into_prefilled_buffer() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let other = Ray::new(Tuple::point(0.0, 0.5, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        let mut buffer = world.intersect(&other);
+        assert!(!buffer.is_empty());
+        world.intersect_into(&ray, &mut buffer);
+
+        let expected = world.intersect(&ray);
+        assert_eq!(buffer.len(), expected.len());
+        for (actual, expected) in buffer.iter().zip(expected.iter()) {
+            assert_eq!(actual.t(), expected.t());
+            assert!(Rc::ptr_eq(&actual.shape(), &expected.shape()));
+        }
+    }
+
+    #[test]
+    fn intersect_shares_shapes() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let intersections = world.intersect(&ray);
+
+        assert!(Rc::ptr_eq(&intersections[0].shape(), &world.shapes[0]));
+        assert!(Rc::ptr_eq(&intersections[3].shape(), &world.shapes[0]));
+        assert!(Rc::ptr_eq(&intersections[1].shape(), &world.shapes[1]));
+        assert!(Rc::ptr_eq(&intersections[2].shape(), &world.shapes[1]));
+    }
+
+    #[test]
+    fn hit_miss() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+        assert!(world.hit(&ray).is_none());
+    }
+
+    #[test]
+    fn hit_nearest() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = world.hit(&ray).unwrap();
+        assert_eq!(rec.t(), 4.0);
+        assert!(Rc::ptr_eq(&rec.shape(), &world.shapes[0]));
+        assert_eq!(rec.point(), Tuple::point(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn hit_inside() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = world.hit(&ray).unwrap();
+        assert_eq!(rec.t(), 0.5);
+        assert!(Rc::ptr_eq(&rec.shape(), &world.shapes[1]));
+    }
+
     #[test]
     fn shade_hit() {
-        let world = test_world();
+        let world = World::default_world();
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
         let shape = Rc::clone(&world.shapes[0]);
         let intersection = Intersection::new(4.0, shape);
@@ -156,7 +1056,7 @@ mod tests {
 
     #[test]
     fn shade_hit_inside() {
-        let mut world = test_world();
+        let mut world = World::default_world();
         world.lights[0] = Rc::new(PointLight::new(
             Tuple::point(0.0, 0.25, 0.0),
             Color::new(1.0, 1.0, 1.0),
@@ -174,7 +1074,7 @@ mod tests {
 
     #[test]
     fn shade_hit_intersection_in_shadow() {
-        let mut world = test_world();
+        let mut world = World::default_world();
         world.lights[0] = Rc::new(PointLight::new(
             Tuple::point(0.0, 0.0, -10.0),
             Color::new(1.0, 1.0, 1.0),
@@ -196,15 +1096,25 @@ mod tests {
 
     #[test]
     fn color_at_miss() {
-        let world = test_world();
+        let world = World::default_world();
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
         let color = world.color_at(&ray);
         assert_eq!(color, Color::new(0.0, 0.0, 0.0));
     }
 
+    #[test]
+    fn color_at_miss_background() {
+        let mut world = World::default_world();
+        world.set_background(Rc::new(GradientBackground::new(Color::BLACK, Color::WHITE)));
+        let up = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+        let down = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, -1.0, 0.0));
+        assert_eq!(world.color_at(&up), Color::WHITE);
+        assert_eq!(world.color_at(&down), Color::BLACK);
+    }
+
     #[test]
     fn color_at_hit() {
-        let world = test_world();
+        let world = World::default_world();
         let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
         let color = world.color_at(&ray);
         assert_eq!(
@@ -219,29 +1129,775 @@ mod tests {
 
     #[test]
     fn is_shadowed_nothing_collinear_with_point_and_light() {
-        let world = test_world();
+        let world = World::default_world();
         let point = Tuple::point(0.0, 10.0, 0.0);
         assert!(!world.is_shadowed(0, point));
     }
 
     #[test]
     fn is_shadowed_object_between_point_and_light() {
-        let world = test_world();
+        let world = World::default_world();
         let point = Tuple::point(10.0, -10.0, 10.0);
         assert!(world.is_shadowed(0, point));
     }
 
     #[test]
     fn is_shadowed_object_behind_light() {
-        let world = test_world();
+        let world = World::default_world();
         let point = Tuple::point(-20.0, 20.0, -20.0);
         assert!(!world.is_shadowed(0, point));
     }
 
+    #[test]
+    fn is_shadowed_object_not_casting_shadow() {
+        let mut world = World::default_world();
+        for shape in world.shapes_mut() {
+            let mut sphere = Sphere::new();
+            sphere.set_transform(shape.transform().clone());
+            sphere.set_material(Material {
+                casts_shadow: false,
+                ..shape.material().clone()
+            });
+            *shape = Rc::new(sphere);
+        }
+        let point = Tuple::point(10.0, -10.0, 10.0);
+        assert!(!world.is_shadowed(0, point));
+    }
+
     #[test]
     fn is_shadowed_object_behind_point() {
-        let world = test_world();
+        let world = World::default_world();
         let point = Tuple::point(-2.0, 2.0, -2.0);
         assert!(!world.is_shadowed(0, point));
     }
+
+    #[test]
+    fn any_hit_before() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert!(world.any_hit_before(&ray, 4.5));
+        assert!(!world.any_hit_before(&ray, 4.0));
+        assert!(!world.any_hit_before(&ray, 3.0));
+        let behind = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, -1.0));
+        assert!(!world.any_hit_before(&behind, f64::INFINITY));
+    }
+
+    #[test]
+    fn is_shadowed_matches_full_intersection_list() {
+        let mut world = World::default_world();
+        let mut glass = Sphere::new();
+        glass.set_transform(Matrix::translation(-3.0, 3.0, -3.0));
+        glass.set_material(Material {
+            casts_shadow: false,
+            ..Material::default()
+        });
+        world.add_shape(Rc::new(glass));
+        let light = world.lights()[0].position();
+
+        let mut checked = 0;
+        let mut shadowed = 0;
+        for x in -10..=10 {
+            for y in -10..=10 {
+                for &z in &[-5.0, -1.5, 0.0, 2.0, 10.0] {
+                    let point = Tuple::point(x as f64 * 0.5, y as f64 * 0.5, z);
+                    let v = light - point;
+                    let distance = v.norm();
+                    let ray = Ray::new(point, v / distance);
+                    let expected = world.intersect(&ray).iter().any(|i| {
+                        i.t() > 0.0 && i.t() < distance && i.shape().material().casts_shadow
+                    });
+                    assert_eq!(world.is_shadowed(0, point), expected, "at {:?}", point);
+                    checked += 1;
+                    if expected {
+                        shadowed += 1;
+                    }
+                }
+            }
+        }
+        assert!(shadowed > 0 && shadowed < checked);
+    }
+
+    #[test]
+    fn in_scattering_lit_and_shadowed() {
+        let mut world = World::builder()
+            .shape_rc(plane_with(
+                Matrix::translation(0.0, 3.0, 0.0),
+                Material::default(),
+            ))
+            .light(PointLight::new(Tuple::point(0.0, 5.0, 0.0), Color::WHITE).with_intensity(10.0))
+            .build();
+        world.set_scattering(0.05, 32);
+        assert_eq!(world.scattering_density(), 0.05);
+        assert_eq!(world.scattering_steps(), 32);
+
+        // Passes just below the light, above the plane.
+        let lit = Ray::new(Tuple::point(-5.0, 4.5, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+        // Runs under the plane, which blocks the light everywhere.
+        let shadowed = Ray::new(Tuple::point(-5.0, 0.0, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+
+        let lit = world.in_scattering(&lit, 32);
+        let shadowed = world.in_scattering(&shadowed, 32);
+        assert!(lit.r() > 0.1, "lit {:?}", lit);
+        assert_eq!(shadowed, Color::BLACK);
+    }
+
+    #[test]
+    fn in_scattering_stops_at_hit() {
+        let mut world = World::default_world();
+        world.set_scattering(0.1, 10);
+        let short = Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, 0.0, 1.0));
+        let long = Ray::new(Tuple::point(0.0, 0.0, -6.0), Tuple::vector(0.0, 0.0, 1.0));
+        let short = world.in_scattering(&short, 10);
+        let long = world.in_scattering(&long, 10);
+        assert!(short.r() > 0.0);
+        assert!((long.r() / short.r() - 2.5).abs() < 1e-9);
+        assert_eq!(
+            world.in_scattering(&Ray::new(Tuple::ORIGIN, Tuple::UP), 0),
+            Color::BLACK
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "scattering density should not be negative, got -0.1")]
+    fn set_scattering_negative_density() {
+        World::new().set_scattering(-0.1, 4);
+    }
+
+    #[test]
+    fn huge_floor_not_self_shadowed() {
+        // The top of a sphere with radius 1e11 acts as the floor. Far from the origin, a fixed
+        // offset of EPSILON is smaller than the rounding error of the hit points.
+        let radius = 1e11;
+        let mut world = World::new();
+        let mut floor = Sphere::new();
+        floor.set_transform(
+            Matrix::translation(0.0, -radius, 0.0) * &Matrix::scaling(radius, radius, radius),
+        );
+        world.add_shape(Rc::new(floor));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(0.0, 100.0 * radius, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+
+        let n = 20;
+        for i in 0..n {
+            let x = (i as f64 / n as f64 - 0.5) * 0.6 * radius;
+            let z = ((i * 19 % n) as f64 / n as f64 - 0.5) * 0.6 * radius;
+            let ray = Ray::new(
+                Tuple::point(x, 0.5 * radius, z),
+                Tuple::vector(0.0, -1.0, 0.0),
+            );
+            let rec = world.hit(&ray).unwrap();
+            assert!(!world.is_shadowed(0, rec.over_point()));
+        }
+    }
+
+    fn lit_floor() -> World {
+        let mut world = World::new();
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        world.add_shape(Rc::new(Plane::new()));
+        world
+    }
+
+    fn floor_hit(world: &World, x: f64) -> HitRecord {
+        let ray = Ray::new(Tuple::point(x, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        world.hit(&ray).unwrap()
+    }
+
+    #[test]
+    fn ambient_occlusion_off_by_default() {
+        let mut world = lit_floor();
+        let mut wall = Plane::new();
+        wall.set_transform(Matrix::rotation_z(PI / 2.0));
+        world.add_shape(Rc::new(wall));
+        let rec = floor_hit(&world, 0.01);
+        assert_eq!(world.ao_samples(), 0);
+        assert_eq!(world.ambient_occlusion(&rec), 0.0);
+    }
+
+    #[test]
+    fn ambient_occlusion_open_area() {
+        let mut world = lit_floor();
+        world.set_ambient_occlusion(64, 1.0);
+        let rec = floor_hit(&world, 0.0);
+        assert_eq!(world.ambient_occlusion(&rec), 0.0);
+
+        let mut plain = World::new();
+        plain.add_light(Rc::clone(&world.lights()[0]));
+        plain.add_shape(Rc::clone(&world.shapes()[0]));
+        let ray = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        assert_eq!(world.color_at(&ray), plain.color_at(&ray));
+    }
+
+    #[test]
+    fn ambient_occlusion_crevice() {
+        // Two walls close together on either side of the hit, forming a narrow trench.
+        let mut world = lit_floor();
+        for &x in &[-0.1, 0.1] {
+            let mut wall = Plane::new();
+            wall.set_transform(Matrix::translation(x, 0.0, 0.0) * &Matrix::rotation_z(PI / 2.0));
+            world.add_shape(Rc::new(wall));
+        }
+        world.set_ambient_occlusion(64, 1.0);
+        let rec = floor_hit(&world, 0.0);
+        let occlusion = world.ambient_occlusion(&rec);
+        assert!(occlusion > 0.5, "occlusion {}", occlusion);
+
+        let ray = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        let darkened = world.color_at(&ray);
+        world.set_ambient_occlusion(0, 1.0);
+        let plain = world.color_at(&ray);
+        assert!(darkened.r() < plain.r());
+        assert!((plain.r() - darkened.r() - 0.1 * occlusion).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "ambient occlusion radius should be positive, got 0")]
+    fn set_ambient_occlusion_zero_radius() {
+        World::new().set_ambient_occlusion(4, 0.0);
+    }
+
+    #[test]
+    fn shadow_fraction_single_sample_matches_is_shadowed() {
+        let mut world = World::default_world();
+        world.set_soft_shadows(1, 2.0);
+        let points = [
+            Tuple::point(0.0, 10.0, 0.0),
+            Tuple::point(10.0, -10.0, 10.0),
+            Tuple::point(-20.0, 20.0, -20.0),
+            Tuple::point(-2.0, 2.0, -2.0),
+        ];
+        for &point in &points {
+            let expected = if world.is_shadowed(0, point) {
+                1.0
+            } else {
+                0.0
+            };
+            assert_eq!(world.shadow_fraction(0, point), expected);
+        }
+    }
+
+    #[test]
+    fn shadow_fraction_penumbra() {
+        let mut world = World::default_world();
+        world.set_soft_shadows(16, 5.0);
+
+        // Right behind the spheres, every shadow ray is blocked.
+        assert_eq!(world.shadow_fraction(0, Tuple::point(0.7, -0.7, 0.7)), 1.0);
+        // Nothing is near the path to the light.
+        assert_eq!(world.shadow_fraction(0, Tuple::point(0.0, 10.0, 0.0)), 0.0);
+        // Just beside the hard shadow, only some rays are blocked.
+        let fraction = world.shadow_fraction(0, Tuple::point(11.4, -8.6, 10.0));
+        assert!(fraction > 0.0 && fraction < 1.0, "got {}", fraction);
+    }
+
+    #[test]
+    #[should_panic(expected = "soft shadows need at least 1 sample")]
+    fn set_soft_shadows_zero_samples() {
+        World::new().set_soft_shadows(0, 1.0);
+    }
+
+    fn assert_color_eq(actual: Color, expected: Color) {
+        for i in 0..3 {
+            assert!(
+                (actual[i] - expected[i]).abs() < 0.0001,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    fn plane_with(transform: Matrix, material: Material) -> Rc<dyn Shape> {
+        let mut plane = Plane::new();
+        plane.set_transform(transform);
+        plane.set_material(material);
+        Rc::new(plane)
+    }
+
+    fn diagonal_ray() -> Ray {
+        let v = 2.0_f64.sqrt() / 2.0;
+        Ray::new(Tuple::point(0.0, 0.0, -3.0), Tuple::vector(0.0, -v, v))
+    }
+
+    #[test]
+    fn reflected_color_nonreflective() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = world.hit(&ray).unwrap();
+        assert_eq!(world.reflected_color(&rec, MAX_DEPTH), Color::BLACK);
+    }
+
+    #[test]
+    fn reflected_color_reflective() {
+        let mut world = World::default_world();
+        world.add_shape(plane_with(
+            Matrix::translation(0.0, -1.0, 0.0),
+            Material::builder().reflective(0.5).build(),
+        ));
+        let rec = world.hit(&diagonal_ray()).unwrap();
+        assert_color_eq(
+            world.reflected_color(&rec, MAX_DEPTH),
+            Color::new(0.19032, 0.2379, 0.14274),
+        );
+        assert_eq!(world.reflected_color(&rec, 0), Color::BLACK);
+    }
+
+    #[test]
+    fn color_at_reflective() {
+        let mut world = World::default_world();
+        world.add_shape(plane_with(
+            Matrix::translation(0.0, -1.0, 0.0),
+            Material::builder().reflective(0.5).build(),
+        ));
+        assert_color_eq(
+            world.color_at(&diagonal_ray()),
+            Color::new(0.87677, 0.92436, 0.82918),
+        );
+    }
+
+    #[test]
+    fn reflection_not_tinted_by_surface_color() {
+        let mut world = World::new();
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(-10.0, 10.0, -10.0),
+            Color::WHITE,
+        )));
+        world.add_shape(plane_with(
+            Matrix::translation(0.0, -1.0, 0.0),
+            Material::with_color(Color::GREEN),
+        ));
+        let mut sphere = Sphere::new();
+        sphere.set_material(
+            Material::builder()
+                .color(Color::RED)
+                .reflective(0.5)
+                .build(),
+        );
+        world.add_shape(Rc::new(sphere));
+
+        // Hits the lower half of the sphere, and reflects down onto the floor.
+        let ray = Ray::new(Tuple::point(0.0, -0.5, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = world.hit(&ray).unwrap();
+        assert!(Rc::ptr_eq(&rec.shape(), &world.shapes()[1]));
+
+        let reflected = world.reflected_color(&rec, MAX_DEPTH);
+        // The floor's specular highlight is white, but far too faint to show here.
+        assert!(reflected.g() > 0.01);
+        assert!(reflected.r() < 1e-9);
+        assert!(reflected.b() < 1e-9);
+
+        let surface = world.shade_hit(0, HitRecord::clone(&rec));
+        assert_eq!(surface.g(), 0.0);
+        assert_color_eq(world.color_at(&ray), surface + reflected);
+    }
+
+    #[test]
+    fn color_at_mutually_reflective_surfaces() {
+        let mut world = World::new();
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(0.0, 0.0, 0.0),
+            Color::WHITE,
+        )));
+        let mirror = Material::builder().reflective(1.0).build();
+        world.add_shape(plane_with(
+            Matrix::translation(0.0, -1.0, 0.0),
+            mirror.clone(),
+        ));
+        world.add_shape(plane_with(
+            Matrix::translation(0.0, 1.0, 0.0) * &Matrix::rotation_x(std::f64::consts::PI),
+            mirror,
+        ));
+        let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+        world.color_at(&ray);
+    }
+
+    #[test]
+    fn refracted_color_opaque() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = world.hit(&ray).unwrap();
+        assert_eq!(world.refracted_color(&rec, MAX_DEPTH), Color::BLACK);
+    }
+
+    #[test]
+    fn refracted_color_max_depth_and_total_internal_reflection() {
+        let mut world = World::default_world();
+        let mut glass = Sphere::new();
+        glass.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..world.shapes()[0].material().clone()
+        });
+        world.shapes_mut()[0] = Rc::new(glass);
+
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = world.hit(&ray).unwrap();
+        assert_eq!(world.refracted_color(&rec, 0), Color::BLACK);
+
+        let v = 2.0_f64.sqrt() / 2.0;
+        let shape = Rc::clone(&world.shapes()[0]);
+        let ray = Ray::new(Tuple::point(0.0, 0.0, v), Tuple::vector(0.0, 1.0, 0.0));
+        let intersections = vec![
+            Intersection::new(-v, Rc::clone(&shape)),
+            Intersection::new(v, shape),
+        ];
+        let rec = HitRecord::with_intersections(&intersections[1], &ray, &intersections);
+        assert_eq!(world.refracted_color(&rec, MAX_DEPTH), Color::BLACK);
+    }
+
+    fn floor_and_ball(floor: Material) -> World {
+        let mut world = World::default_world();
+        world.add_shape(plane_with(Matrix::translation(0.0, -1.0, 0.0), floor));
+        let mut ball = Sphere::new();
+        ball.set_transform(Matrix::translation(0.0, -3.5, -0.5));
+        ball.set_material(Material::builder().color(Color::RED).ambient(0.5).build());
+        world.add_shape(Rc::new(ball));
+        world
+    }
+
+    #[test]
+    fn color_at_transparent() {
+        let world = floor_and_ball(
+            Material::builder()
+                .transparency(0.5)
+                .refractive_index(1.5)
+                .build(),
+        );
+        assert_color_eq(
+            world.color_at(&diagonal_ray()),
+            Color::new(0.93642, 0.68642, 0.68642),
+        );
+    }
+
+    #[test]
+    fn color_at_reflective_transparent() {
+        let world = floor_and_ball(
+            Material::builder()
+                .reflective(0.5)
+                .transparency(0.5)
+                .refractive_index(1.5)
+                .build(),
+        );
+        assert_color_eq(
+            world.color_at(&diagonal_ray()),
+            Color::new(0.93391, 0.69643, 0.69243),
+        );
+    }
+
+    #[test]
+    fn glass_sphere_over_checker_floor() {
+        let glass_world = |transparency: f64, reflective: f64| {
+            let mut world = World::new();
+            world.set_check_energy(true);
+            world.add_light(Rc::new(PointLight::new(
+                Tuple::point(-10.0, 10.0, -10.0),
+                Color::WHITE,
+            )));
+            world.add_shape(plane_with(
+                Matrix::translation(0.0, -1.0, 0.0),
+                Material::builder()
+                    .texture(Rc::new(Checker2d::colors(Color::WHITE, Color::gray(0.3))))
+                    .specular(0.0)
+                    .build(),
+            ));
+            let mut sphere = Sphere::new();
+            sphere.set_material(
+                Material::builder()
+                    .color(Color::BLACK)
+                    .ambient(0.0)
+                    .diffuse(0.1)
+                    .specular(1.0)
+                    .reflective(reflective)
+                    .transparency(transparency)
+                    .refractive_index(1.5)
+                    .casts_shadow(false)
+                    .build(),
+            );
+            world.add_shape(Rc::new(sphere));
+            world
+        };
+        let glass = glass_world(0.9, 0.9);
+        let opaque = glass_world(0.0, 0.0);
+
+        // Looking down at the sphere, the floor can be seen through it.
+        let ray = Ray::new(Tuple::point(0.0, 1.0, -5.0), Tuple::vector(0.0, -0.3, 1.0));
+        let rec = glass.hit(&ray).unwrap();
+        assert!(Rc::ptr_eq(&rec.shape(), &glass.shapes()[1]));
+        let refracted = glass.refracted_color(&rec, MAX_DEPTH);
+        assert!(refracted.luminance() > 0.0);
+        let opaque_color = opaque.color_at(&ray);
+        assert!(glass.color_at(&ray).luminance() > opaque_color.luminance());
+
+        // At a grazing angle, the floor is reflected.
+        let ray = Ray::new(Tuple::point(0.0, -0.2, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let rec = glass.hit(&ray).unwrap();
+        assert!(glass.reflected_color(&rec, MAX_DEPTH).luminance() > 0.0);
+    }
+
+    #[test]
+    fn hit_refractive_indices_overlapping_spheres() {
+        let glass = |z: f64, refractive_index: f64| {
+            let mut sphere = Sphere::new();
+            sphere.set_transform(Matrix::translation(0.0, 0.0, z));
+            sphere.set_material(Material {
+                transparency: 1.0,
+                refractive_index,
+                ..Material::default()
+            });
+            Rc::new(sphere)
+        };
+        let mut world = World::new();
+        world.add_shape(glass(-0.5, 1.5));
+        world.add_shape(glass(0.5, 2.0));
+
+        // The ray enters the first sphere at z = -1.5, the second at z = -0.5, leaves the first at
+        // z = 0.5 and the second at z = 1.5. Start just before each of those.
+        for &(z, n1, n2) in &[
+            (-4.0, 1.0, 1.5),
+            (-1.0, 1.5, 2.0),
+            (0.0, 2.0, 2.0),
+            (1.0, 2.0, 1.0),
+        ] {
+            let ray = Ray::new(Tuple::point(0.0, 0.0, z), Tuple::vector(0.0, 0.0, 1.0));
+            let rec = world.hit(&ray).unwrap();
+            assert_eq!((rec.n1(), rec.n2()), (n1, n2), "ray from z = {}", z);
+        }
+    }
+
+    #[test]
+    fn clone_shares_shapes_and_settings() {
+        let mut world = World::default_world();
+        world.set_soft_shadows(4, 0.5);
+        let clone = world.clone();
+        assert_eq!(clone.shapes().len(), 2);
+        assert!(Rc::ptr_eq(&clone.shapes()[0], &world.shapes()[0]));
+        assert!(Rc::ptr_eq(&clone.lights()[0], &world.lights()[0]));
+        assert_eq!(clone.soft_shadow_samples(), 4);
+
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        world.color_at(&ray);
+        assert_eq!(clone.stats(), RenderStats::default());
+        assert_eq!(clone.color_at(&ray), world.color_at(&ray));
+    }
+
+    #[test]
+    fn with_shape_transform_leaves_original() {
+        let world = World::default_world();
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let before = world.color_at(&ray);
+
+        let moved = world.with_shape_transform(0, Matrix::translation(0.0, 10.0, 0.0));
+        assert_eq!(
+            moved.shapes()[0].transform(),
+            &Matrix::translation(0.0, 10.0, 0.0)
+        );
+        assert_eq!(
+            moved.shapes()[0].material().texture.color_at(Tuple::ORIGIN),
+            world.shapes()[0].material().texture.color_at(Tuple::ORIGIN)
+        );
+        assert!(Rc::ptr_eq(&moved.shapes()[1], &world.shapes()[1]));
+        // Only the inner sphere is hit now.
+        assert_eq!(moved.intersect(&ray).ts(), [4.5, 5.5]);
+
+        assert_eq!(world.shapes()[0].transform(), &Matrix::eye());
+        assert_eq!(world.intersect(&ray).ts(), [4.0, 4.5, 5.5, 6.0]);
+        assert_eq!(world.color_at(&ray), before);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't copy shape Group at index 0")]
+    fn with_shape_transform_group() {
+        let mut world = World::new();
+        world.add_shape(Rc::new(Group::new()));
+        world.with_shape_transform(0, Matrix::eye());
+    }
+
+    #[test]
+    fn disabled_shape_shows_background() {
+        let mut world = World::new();
+        world.set_background(Rc::new(SolidBackground(Color::BLUE)));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(-10.0, 10.0, -10.0),
+            Color::WHITE,
+        )));
+        world.add_shape(Rc::new(Sphere::new()));
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert_ne!(world.color_at(&ray), Color::BLUE);
+
+        world.set_shape_enabled(0, false);
+        assert_eq!(world.color_at(&ray), Color::BLUE);
+        assert_eq!(world.shapes().len(), 1);
+
+        world.set_shape_enabled(0, true);
+        assert_ne!(world.color_at(&ray), Color::BLUE);
+    }
+
+    #[test]
+    fn disabled_shape_casts_no_shadow() {
+        let mut world = World::default_world();
+        let point = Tuple::point(10.0, -10.0, 10.0);
+        assert!(world.is_shadowed(0, point));
+        world.set_shape_enabled(0, false);
+        world.set_shape_enabled(1, false);
+        assert!(!world.is_shadowed(0, point));
+    }
+
+    #[test]
+    fn enabled_flags_follow_shapes() {
+        let mut world = World::new();
+        world.add_shapes((0..3).map(|_| Rc::new(Sphere::new()) as Rc<dyn Shape>));
+        world.set_shape_enabled(1, false);
+        world.remove_shape(0);
+        assert!(!world.is_shape_enabled(0));
+        assert!(world.is_shape_enabled(1));
+        world.add_shape(Rc::new(Sphere::new()));
+        assert!(world.is_shape_enabled(world.shapes().len() - 1));
+
+        let clone = world.clone();
+        assert!(!clone.is_shape_enabled(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "shape index 2 out of bounds for 2 shapes")]
+    fn set_shape_enabled_out_of_bounds() {
+        World::default_world().set_shape_enabled(2, false);
+    }
+
+    #[test]
+    fn shadow_rays_use_time_of_ray() {
+        use crate::shape::MovingSphere;
+
+        let mut world = World::new();
+        world.add_shape(Rc::new(Plane::new()));
+        world.add_shape(Rc::new(MovingSphere::new(
+            Matrix::translation(0.0, 1.0, 0.0),
+            Matrix::translation(10.0, 1.0, 0.0),
+        )));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(10.0, 10.0, 0.0),
+            Color::WHITE,
+        )));
+
+        // The sphere only shadows the floor below it at time 1.
+        let origin = Tuple::point(10.0, 0.5, 5.0);
+        let direction = Tuple::point(10.0, 0.0, 0.5) - origin;
+        let lit = world.color_at(&Ray::with_time(origin, direction, 0.0));
+        let shadowed = world.color_at(&Ray::with_time(origin, direction, 1.0));
+        assert!(
+            shadowed.r() < lit.r(),
+            "{} should be darker than {}",
+            shadowed,
+            lit
+        );
+
+        let rec = world.hit(&Ray::with_time(origin, direction, 1.0)).unwrap();
+        assert_eq!(rec.time(), 1.0);
+    }
+
+    #[test]
+    fn reflected_rays_use_time_of_ray() {
+        use crate::shape::MovingSphere;
+
+        let mut world = World::new();
+        let mut floor = Plane::new();
+        floor.set_material(Material::builder().reflective(1.0).build());
+        world.add_shape(Rc::new(floor));
+        world.add_shape(Rc::new(MovingSphere::new(
+            Matrix::translation(5.0, 1.0, 50.0),
+            Matrix::translation(5.0, 1.0, 0.0),
+        )));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(0.0, 10.0, 0.0),
+            Color::WHITE,
+        )));
+
+        // The ray bounces off the floor at the origin, towards where the sphere is at time 1.
+        let origin = Tuple::point(-5.0, 1.0, 0.0);
+        let direction = Tuple::vector(1.0, -0.2, 0.0);
+        let rec = world.hit(&Ray::with_time(origin, direction, 0.0)).unwrap();
+        assert_eq!(world.reflected_color(&rec, 1), Color::BLACK);
+        let rec = world.hit(&Ray::with_time(origin, direction, 1.0)).unwrap();
+        assert!(world.reflected_color(&rec, 1).r() > 0.0);
+    }
+
+    #[test]
+    fn triangle_uv_texture() {
+        use crate::shape::Triangle;
+        use crate::texture::Texture;
+
+        let mut texture = Checker2d::colors(Color::WHITE, Color::BLACK);
+        texture.set_transform(Matrix::scaling(0.5, 1.0, 0.5));
+        let mut triangle = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+        );
+        triangle.set_material(
+            Material::builder()
+                .texture(Rc::new(texture))
+                .ambient(1.0)
+                .diffuse(0.0)
+                .specular(0.0)
+                .build(),
+        );
+        let mut world = World::new();
+        world.add_shape(Rc::new(triangle));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(0.0, 0.0, -10.0),
+            Color::WHITE,
+        )));
+
+        // Both points have the same x, so a lookup by position gives the same color. Their
+        // (u, v) are (0.5, 0.3) and (0.3, 0.1), which are on different checkers.
+        let color_at = |x, y| {
+            let ray = Ray::new(Tuple::point(x, y, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+            world.color_at(&ray)
+        };
+        assert_eq!(color_at(-0.2, 0.2), Color::BLACK);
+        assert_eq!(color_at(-0.2, 0.6), Color::WHITE);
+    }
+
+    #[test]
+    fn emission_added_once() {
+        let emission = Color::gray(0.5);
+        let mut sphere = Sphere::new();
+        sphere.set_material(
+            Material::builder()
+                .ambient(0.0)
+                .diffuse(0.0)
+                .specular(0.0)
+                .emission(emission)
+                .build(),
+        );
+        let mut world = World::new();
+        world.add_shape(Rc::new(sphere));
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert_eq!(world.color_at(&ray), emission);
+
+        for x in [-10.0, 10.0].iter() {
+            world.add_light(Rc::new(PointLight::new(
+                Tuple::point(*x, 10.0, -10.0),
+                Color::WHITE,
+            )));
+        }
+        assert_eq!(world.color_at(&ray), emission);
+    }
+
+    #[test]
+    fn plane_lit_from_below() {
+        let mut world = World::new();
+        world.add_shape(Rc::new(Plane::new()));
+        world.add_light(Rc::new(PointLight::new(
+            Tuple::point(0.0, -10.0, 0.0),
+            Color::WHITE,
+        )));
+
+        // The eye and the light are both below the plane, on the side its normal points away from.
+        let ray = Ray::new(Tuple::point(0.0, -1.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+        assert_eq!(world.color_at(&ray), Color::gray(1.9));
+    }
 }
@@ -0,0 +1,545 @@
+//! Saving and loading worlds in a compact binary format.
+//!
+//! The format starts with the magic bytes `TRST` and a version number. Then follow the soft
+//! shadow, ambient occlusion and scattering settings, the lights and the shapes. All numbers are
+//! little endian. Every shape is stored as a type tag, its transform, its material and whether it
+//! is enabled. The type tags are fixed constants, independent of [Shape::name]. The boolean
+//! properties of a material are stored as bit flags in one byte, for now only bit 0 for casting
+//! shadows. The emission color follows it. Only spheres and planes with solid color textures are
+//! supported for now.
+//!
+//! The background is not saved, because backgrounds can't be inspected. Loaded worlds have the
+//! default black background, so set it again after loading.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+
+use crate::color::Color;
+use crate::light::PointLight;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::shape::{Plane, Shape, ShapeKind, Sphere};
+use crate::texture::{solid_color::SolidColor, TextureKind};
+use crate::tuple::Tuple;
+
+use super::World;
+
+const MAGIC: &[u8; 4] = b"TRST";
+const VERSION: u32 = 1;
+
+// The type tags are part of the format, so they must never change.
+const SPHERE_TAG: u8 = 0;
+const PLANE_TAG: u8 = 1;
+
+/// The error returned by [World::save] and [World::load].
+#[derive(Debug)]
+pub enum SceneError {
+    /// Reading or writing failed.
+    Io(io::Error),
+    /// The data does not start with the magic bytes, so it is not a saved world.
+    InvalidHeader,
+    /// The data was saved with a version of the format which is not supported.
+    UnsupportedVersion(u32),
+    /// A shape can't be saved, because its type is not supported.
+    UnsupportedShape(&'static str),
+    /// A material can't be saved, because its texture is not a solid color, or it has a normal
+    /// texture.
+    UnsupportedTexture(&'static str),
+    /// The data contains an unknown shape type tag.
+    UnknownShapeTag(u8),
+}
+
+impl Display for SceneError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::InvalidHeader => write!(f, "not a saved world"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            Self::UnsupportedShape(name) => write!(f, "can't save shapes of type {}", name),
+            Self::UnsupportedTexture(name) => write!(f, "can't save textures of type {}", name),
+            Self::UnknownShapeTag(tag) => write!(f, "unknown shape tag {}", tag),
+        }
+    }
+}
+
+impl Error for SceneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SceneError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl World {
+    /// Writes `self` to `writer` in a compact binary format, which can be read back with
+    /// [World::load]. The format is versioned, so old data can be detected. Only spheres and
+    /// planes with solid color textures and without normal textures are supported for now; other
+    /// shapes and textures give an error. The background is not saved, because backgrounds can't
+    /// be inspected. Loaded worlds have the default black background.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use truster::world::World;
+    /// let world = World::default_world();
+    /// let mut bytes = Vec::new();
+    /// world.save(&mut bytes).unwrap();
+    ///
+    /// let loaded = World::load(&mut bytes.as_slice()).unwrap();
+    /// assert_eq!(loaded.shapes().len(), 2);
+    /// assert_eq!(loaded.lights().len(), 1);
+    /// ```
+    pub fn save(&self, writer: &mut dyn Write) -> Result<(), SceneError> {
+        writer.write_all(MAGIC)?;
+        write_u32(writer, VERSION)?;
+
+        write_u32(writer, self.soft_shadow_samples as u32)?;
+        write_f64(writer, self.soft_shadow_radius)?;
+        write_u32(writer, self.ao_samples as u32)?;
+        write_f64(writer, self.ao_radius)?;
+        write_f64(writer, self.scattering_density)?;
+        write_u32(writer, self.scattering_steps as u32)?;
+
+        write_u32(writer, self.lights.len() as u32)?;
+        for light in self.lights.iter() {
+            write_tuple(writer, light.position())?;
+            write_color(writer, light.color())?;
+            let (constant, linear, quadratic) = light.attenuation();
+            for &value in &[constant, linear, quadratic, light.intensity()] {
+                write_f64(writer, value)?;
+            }
+        }
+
+        write_u32(writer, self.shapes.len() as u32)?;
+        for (shape, &enabled) in self.shapes.iter().zip(self.enabled.iter()) {
+            let tag = match shape.kind() {
+                Some(ShapeKind::Sphere) => SPHERE_TAG,
+                Some(ShapeKind::Plane) => PLANE_TAG,
+                _ => return Err(SceneError::UnsupportedShape(shape.name())),
+            };
+            writer.write_all(&[tag])?;
+            for i in 0..4 {
+                for j in 0..4 {
+                    write_f64(writer, shape.transform()[[i, j]])?;
+                }
+            }
+            write_material(writer, shape.material())?;
+            writer.write_all(&[enabled as u8])?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a world from `reader`, which was written by [World::save].
+    pub fn load(reader: &mut dyn Read) -> Result<Self, SceneError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SceneError::InvalidHeader);
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(SceneError::UnsupportedVersion(version));
+        }
+
+        let mut world = Self::new();
+        world.soft_shadow_samples = read_u32(reader)? as usize;
+        world.soft_shadow_radius = read_f64(reader)?;
+        world.ao_samples = read_u32(reader)? as usize;
+        world.ao_radius = read_f64(reader)?;
+        world.scattering_density = read_f64(reader)?;
+        world.scattering_steps = read_u32(reader)? as usize;
+
+        for _ in 0..read_u32(reader)? {
+            let position = read_tuple(reader, 1.0)?;
+            let color = read_color(reader)?;
+            let constant = read_f64(reader)?;
+            let linear = read_f64(reader)?;
+            let quadratic = read_f64(reader)?;
+            let intensity = read_f64(reader)?;
+            world.add_light(Rc::new(
+                PointLight::new(position, color)
+                    .with_attenuation(constant, linear, quadratic)
+                    .with_intensity(intensity),
+            ));
+        }
+
+        for _ in 0..read_u32(reader)? {
+            let mut tag = [0];
+            reader.read_exact(&mut tag)?;
+            let mut shape: Box<dyn Shape> = match tag[0] {
+                SPHERE_TAG => Box::new(Sphere::new()),
+                PLANE_TAG => Box::new(Plane::new()),
+                tag => return Err(SceneError::UnknownShapeTag(tag)),
+            };
+            let mut data = [0.0; 16];
+            for value in data.iter_mut() {
+                *value = read_f64(reader)?;
+            }
+            shape.set_transform(Matrix::new(&data));
+            shape.set_material(read_material(reader)?);
+            world.add_shape(Rc::from(shape));
+            let mut enabled = [0];
+            reader.read_exact(&mut enabled)?;
+            world.set_shape_enabled(world.shapes.len() - 1, enabled[0] != 0);
+        }
+
+        Ok(world)
+    }
+}
+
+fn write_material(writer: &mut dyn Write, material: &Material) -> Result<(), SceneError> {
+    if material.texture.kind() != Some(TextureKind::SolidColor) {
+        return Err(SceneError::UnsupportedTexture(material.texture.name()));
+    }
+    if let Some(normal_texture) = &material.normal_texture {
+        return Err(SceneError::UnsupportedTexture(normal_texture.name()));
+    }
+    write_color(
+        writer,
+        material.texture.color_at(Tuple::point(0.0, 0.0, 0.0)),
+    )?;
+    for &value in &[
+        material.ambient,
+        material.diffuse,
+        material.specular,
+        material.shininess,
+        material.reflective,
+        material.transparency,
+        material.refractive_index,
+    ] {
+        write_f64(writer, value)?;
+    }
+    let flags = material.casts_shadow as u8;
+    writer.write_all(&[flags])?;
+    write_color(writer, material.emission)?;
+    Ok(())
+}
+
+fn read_material(reader: &mut dyn Read) -> Result<Material, SceneError> {
+    let color = read_color(reader)?;
+    let mut material = Material {
+        texture: Rc::new(SolidColor::new(color)),
+        ambient: read_f64(reader)?,
+        diffuse: read_f64(reader)?,
+        specular: read_f64(reader)?,
+        shininess: read_f64(reader)?,
+        reflective: read_f64(reader)?,
+        transparency: read_f64(reader)?,
+        refractive_index: read_f64(reader)?,
+        casts_shadow: false,
+        normal_texture: None,
+        emission: Color::BLACK,
+    };
+    let mut flags = [0];
+    reader.read_exact(&mut flags)?;
+    material.casts_shadow = flags[0] & 1 != 0;
+    material.emission = read_color(reader)?;
+    Ok(material)
+}
+
+fn write_u32(writer: &mut dyn Write, value: u32) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_f64(writer: &mut dyn Write, value: f64) -> io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_tuple(writer: &mut dyn Write, tuple: Tuple) -> io::Result<()> {
+    write_f64(writer, tuple.x())?;
+    write_f64(writer, tuple.y())?;
+    write_f64(writer, tuple.z())
+}
+
+fn write_color(writer: &mut dyn Write, color: Color) -> io::Result<()> {
+    write_f64(writer, color.r())?;
+    write_f64(writer, color.g())?;
+    write_f64(writer, color.b())
+}
+
+fn read_u32(reader: &mut dyn Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_f64(reader: &mut dyn Read) -> io::Result<f64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(f64::from_le_bytes(bytes))
+}
+
+fn read_tuple(reader: &mut dyn Read, w: f64) -> io::Result<Tuple> {
+    Ok(Tuple::new(
+        read_f64(reader)?,
+        read_f64(reader)?,
+        read_f64(reader)?,
+        w,
+    ))
+}
+
+fn read_color(reader: &mut dyn Read) -> io::Result<Color> {
+    Ok(Color::new(
+        read_f64(reader)?,
+        read_f64(reader)?,
+        read_f64(reader)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::background::SolidBackground;
+    use crate::ray::Ray;
+    use crate::shape::Group;
+    use crate::texture::stripe::Stripe;
+
+    fn round_trip(world: &World) -> World {
+        let mut bytes = Vec::new();
+        world.save(&mut bytes).unwrap();
+        World::load(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn round_trip_intersections() {
+        let mut world = World::new();
+        let mut sphere1 = Sphere::new();
+        sphere1.set_transform(Matrix::translation(1.0, 0.5, 2.0) * &Matrix::scaling(2.0, 1.0, 1.0));
+        sphere1.set_material(
+            Material::builder()
+                .color(Color::RED)
+                .reflective(0.3)
+                .build(),
+        );
+        let mut sphere2 = Sphere::new();
+        sphere2.set_transform(Matrix::rotation_x(0.3) * &Matrix::translation(0.0, 0.0, -3.0));
+        world.add_shape(Rc::new(sphere1));
+        world.add_shape(Rc::new(sphere2));
+        world.add_light(Rc::new(
+            PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::WHITE)
+                .with_attenuation(1.0, 0.1, 0.01),
+        ));
+
+        let loaded = round_trip(&world);
+
+        for direction in &[
+            Tuple::vector(0.0, 0.0, 1.0),
+            Tuple::vector(0.1, 0.05, 1.0).normalized(),
+            Tuple::vector(-0.2, 0.0, 1.0).normalized(),
+        ] {
+            let ray = Ray::new(Tuple::point(0.0, 0.0, -10.0), *direction);
+            let expected: Vec<f64> = world.intersect(&ray).iter().map(|i| i.t()).collect();
+            let actual: Vec<f64> = loaded.intersect(&ray).iter().map(|i| i.t()).collect();
+            assert_eq!(actual, expected);
+            assert_eq!(loaded.color_at(&ray), world.color_at(&ray));
+        }
+    }
+
+    #[test]
+    fn round_trip_default_world() {
+        let world = World::default_world();
+        let loaded = round_trip(&world);
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert_eq!(loaded.color_at(&ray), world.color_at(&ray));
+        assert_eq!(loaded.shapes()[1].name(), "Sphere");
+    }
+
+    #[test]
+    fn round_trip_plane_and_settings() {
+        let mut world = World::new();
+        world.add_shape(Rc::new(Plane::new()));
+        world.set_soft_shadows(8, 0.5);
+        let loaded = round_trip(&world);
+        assert_eq!(loaded.shapes()[0].name(), "Plane");
+        assert_eq!(loaded.soft_shadow_samples(), 8);
+        assert_eq!(loaded.soft_shadow_radius(), 0.5);
+    }
+
+    #[test]
+    fn round_trip_material_flags() {
+        let mut world = World::new();
+        let mut plane = Plane::new();
+        plane.set_material(Material::builder().casts_shadow(false).build());
+        world.add_shape(Rc::new(plane));
+        let loaded = round_trip(&world);
+        assert!(!loaded.shapes()[0].material().casts_shadow);
+    }
+
+    #[test]
+    fn load_invalid_header() {
+        let bytes = b"NOPE\x01\x00\x00\x00";
+        assert!(matches!(
+            World::load(&mut &bytes[..]),
+            Err(SceneError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn load_unsupported_version() {
+        let bytes = b"TRST\x02\x00\x00\x00";
+        assert!(matches!(
+            World::load(&mut &bytes[..]),
+            Err(SceneError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn round_trip_light_intensity() {
+        let mut world = World::default_world();
+        world.lights_mut()[0] = Rc::new(
+            PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::WHITE).with_intensity(2.5),
+        );
+        let loaded = round_trip(&world);
+        assert_eq!(loaded.lights()[0].intensity(), 2.5);
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        assert_eq!(loaded.color_at(&ray), world.color_at(&ray));
+    }
+
+    /// Returns the default world written field by field.
+    fn default_world_bytes() -> Vec<u8> {
+        let world = World::default_world();
+        let mut bytes = MAGIC.to_vec();
+        let w = &mut bytes;
+        write_u32(w, VERSION).unwrap();
+
+        write_u32(w, 1).unwrap();
+        write_f64(w, 0.0).unwrap();
+        write_u32(w, 0).unwrap();
+        write_f64(w, 1.0).unwrap();
+        write_f64(w, 0.0).unwrap();
+        write_u32(w, 0).unwrap();
+
+        write_u32(w, 1).unwrap();
+        let light = &world.lights()[0];
+        write_tuple(w, light.position()).unwrap();
+        write_color(w, light.color()).unwrap();
+        for &value in &[1.0, 0.0, 0.0, 1.0] {
+            write_f64(w, value).unwrap();
+        }
+
+        write_u32(w, 2).unwrap();
+        for shape in world.shapes() {
+            w.push(SPHERE_TAG);
+            for i in 0..4 {
+                for j in 0..4 {
+                    write_f64(w, shape.transform()[[i, j]]).unwrap();
+                }
+            }
+            let material = shape.material();
+            write_color(w, material.texture.color_at(Tuple::ORIGIN)).unwrap();
+            for &value in &[
+                material.ambient,
+                material.diffuse,
+                material.specular,
+                material.shininess,
+                0.0,
+                0.0,
+                1.0,
+            ] {
+                write_f64(w, value).unwrap();
+            }
+            // Casts shadows, no emission, enabled.
+            w.push(1);
+            write_color(w, Color::BLACK).unwrap();
+            w.push(1);
+        }
+        bytes
+    }
+
+    #[test]
+    fn save_default_world_bytes() {
+        let mut bytes = Vec::new();
+        World::default_world().save(&mut bytes).unwrap();
+        assert_eq!(bytes, default_world_bytes());
+    }
+
+    #[test]
+    fn load_truncated() {
+        let mut bytes = default_world_bytes();
+        bytes.pop();
+        assert!(matches!(
+            World::load(&mut bytes.as_slice()),
+            Err(SceneError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn round_trip_world_settings() {
+        let mut world = World::default_world();
+        world.set_ambient_occlusion(4, 0.5);
+        world.set_scattering(0.02, 8);
+        world.set_shape_enabled(0, false);
+        let loaded = round_trip(&world);
+        assert_eq!(loaded.ao_samples(), 4);
+        assert_eq!(loaded.ao_radius(), 0.5);
+        assert_eq!(loaded.scattering_density(), 0.02);
+        assert_eq!(loaded.scattering_steps(), 8);
+        assert!(!loaded.is_shape_enabled(0));
+        assert!(loaded.is_shape_enabled(1));
+    }
+
+    #[test]
+    fn background_is_not_saved() {
+        let mut world = World::new();
+        world.set_background(Rc::new(SolidBackground(Color::RED)));
+        let loaded = round_trip(&world);
+        let ray = Ray::new(Tuple::ORIGIN, Tuple::vector(0.0, 0.0, 1.0));
+        assert_eq!(world.color_at(&ray), Color::RED);
+        assert_eq!(loaded.color_at(&ray), Color::BLACK);
+    }
+
+    #[test]
+    fn round_trip_emission() {
+        let mut world = World::new();
+        let mut sphere = Sphere::new();
+        sphere.set_material(
+            Material::builder()
+                .emission(Color::new(1.0, 0.5, 0.25))
+                .build(),
+        );
+        world.add_shape(Rc::new(sphere));
+        let loaded = round_trip(&world);
+        assert_eq!(
+            loaded.shapes()[0].material().emission,
+            Color::new(1.0, 0.5, 0.25)
+        );
+    }
+
+    #[test]
+    fn save_unsupported_shape() {
+        let mut world = World::new();
+        world.add_shape(Rc::new(Group::new()));
+        assert!(matches!(
+            world.save(&mut Vec::new()),
+            Err(SceneError::UnsupportedShape("Group"))
+        ));
+    }
+
+    #[test]
+    fn save_unsupported_texture() {
+        let mut world = World::new();
+        let mut sphere = Sphere::new();
+        sphere.set_material(
+            Material::builder()
+                .texture(Rc::new(Stripe::colors(Color::WHITE, Color::BLACK)))
+                .build(),
+        );
+        world.add_shape(Rc::new(sphere));
+        assert!(matches!(
+            world.save(&mut Vec::new()),
+            Err(SceneError::UnsupportedTexture("Stripe"))
+        ));
+    }
+}
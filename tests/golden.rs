@@ -0,0 +1,93 @@
+//! Renders the scenes of the examples at a low resolution and compares them with committed golden
+//! images in `tests/golden/`, to catch unintended changes to the renderer.
+//!
+//! When a change to the renderer is meant to change the images, regenerate them with
+//!
+//! ```text
+//! TRUSTER_BLESS=1 cargo test --test golden
+//! ```
+//!
+//! and check the new images before committing them.
+
+use std::fs::{self, File};
+use std::path::PathBuf;
+
+use truster::canvas::Canvas;
+use truster::color::Color;
+
+#[path = "../examples/simple_scene.rs"]
+#[allow(dead_code)]
+mod simple_scene;
+
+/// PPM files store 256 levels per channel, and rounding errors may push a channel to the next
+/// level. A real regression changes colors by much more.
+const TOLERANCE: f64 = 2.0 / 256.0;
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{}.ppm", name))
+}
+
+/// Parses a PPM file written by [Canvas::to_ppm]. Every channel is mapped to the middle of the
+/// range of colors which are written as it.
+fn parse_ppm(text: &str) -> Canvas {
+    let mut numbers = text
+        .split_whitespace()
+        .skip(1)
+        .map(|n| n.parse::<usize>().unwrap());
+    let width = numbers.next().unwrap();
+    let height = numbers.next().unwrap();
+    let maxval = numbers.next().unwrap() as f64;
+
+    let mut canvas = Canvas::new(width, height);
+    for pixel in canvas.as_mut_slice() {
+        let mut channel = || (numbers.next().unwrap() as f64 + 0.5) / (maxval + 1.0);
+        *pixel = Color::new(channel(), channel(), channel());
+    }
+    assert!(numbers.next().is_none(), "trailing data in PPM");
+    canvas
+}
+
+fn check_golden(name: &str, mut canvas: Canvas) {
+    let path = golden_path(name);
+    if std::env::var_os("TRUSTER_BLESS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        canvas.to_ppm(&mut File::create(&path).unwrap()).unwrap();
+        return;
+    }
+
+    let golden = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "can't read {}: {}. Run with TRUSTER_BLESS=1 to create it.",
+            path.display(),
+            err
+        )
+    });
+    let golden = parse_ppm(&golden);
+    assert_eq!(
+        (canvas.width(), canvas.height()),
+        (golden.width(), golden.height())
+    );
+
+    // The PPM clamps channels between 0 and 1.
+    for pixel in canvas.as_mut_slice() {
+        *pixel = pixel.map(|c| c.clamp(0.0, 1.0));
+    }
+    let diff = canvas.max_channel_diff(&golden);
+    assert!(
+        diff <= TOLERANCE,
+        "{} differs from {} by {} at pixel {:?}",
+        name,
+        path.display(),
+        diff,
+        canvas.first_diff(&golden, TOLERANCE)
+    );
+}
+
+#[test]
+fn simple_scene() {
+    let canvas = simple_scene::camera(25, 25).render(&simple_scene::world());
+    check_golden("simple_scene", canvas);
+}
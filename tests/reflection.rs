@@ -0,0 +1,50 @@
+//! Checks that textures show up in reflections, with the scene of the `reflective_stripes`
+//! example.
+
+use std::rc::Rc;
+
+use truster::color::Color;
+
+#[path = "../examples/reflective_stripes.rs"]
+#[allow(dead_code)]
+mod reflective_stripes;
+
+const HSIZE: usize = 40;
+const VSIZE: usize = 24;
+const REFLECTIVE: f64 = 0.4;
+
+/// Returns whether `added` looks like the orange ball: it is mostly red. The wall and the floor
+/// only have a little red in their stripes, so their reflections add more green or blue.
+fn is_ball_color(added: Color) -> bool {
+    added.r() > 0.1 && added.r() > added.g() && added.r() > added.b()
+}
+
+#[test]
+fn reflective_striped_floor_shows_ball() {
+    let matte_world = reflective_stripes::world(0.0);
+    let world = reflective_stripes::world(REFLECTIVE);
+    let floor = Rc::clone(&world.shapes()[0]);
+    let camera = reflective_stripes::camera(HSIZE, VSIZE);
+    let matte = camera.render(&matte_world);
+    let reflective = camera.render(&world);
+
+    let mut floor_pixels = 0;
+    let mut ball_pixels = 0;
+    for (x, y, &color) in reflective.enumerate_pixels() {
+        let on_floor = camera
+            .pick(&world, x, y)
+            .is_some_and(|shape| Rc::ptr_eq(&shape, &floor));
+        if !on_floor {
+            assert_eq!(color, matte[[x, y]], "pixel ({}, {})", x, y);
+            continue;
+        }
+        floor_pixels += 1;
+        if is_ball_color(color - matte[[x, y]]) {
+            ball_pixels += 1;
+        }
+    }
+
+    assert!(floor_pixels > 0);
+    assert!(ball_pixels > 0, "no floor pixel reflects the ball");
+    assert!(ball_pixels < floor_pixels);
+}